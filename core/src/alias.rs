@@ -0,0 +1,160 @@
+//! Named lists of traits, defined by `derive_where_alias!(Name = Clone,
+//! Debug)` and used as `Name!` among the traits of a `derive_where` attribute.
+//!
+//! Macros can't share state, so the definition is a `macro_rules!` of the
+//! same name, and an attribute using it re-emits the item through it. The
+//! alias then writes the attribute again with its traits in place of `Name!`,
+//! to be expanded like any other. `derive_where` has to be in scope for this.
+//!
+//! The rewritten attribute is written by the `macro_rules!`, so local variables
+//! of its expansion would resolve differently depending on where their tokens
+//! came from. It's marked with a leading `@alias`, and its whole expansion is
+//! resolved at the attribute instead.
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Ident, Result, Token, Visibility,
+};
+
+use crate::attr::{is_derive_where, DeriveWhere};
+
+/// Definition of an alias: `pub(crate) Name = Clone, Debug`.
+struct Alias {
+    vis: Visibility,
+    name: Ident,
+    traits: TokenStream,
+}
+
+impl Parse for Alias {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let traits: TokenStream = input.parse()?;
+
+        // The traits are checked once here instead of wherever they're used.
+        syn::parse2::<DeriveWhere>(quote! { ; #traits })?;
+
+        Ok(Self { vis, name, traits })
+    }
+}
+
+/// Generates the `macro_rules!` of an alias.
+pub fn generate(input: TokenStream) -> Result<TokenStream> {
+    let Alias { vis, name, traits } = syn::parse2(input)?;
+
+    let export = match &vis {
+        Visibility::Inherited => TokenStream::new(),
+        Visibility::Public(_) => {
+            return Err(Error::new(
+                name.span(),
+                "aliases can't be exported from the crate, use `pub(crate)` instead",
+            ))
+        }
+        vis => quote! {
+            #[allow(unused_imports)]
+            #vis use #name;
+        },
+    };
+
+    Ok(quote! {
+        macro_rules! #name {
+            (($($path:tt)*) ($($prefix:tt)*) ($($suffix:tt)*) $($item:tt)*) => {
+                #[$($path)*(@alias $($prefix)* #traits $($suffix)*)]
+                $($item)*
+            };
+        }
+
+        #export
+    })
+}
+
+/// Re-emits the item through the first alias used by the invoked attribute
+/// `attr` or another `derive_where` attribute of the item, if any.
+pub fn expand(attr: &TokenStream, item: &syn::DeriveInput) -> Option<TokenStream> {
+    if let Some((alias, prefix, suffix)) = split(attr) {
+        return Some(quote! {
+            #alias! { (derive_where) (#prefix) (#suffix) #item }
+        });
+    }
+
+    let (index, (alias, prefix, suffix)) =
+        item.attrs.iter().enumerate().find_map(|(index, attr)| {
+            if !is_derive_where(attr) {
+                return None;
+            }
+
+            match attr.tokens.clone().into_iter().next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                    split(&group.stream()).map(|split| (index, split))
+                }
+                _ => None,
+            }
+        })?;
+
+    let mut item = item.clone();
+    let path = item.attrs.remove(index).path;
+    // The invoked attribute is expanded by the one the alias writes, which is
+    // marked itself.
+    let (_, attr) = unmark(attr.clone());
+
+    Some(quote! {
+        #alias! { (#path) (#prefix) (#suffix) #[derive_where(#attr)] #item }
+    })
+}
+
+/// Splits the content of an attribute around the first alias among its
+/// traits, `Name!`.
+fn split(attr: &TokenStream) -> Option<(Ident, TokenStream, TokenStream)> {
+    let tokens: Vec<_> = attr.clone().into_iter().collect();
+    let traits = tokens
+        .iter()
+        .position(|token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ';'))?;
+
+    tokens
+        .windows(2)
+        .enumerate()
+        .skip(traits)
+        .find_map(|(index, pair)| match pair {
+            [TokenTree::Ident(alias), TokenTree::Punct(punct)] if punct.as_char() == '!' => Some((
+                alias.clone(),
+                tokens[..index].iter().cloned().collect(),
+                tokens[index + 2..].iter().cloned().collect(),
+            )),
+            _ => None,
+        })
+}
+
+/// Removes the mark of an attribute rewritten by an alias, returning whether it
+/// was there.
+pub fn unmark(attr: TokenStream) -> (bool, TokenStream) {
+    let mut tokens = attr.clone().into_iter();
+
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Punct(punct)), Some(TokenTree::Ident(ident)))
+            if punct.as_char() == '@' && ident == "alias" =>
+        {
+            (true, tokens.collect())
+        }
+        _ => (false, attr),
+    }
+}
+
+/// Resolves all tokens at the context of `span`, keeping their location.
+pub fn resolve_at(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut resolved = Group::new(group.delimiter(), resolve_at(group.stream(), span));
+                resolved.set_span(group.span());
+                token = TokenTree::Group(resolved);
+            }
+
+            token.set_span(token.span().resolved_at(span));
+            token
+        })
+        .collect()
+}
@@ -0,0 +1,58 @@
+//! Compile-time assertions that the item implements traits like `Send` and
+//! `Sync` under given bounds, requested by `assert(...)`, and that the fields
+//! implement the derived traits, requested by `assert_only`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, Path, Type, WherePredicate};
+
+/// Generic function naming the item with the traits as bounds, which fails to
+/// type-check if they aren't implemented. It's never called.
+pub fn generate(
+    ident: &Ident,
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+    traits: &[Path],
+) -> TokenStream {
+    let (impl_generics, type_generics, _) = generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn __assert<__T: ?::core::marker::Sized #(+ #traits)*>() {}
+
+            #[allow(dead_code)]
+            fn __assert_item #impl_generics ()
+                where #(#predicates),*
+            {
+                __assert::<#ident #type_generics>();
+            }
+        };
+    }
+}
+
+/// Generic function naming the types of the fields with the trait as bound,
+/// which fails to type-check if the impl of the trait wouldn't be satisfied by
+/// the bounds. It's never called.
+pub fn generate_fields(
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+    trait_: &Path,
+    types: &[&Type],
+) -> TokenStream {
+    let (impl_generics, ..) = generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn __assert<__T: ?::core::marker::Sized + #trait_>() {}
+
+            #[allow(dead_code)]
+            fn __assert_fields #impl_generics ()
+                where #(#predicates),*
+            {
+                #(__assert::<#types>();)*
+            }
+        };
+    }
+}
@@ -0,0 +1,1222 @@
+//! Parsing of `derive_where` attributes.
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use syn::{
+    parse::{discouraged::Speculative, Parse, ParseStream, Parser},
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token, Attribute, Data, DeriveInput, Error, Expr, ExprPath, GenericParam, Generics, Ident,
+    LitInt, LitStr, Path, PredicateType, Result, Token, Type, TypePath, WherePredicate,
+};
+
+#[cfg(feature = "tabled")]
+use crate::traits::FieldTabled;
+#[cfg(feature = "zeroize")]
+use crate::traits::FieldZeroize;
+use crate::{
+    data::Item,
+    traits::{DebugTemplate, FieldDebug, FieldToBits, IncomparableOrd, Traits},
+    warning::WARNINGS,
+};
+
+mod kw {
+    syn::custom_keyword!(assert);
+    syn::custom_keyword!(cfg);
+    syn::custom_keyword!(custom);
+    syn::custom_keyword!(export_bounds);
+}
+
+/// Parsed content of a single `derive_where` attribute: `bounds; traits`.
+pub struct DeriveWhere {
+    /// Condition the impls are compiled under, given before the bounds:
+    /// `cfg(feature = "debug-impls"), T: Debug; Debug`.
+    pub cfg: Option<TokenStream>,
+    /// Generic parameters the impls have in addition to those of the item,
+    /// declared before the bounds: `<U> T: PartialEq<U>; PartialEq<Foo<U>>`.
+    pub params: Punctuated<GenericParam, Token![,]>,
+    pub bounds: Punctuated<WherePredicate, Token![,]>,
+    pub traits: Vec<(Traits, Span)>,
+    /// Traits the item is asserted to implement under the bounds, like `Send`,
+    /// given by `assert(...)` among the traits.
+    pub asserts: Vec<Path>,
+    /// Name of a `macro_rules!` writing impls with the same generics and bounds
+    /// as the derived ones, given by `export_bounds(name)` among the traits.
+    pub export_bounds: Option<Ident>,
+}
+
+impl Parse for DeriveWhere {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut params = Punctuated::new();
+
+        // Not to be confused with a bound on a qualified path, like
+        // `<T as Trait>::Type: Bound`.
+        if input.peek(Token![<]) {
+            let fork = input.fork();
+
+            if let Ok(generics) = fork.parse::<Generics>() {
+                if !fork.peek(Token![::]) {
+                    input.advance_to(&fork);
+                    params = generics.params;
+                }
+            }
+        }
+
+        let mut cfg = None;
+
+        if input.peek(kw::cfg) && input.peek2(token::Paren) {
+            input.parse::<kw::cfg>()?;
+            let content;
+            syn::parenthesized!(content in input);
+            cfg = Some(content.parse()?);
+
+            if !input.peek(Token![;]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let mut bounds = Punctuated::new();
+
+        while !input.peek(Token![;]) {
+            bounds.push_value(input.parse()?);
+
+            if input.peek(Token![;]) {
+                break;
+            }
+
+            bounds.push_punct(input.parse()?);
+        }
+
+        input.parse::<Token![;]>()?;
+
+        let mut traits: Vec<(Traits, Span)> = Vec::new();
+        let mut asserts = Vec::new();
+        let mut export_bounds = None;
+
+        // Trailing separators are accepted to ease generating the attribute
+        // from other macros.
+        while !input.is_empty() && !input.peek(Token![;]) {
+            if input.peek(kw::assert) && input.peek2(token::Paren) {
+                input.parse::<kw::assert>()?;
+                asserts.extend(parse_asserts(input)?);
+            } else if input.peek(kw::export_bounds) && input.peek2(token::Paren) {
+                let keyword = input.parse::<kw::export_bounds>()?;
+
+                if export_bounds.is_some() {
+                    return Err(Error::new(keyword.span, "duplicate option"));
+                }
+
+                let content;
+                syn::parenthesized!(content in input);
+                export_bounds = Some(content.parse()?);
+            } else if input.peek(Ident) && input.peek2(Token![!]) {
+                return Err(
+                    input.error("trait aliases are only supported by the `derive_where` attribute")
+                );
+            } else if input.peek(kw::custom) && input.peek2(token::Paren) {
+                let span = input.span();
+                traits.push((Traits::Custom(input.parse()?), span));
+            } else {
+                let path: Path = input.parse()?;
+                let mut trait_ = Traits::from_path(&path)?;
+
+                if input.peek(token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    trait_ = trait_.parse_options(&content)?;
+                }
+
+                traits.push((trait_, path.span()));
+            }
+
+            if input.is_empty() || input.peek(Token![;]) {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        if traits.is_empty() && asserts.is_empty() && export_bounds.is_none() {
+            return Err(input.error("expected at least one trait"));
+        }
+
+        input.parse::<Option<Token![;]>>()?;
+
+        Ok(Self {
+            cfg,
+            params,
+            bounds,
+            traits,
+            asserts,
+            export_bounds,
+        })
+    }
+}
+
+impl DeriveWhere {
+    /// Generics of the impls: those of the item with the additional
+    /// parameters of the attribute.
+    pub fn generics(&self, generics: &Generics) -> Generics {
+        let mut generics = generics.clone();
+
+        // Lifetimes have to come first.
+        let mut lifetimes = 0;
+
+        for param in &self.params {
+            match param {
+                GenericParam::Lifetime(_) => {
+                    generics.params.insert(lifetimes, param.clone());
+                    lifetimes += 1;
+                }
+                _ => generics.params.push(param.clone()),
+            }
+        }
+
+        generics
+    }
+
+    /// Predicates of the impls: those of the item followed by the bounds of
+    /// the attribute.
+    pub fn predicates<'a>(&'a self, generics: &'a Generics) -> Vec<&'a WherePredicate> {
+        generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+            .chain(&self.bounds)
+            .collect()
+    }
+
+    /// Removes bounds on parameters neither the item nor the attribute has,
+    /// when the same attribute is applied to several items.
+    pub fn retain_bounds_for(&mut self, generics: &Generics) {
+        let params: Vec<_> = generics
+            .type_params()
+            .chain(self.params.iter().filter_map(|param| match param {
+                GenericParam::Type(param) => Some(param),
+                _ => None,
+            }))
+            .map(|param| param.ident.clone())
+            .collect();
+
+        self.bounds = std::mem::take(&mut self.bounds)
+            .into_pairs()
+            .filter(|pair| match pair.value() {
+                WherePredicate::Type(PredicateType {
+                    bounded_ty: Type::Path(TypePath { qself: None, path }),
+                    ..
+                }) => path.get_ident().is_none_or(|ident| params.contains(ident)),
+                _ => true,
+            })
+            .collect();
+    }
+
+    /// Checks that no trait is requested more than once, within one or across
+    /// several `derive_where` attributes of an item.
+    pub fn check_duplicates(derive_wheres: &[Self]) -> Result<()> {
+        let mut seen = Vec::new();
+
+        for (trait_, span) in derive_wheres.iter().flat_map(|d| &d.traits) {
+            if seen.iter().any(|seen| trait_.is_same_trait(seen)) {
+                return Err(Error::new(
+                    *span,
+                    format!("`{}` is already derived", trait_.ident()),
+                ));
+            }
+
+            seen.push(trait_.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Type parameters bounded by this attribute that aren't used by any field
+    /// its traits don't skip, so their bounds are unnecessary.
+    pub fn unused_bounds<'a>(&'a self, generics: &Generics, item: &Item) -> Vec<&'a Ident> {
+        // Custom traits might not be implemented for `PhantomData`, and bounds
+        // without traits are only exported.
+        if self.traits.is_empty()
+            || self
+                .traits
+                .iter()
+                .any(|(trait_, _)| matches!(trait_, Traits::Custom(_)))
+        {
+            return Vec::new();
+        }
+
+        self.bounds
+            .iter()
+            .filter_map(|predicate| match predicate {
+                WherePredicate::Type(PredicateType {
+                    bounded_ty: Type::Path(TypePath { qself: None, path }),
+                    ..
+                }) => path.get_ident(),
+                _ => None,
+            })
+            .filter(|ident| generics.type_params().any(|param| &param.ident == *ident))
+            .filter(|ident| {
+                !self
+                    .traits
+                    .iter()
+                    .any(|(trait_, _)| item.uses_type_param(ident, trait_))
+            })
+            .collect()
+    }
+
+    /// Checks that no requested trait is also derived by a `#[derive(...)]`
+    /// attribute of the item, which would cause conflicting impls.
+    pub fn check_std_derives(derive_wheres: &[Self], std_derives: &[Traits]) -> Result<()> {
+        // serde's and bevy_reflect's traits are implemented by their own
+        // derives, only with our bounds.
+        for (trait_, span) in derive_wheres
+            .iter()
+            .flat_map(|d| &d.traits)
+            .filter(|(trait_, _)| !trait_.is_external())
+        {
+            if std_derives
+                .iter()
+                .any(|derived| trait_.is_same_trait(derived))
+            {
+                return Err(Error::new(
+                    *span,
+                    format!(
+                        "`{}` is also derived by `#[derive(...)]`, remove it from one of them",
+                        trait_.ident()
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the supertraits of all requested traits that aren't requested
+    /// themselves or derived by `#[derive(...)]`, using the bounds of the
+    /// attribute that implied them.
+    pub fn add_implied(derive_wheres: &mut [Self], std_derives: &[Traits]) {
+        let mut derived: Vec<_> = derive_wheres
+            .iter()
+            .flat_map(|d| &d.traits)
+            .map(|(trait_, _)| trait_.clone())
+            .chain(std_derives.iter().cloned())
+            .collect();
+
+        for derive_where in derive_wheres {
+            let mut implied = Vec::new();
+
+            for (trait_, span) in &derive_where.traits {
+                for implied_trait in trait_.implied() {
+                    if !derived.contains(implied_trait) {
+                        derived.push(implied_trait.clone());
+                        implied.push((implied_trait.clone(), *span));
+                    }
+                }
+            }
+
+            derive_where.traits.extend(implied);
+        }
+    }
+}
+
+/// Item-level options, given by `derive_where` attributes without `;`.
+#[derive(Default)]
+pub struct Options {
+    /// Show each generated impl as a warning on the item while compiling.
+    pub debug_expansion: bool,
+    /// Derive supertraits of requested traits too.
+    pub implied_traits: bool,
+    /// Generate `VARIANTS` and `variant_name()` for enums.
+    pub variant_names: bool,
+    /// Generate `variants()` for enums without fields.
+    pub variant_iter: bool,
+    /// Generate `is_*()`, `as_*()` and `into_*()` for each variant of enums.
+    pub accessors: bool,
+    /// Generate `new()` for structs, taking all fields not marked `default`.
+    pub constructor: bool,
+    /// Generate `const_eq()` and `const_cmp()`, mirroring `PartialEq` and
+    /// `Ord` in const contexts.
+    pub const_fns: bool,
+    /// How `Ord` orders variants marked `incomparable`, given by
+    /// `incomparable_ord = last` or `incomparable_ord = panic`.
+    pub incomparable_ord: Option<IncomparableOrd>,
+    /// Skip fields skipped by `PartialEq` in serde's derives too, given by
+    /// `serde_skip`.
+    #[cfg(feature = "serde")]
+    pub serde_skip: bool,
+    /// Name of the item in serde's derives, given by `rename = "name"`.
+    #[cfg(feature = "serde")]
+    pub rename: Option<LitStr>,
+    /// Case serde's derives rename fields or variants to, given by
+    /// `rename_all = "camelCase"`.
+    #[cfg(feature = "serde")]
+    pub rename_all: Option<LitStr>,
+    /// Field holding the variant name in serde's derives, given by
+    /// `tag = "type"`.
+    #[cfg(feature = "serde")]
+    pub tag: Option<LitStr>,
+    /// Field holding the content of the variant next to its `tag` in serde's
+    /// derives, given by `content = "value"`.
+    #[cfg(feature = "serde")]
+    pub content: Option<LitStr>,
+    /// Represent variants by their content alone in serde's derives, given by
+    /// `untagged`.
+    #[cfg(feature = "serde")]
+    pub untagged: bool,
+    /// Traits the item is asserted to implement under its own bounds, like
+    /// `Send`, given by `assert(...)`.
+    pub asserts: Vec<Path>,
+    /// Return early from `PartialEq`, `PartialOrd` and `Ord` when comparing a
+    /// value with itself, given by `ptr_eq`.
+    pub ptr_eq: bool,
+    /// Generate no impls, only check that the fields implement the traits
+    /// under the bounds, given by `assert_only`. For traits implemented by
+    /// hand, but with the bounds `derive_where` would use.
+    pub assert_only: bool,
+    /// Make fields skipped inconsistently by traits that have to agree, like
+    /// `Hash` and `PartialEq`, an error instead of a warning.
+    pub strict_skip: bool,
+    /// Expression of `self` that comparisons and hashing use instead of the
+    /// fields, given by `key = expr` or `via = function`.
+    pub key: Option<Expr>,
+    /// Type in another crate the item mirrors the shape of, given by
+    /// `remote = path::Type`. The item is replaced by a newtype wrapping it.
+    pub remote: Option<Path>,
+    /// Tag hashed before the fields, given by `hash_tag = "tag"` or `hash_tag`
+    /// for the name of the item.
+    pub hash_tag: Option<Option<LitStr>>,
+    /// Rendering of `Debug`, given by `debug_template(...)`.
+    pub debug_template: Option<DebugTemplate>,
+    /// Render the discriminant of enum variants after their name in `Debug`,
+    /// like `Variant (= 3)`.
+    pub debug_discriminant: bool,
+    /// Render fields in `Debug` sorted by name instead of in declaration
+    /// order, given by `debug_sorted`.
+    pub debug_sorted: bool,
+    /// Render the types of generic parameters after the name in `Debug`, like
+    /// `Wrapper<u32>`, given by `debug_type_names`.
+    pub debug_type_names: bool,
+    /// Oldest Rust version the generated code has to compile with, given by
+    /// `msrv = "1.56"`. Newer language and library features are avoided or
+    /// rejected accordingly.
+    pub msrv: Option<Msrv>,
+    /// Document the bounds on the generated impls, given by `doc_bounds`.
+    pub doc_bounds: bool,
+    /// Hide the generated impls from the documentation, given by
+    /// `doc_hidden`. Takes precedence over `doc_bounds`.
+    pub doc_hidden: bool,
+    /// Understand field attributes of `derivative` and `educe` and remove
+    /// those without other options from the item, given by `compat`.
+    pub compat: bool,
+    /// Implement `StructuralPartialEq` along with `PartialEq`, so constants of
+    /// the item can be used as patterns, given by `structural_match`. Needs
+    /// `#![feature(structural_match)]` on nightly.
+    #[cfg(feature = "nightly")]
+    pub structural_match: bool,
+    /// Warnings not to emit for the item, given by
+    /// `allow(unused_bound, inconsistent_skip)`.
+    pub allow: Vec<Ident>,
+    /// Paths of renamed or re-exported integration crates, given by
+    /// `<crate>_crate = path`, like `zeroize_crate = my_zeroize`.
+    pub crates: Vec<(String, Path)>,
+}
+
+/// Integration crates whose path can be overridden.
+const CRATES: &[&str] = &[
+    #[cfg(feature = "arbitrary")]
+    "arbitrary",
+    #[cfg(feature = "bevy_reflect")]
+    "bevy_reflect",
+    #[cfg(feature = "clap")]
+    "clap",
+    #[cfg(feature = "diesel")]
+    "diesel",
+    #[cfg(feature = "fake")]
+    "fake",
+    #[cfg(feature = "minicbor")]
+    "minicbor",
+    #[cfg(feature = "parity-scale-codec")]
+    "parity_scale_codec",
+    #[cfg(feature = "serde")]
+    "serde",
+    #[cfg(feature = "speedy")]
+    "speedy",
+    #[cfg(feature = "sqlx")]
+    "sqlx",
+    #[cfg(feature = "tabled")]
+    "tabled",
+    #[cfg(feature = "zerocopy")]
+    "zerocopy",
+    #[cfg(feature = "zeroize")]
+    "zeroize",
+];
+
+impl Options {
+    fn parse_into(&mut self, input: ParseStream) -> Result<()> {
+        if input.is_empty() {
+            return Err(input.error("expected at least one option"));
+        }
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            match ident.to_string().as_str() {
+                "debug_expansion" => Self::set(&mut self.debug_expansion, &ident)?,
+                "implied_traits" => Self::set(&mut self.implied_traits, &ident)?,
+                "variant_names" => Self::set(&mut self.variant_names, &ident)?,
+                "variant_iter" => Self::set(&mut self.variant_iter, &ident)?,
+                "accessors" => Self::set(&mut self.accessors, &ident)?,
+                "constructor" => Self::set(&mut self.constructor, &ident)?,
+                "const_fns" => Self::set(&mut self.const_fns, &ident)?,
+                "strict_skip" => Self::set(&mut self.strict_skip, &ident)?,
+                "ptr_eq" => Self::set(&mut self.ptr_eq, &ident)?,
+                "assert_only" => Self::set(&mut self.assert_only, &ident)?,
+                #[cfg(feature = "serde")]
+                "serde_skip" => Self::set(&mut self.serde_skip, &ident)?,
+                #[cfg(feature = "serde")]
+                "rename" => Self::set_str(&mut self.rename, &ident, input)?,
+                #[cfg(feature = "serde")]
+                "rename_all" => Self::set_str(&mut self.rename_all, &ident, input)?,
+                #[cfg(feature = "serde")]
+                "tag" => Self::set_str(&mut self.tag, &ident, input)?,
+                #[cfg(feature = "serde")]
+                "content" => Self::set_str(&mut self.content, &ident, input)?,
+                #[cfg(feature = "serde")]
+                "untagged" => Self::set(&mut self.untagged, &ident)?,
+                "debug_discriminant" => Self::set(&mut self.debug_discriminant, &ident)?,
+                "debug_sorted" => Self::set(&mut self.debug_sorted, &ident)?,
+                "debug_type_names" => Self::set(&mut self.debug_type_names, &ident)?,
+                "doc_bounds" => Self::set(&mut self.doc_bounds, &ident)?,
+                "doc_hidden" => Self::set(&mut self.doc_hidden, &ident)?,
+                "compat" => Self::set(&mut self.compat, &ident)?,
+                #[cfg(feature = "nightly")]
+                "structural_match" => Self::set(&mut self.structural_match, &ident)?,
+                "key" | "via" => {
+                    if self.key.is_some() {
+                        return Err(Error::new(
+                            ident.span(),
+                            "only one of `key` and `via` can be given",
+                        ));
+                    }
+
+                    input.parse::<Token![=]>()?;
+
+                    self.key = Some(if ident == "key" {
+                        input.parse()?
+                    } else {
+                        // `via = path` is `key = path(self)`.
+                        let path: ExprPath = input.parse()?;
+                        parse_quote!(#path(self))
+                    });
+                }
+                "hash_tag" => {
+                    if self.hash_tag.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.hash_tag = Some(if input.parse::<Option<Token![=]>>()?.is_some() {
+                        Some(input.parse()?)
+                    } else {
+                        None
+                    });
+                }
+                "allow" => {
+                    if !self.allow.is_empty() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.allow = parse_allow(input)?;
+                }
+                "assert" => {
+                    if !self.asserts.is_empty() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.asserts = parse_asserts(input)?;
+                }
+                "incomparable_ord" => {
+                    if self.incomparable_ord.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    self.incomparable_ord = Some(input.parse()?);
+                }
+                "msrv" => {
+                    if self.msrv.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    self.msrv = Some(input.parse()?);
+                }
+                "debug_template" => {
+                    if self.debug_template.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    let content;
+                    syn::parenthesized!(content in input);
+                    self.debug_template = Some(content.parse()?);
+                }
+                "remote" => {
+                    if self.remote.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    let path: Path = input.parse()?;
+
+                    if path
+                        .segments
+                        .iter()
+                        .any(|segment| !segment.arguments.is_empty())
+                    {
+                        return Err(Error::new(
+                            path.span(),
+                            "expected a path without generic arguments, they are taken from the item",
+                        ));
+                    }
+
+                    self.remote = Some(path);
+                }
+                option => match option.strip_suffix("_crate") {
+                    Some(krate) if CRATES.contains(&krate) => {
+                        if self.crates.iter().any(|(other, _)| other == krate) {
+                            return Err(Error::new(ident.span(), "duplicate option"));
+                        }
+
+                        input.parse::<Token![=]>()?;
+                        self.crates.push((String::from(krate), input.parse()?));
+                    }
+                    _ => return Err(Error::new(ident.span(), "unsupported option")),
+                },
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(())
+    }
+
+    /// Path of the crate `krate`, `::krate` unless it's an integration crate
+    /// overridden by `<krate>_crate = path`.
+    pub fn crate_path(&self, krate: &str) -> Path {
+        match self.crates.iter().find(|(other, _)| other == krate) {
+            Some((_, path)) => path.clone(),
+            None => {
+                let mut path = Path::from(Ident::new(krate, Span::call_site()));
+                path.leading_colon = Some(Default::default());
+                path
+            }
+        }
+    }
+
+    /// Returns `true` if the warning `name` isn't allowed by `allow(...)`.
+    pub fn warns(&self, name: &str) -> bool {
+        !self.allow.iter().any(|allowed| allowed == name)
+    }
+
+    /// Returns `true` if the generated code may use features stabilized in
+    /// Rust `1.minor`.
+    pub fn supports(&self, minor: u32) -> bool {
+        self.msrv.is_none_or(|msrv| msrv.minor >= minor)
+    }
+
+    fn set(option: &mut bool, ident: &Ident) -> Result<()> {
+        if *option {
+            return Err(Error::new(ident.span(), "duplicate option"));
+        }
+
+        *option = true;
+
+        Ok(())
+    }
+
+    /// Sets an index given as `ident(n)`.
+    #[cfg(any(feature = "minicbor", feature = "parity-scale-codec"))]
+    fn set_index(option: &mut Option<LitInt>, ident: &Ident, input: ParseStream) -> Result<()> {
+        if option.is_some() {
+            return Err(Error::new(ident.span(), "duplicate option"));
+        }
+
+        let content;
+        syn::parenthesized!(content in input);
+        let index: LitInt = content.parse()?;
+        index.base10_parse::<u32>()?;
+        *option = Some(index);
+
+        Ok(())
+    }
+
+    /// Sets a string option given as `ident = "..."`.
+    #[cfg(any(feature = "serde", feature = "tabled"))]
+    fn set_str(option: &mut Option<LitStr>, ident: &Ident, input: ParseStream) -> Result<()> {
+        if option.is_some() {
+            return Err(Error::new(ident.span(), "duplicate option"));
+        }
+
+        input.parse::<Token![=]>()?;
+        *option = Some(input.parse()?);
+
+        Ok(())
+    }
+}
+
+/// Rust version given by the `msrv` option, like `"1.56"` or `"1.56.1"`.
+#[derive(Clone, Copy)]
+pub struct Msrv {
+    pub minor: u32,
+}
+
+impl Parse for Msrv {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let version: LitStr = input.parse()?;
+        let error = || Error::new(version.span(), "expected a version like \"1.56\"");
+
+        let value = version.value();
+        let mut parts = value.split('.');
+
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some("1"), Some(minor), patch, None)
+                if patch.is_none_or(|patch| patch.parse::<u32>().is_ok()) =>
+            {
+                Ok(Self {
+                    minor: minor.parse().map_err(|_| error())?,
+                })
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+/// All `derive_where` attributes of an item.
+#[derive(Default)]
+pub struct Attributes {
+    pub derive_wheres: Vec<DeriveWhere>,
+    pub options: Options,
+}
+
+impl Attributes {
+    pub fn parse_attr(&mut self, input: ParseStream) -> Result<()> {
+        let has_semicolon = input
+            .fork()
+            .parse::<TokenStream>()?
+            .into_iter()
+            .any(|token| matches!(token, TokenTree::Punct(p) if p.as_char() == ';'));
+
+        if has_semicolon {
+            self.derive_wheres.push(input.parse()?);
+            Ok(())
+        } else {
+            self.options.parse_into(input)
+        }
+    }
+}
+
+/// Parses the traits of `assert(...)`, any paths like `Send` or `Sync`.
+fn parse_asserts(input: ParseStream) -> Result<Vec<Path>> {
+    let content;
+    syn::parenthesized!(content in input);
+    let traits = Punctuated::<Path, Token![,]>::parse_terminated(&content)?;
+
+    if traits.is_empty() {
+        return Err(content.error("expected at least one trait"));
+    }
+
+    Ok(traits.into_iter().collect())
+}
+
+fn parse_allow(input: ParseStream) -> Result<Vec<Ident>> {
+    let content;
+    syn::parenthesized!(content in input);
+    let warnings = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+
+    if warnings.is_empty() {
+        return Err(content.error("expected at least one warning"));
+    }
+
+    for warning in &warnings {
+        if !WARNINGS.iter().any(|name| warning == name) {
+            return Err(Error::new(warning.span(), "unknown warning"));
+        }
+    }
+
+    Ok(warnings.into_iter().collect())
+}
+
+/// Recognizes `derive_where` attributes by their last path segment, so paths
+/// like `$crate::derive_where` from macros or re-exports are merged too.
+pub fn is_derive_where(attr: &Attribute) -> bool {
+    is_derive_where_path(&attr.path)
+}
+
+fn is_derive_where_path(path: &Path) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "derive_where")
+}
+
+/// Removes all `derive_where` attributes from the item, its variants and its
+/// fields, as they are consumed by this macro.
+pub fn strip_derive_where(item: &mut DeriveInput) {
+    fn strip(attrs: &mut Vec<Attribute>) {
+        attrs.retain(|attr| !is_derive_where(attr));
+    }
+
+    strip(&mut item.attrs);
+
+    match &mut item.data {
+        Data::Struct(data) => data.fields.iter_mut().for_each(|f| strip(&mut f.attrs)),
+        Data::Enum(data) => {
+            for variant in &mut data.variants {
+                strip(&mut variant.attrs);
+                variant.fields.iter_mut().for_each(|f| strip(&mut f.attrs));
+            }
+        }
+        Data::Union(data) => data
+            .fields
+            .named
+            .iter_mut()
+            .for_each(|f| strip(&mut f.attrs)),
+    }
+}
+
+/// Removes all `derive_where` attributes, and those `strip` returns `true`
+/// for, from the tokens of an item. `strip` is also given the number of
+/// groups the attribute is nested in, e.g. 1 for fields of a struct. The other
+/// tokens are kept as they are, with their exact spans, unlike when reparsing
+/// the item.
+pub fn strip_derive_where_tokens(
+    tokens: TokenStream,
+    strip: &dyn Fn(&Attribute, usize) -> bool,
+) -> TokenStream {
+    strip_tokens(tokens, strip, 0)
+}
+
+fn strip_tokens(
+    tokens: TokenStream,
+    strip: &dyn Fn(&Attribute, usize) -> bool,
+    depth: usize,
+) -> TokenStream {
+    let tokens: Vec<_> = tokens.into_iter().collect();
+    let mut stripped = Vec::with_capacity(tokens.len());
+    let mut index = 0;
+
+    while index < tokens.len() {
+        if let (TokenTree::Punct(punct), Some(TokenTree::Group(group))) =
+            (&tokens[index], tokens.get(index + 1))
+        {
+            if punct.as_char() == '#' && group.delimiter() == Delimiter::Bracket {
+                let attrs = Attribute::parse_outer.parse2(
+                    [tokens[index].clone(), tokens[index + 1].clone()]
+                        .into_iter()
+                        .collect(),
+                );
+
+                if let Ok([attr]) = attrs.as_deref() {
+                    if is_derive_where_path(&attr.path) || strip(attr, depth) {
+                        index += 2;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        stripped.push(match &tokens[index] {
+            TokenTree::Group(group) => {
+                let mut stripped = Group::new(
+                    group.delimiter(),
+                    strip_tokens(group.stream(), strip, depth + 1),
+                );
+                stripped.set_span(group.span());
+                TokenTree::Group(stripped)
+            }
+            token => token.clone(),
+        });
+        index += 1;
+    }
+
+    stripped.into_iter().collect()
+}
+
+/// Options of a variant, given by `derive_where` attributes on it.
+#[derive(Default)]
+pub struct VariantOptions {
+    /// Text `Display` writes for the variant instead of its name, given by
+    /// `display = "text"`.
+    pub display: Option<LitStr>,
+    /// Never equal to or ordered with any value, given by `incomparable`.
+    pub incomparable: bool,
+    /// Variants `PartialOrd` doesn't order this variant with, in either
+    /// direction, given by `incomparable_with(Variant, ...)`.
+    pub incomparable_with: Vec<Ident>,
+    /// Index of the variant in the encodings of minicbor and
+    /// parity-scale-codec, given by `index(n)`.
+    #[cfg(any(feature = "minicbor", feature = "parity-scale-codec"))]
+    pub index: Option<LitInt>,
+    /// Name of the variant in serde's derives, given by `rename = "name"`.
+    #[cfg(feature = "serde")]
+    pub rename: Option<LitStr>,
+    /// Case serde's derives rename the fields of the variant to, given by
+    /// `rename_all = "camelCase"`.
+    #[cfg(feature = "serde")]
+    pub rename_all: Option<LitStr>,
+}
+
+impl VariantOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs.iter().filter(|attr| is_derive_where(attr)) {
+            attr.parse_args_with(|input: ParseStream| options.parse_into(input))?;
+        }
+
+        Ok(options)
+    }
+
+    fn parse_into(&mut self, input: ParseStream) -> Result<()> {
+        if input.is_empty() {
+            return Err(input.error("expected at least one option"));
+        }
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            match ident.to_string().as_str() {
+                "display" => {
+                    if self.display.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    self.display = Some(input.parse()?);
+                }
+                "incomparable" => Options::set(&mut self.incomparable, &ident)?,
+                "incomparable_with" => {
+                    if !self.incomparable_with.is_empty() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let variants = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+
+                    if variants.is_empty() {
+                        return Err(Error::new(ident.span(), "expected at least one variant"));
+                    }
+
+                    self.incomparable_with = variants.into_iter().collect();
+                }
+                #[cfg(any(feature = "minicbor", feature = "parity-scale-codec"))]
+                "index" => Options::set_index(&mut self.index, &ident, input)?,
+                #[cfg(feature = "serde")]
+                "rename" => Options::set_str(&mut self.rename, &ident, input)?,
+                #[cfg(feature = "serde")]
+                "rename_all" => Options::set_str(&mut self.rename_all, &ident, input)?,
+                _ => return Err(Error::new(ident.span(), "unsupported option")),
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Options of a field, given by `derive_where` attributes on it.
+#[derive(Default)]
+pub struct FieldOptions {
+    /// Traits implemented only in terms of this field.
+    pub delegate: Vec<(Traits, Span)>,
+    /// Priority of the field when ordering, given by `ord(n)`: fields with a
+    /// priority are compared first, lowest first, then the others in
+    /// declaration order.
+    pub ord: Option<u32>,
+    /// Order `None` after `Some` for this `Option` field, given by
+    /// `none_last`.
+    pub none_last: bool,
+    /// Set the field to its default in the generated constructor instead of
+    /// taking it as a parameter, given by `default`.
+    pub default: bool,
+    /// Rendering of the field by `Debug`, given by `debug(...)`.
+    pub debug: Option<FieldDebug>,
+    /// Tolerance `PartialEq` compares this field with, given by
+    /// `epsilon = 1e-9`: values are equal if they differ by at most it.
+    pub epsilon: Option<Expr>,
+    /// Compare and hash this `Vec` or slice field as a multiset, regardless of
+    /// the order of its elements, given by `unordered`.
+    pub unordered: bool,
+    /// Compare, order and hash this string field ignoring ASCII case, given by
+    /// `case_insensitive`.
+    pub case_insensitive: bool,
+    /// The type of this field has no values, like an empty enum, given by
+    /// `uninhabited`. Fields of type `!` are recognized without it.
+    pub uninhabited: bool,
+    /// Hashing of this float field by its bits, given by `to_bits` or
+    /// `to_bits(...)`.
+    pub to_bits: Option<FieldToBits>,
+    /// Implement all delegatable traits only in terms of this field, given
+    /// by `transparent`. Fields of `#[repr(transparent)]` structs are picked
+    /// automatically if all others are `PhantomData`.
+    pub transparent: bool,
+    /// Module implementing the traits for this field instead of its own
+    /// impls, given by `with = module`: with `clone`, `eq`, `hash`, `fmt` and
+    /// `cmp` functions for those traits that are derived.
+    pub with: Option<Path>,
+    /// Traits implemented without this field.
+    pub skip: Vec<(Traits, Span)>,
+    /// Zeroization of the field, given by `zeroize(...)`.
+    #[cfg(feature = "zeroize")]
+    pub zeroize: Option<FieldZeroize>,
+    /// Index of the field in minicbor's encoding, given by `index(n)`.
+    #[cfg(feature = "minicbor")]
+    pub index: Option<LitInt>,
+    /// Rendering of the field by `Tabled`, given by `tabled(...)`.
+    #[cfg(feature = "tabled")]
+    pub tabled: Option<FieldTabled>,
+    /// Name of the field in serde's derives and in the headers of `Tabled`,
+    /// given by `rename = "name"`.
+    #[cfg(any(feature = "serde", feature = "tabled"))]
+    pub rename: Option<LitStr>,
+}
+
+impl FieldOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs.iter().filter(|attr| is_derive_where(attr)) {
+            attr.parse_args_with(|input: ParseStream| options.parse_into(input))?;
+        }
+
+        if let Some(with) = &options.with {
+            if options.epsilon.is_some()
+                || options.to_bits.is_some()
+                || options.debug.is_some()
+                || options.none_last
+                || options.unordered
+                || options.case_insensitive
+            {
+                return Err(Error::new(
+                    with.span(),
+                    "`with` can't be combined with other options changing how the field is handled",
+                ));
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn parse_into(&mut self, input: ParseStream) -> Result<()> {
+        if input.is_empty() {
+            return Err(input.error("expected at least one option"));
+        }
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            match ident.to_string().as_str() {
+                "delegate" => Self::parse_traits(
+                    &mut self.delegate,
+                    &ident,
+                    input,
+                    |trait_| trait_.is_delegatable() || trait_.is_wrapper(),
+                    "can't be delegated to a field",
+                )?,
+                "skip" => Self::parse_traits(
+                    &mut self.skip,
+                    &ident,
+                    input,
+                    Traits::is_skippable,
+                    "can't be skipped",
+                )?,
+                "ord" => {
+                    if self.ord.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    let content;
+                    syn::parenthesized!(content in input);
+                    self.ord = Some(content.parse::<LitInt>()?.base10_parse()?);
+                }
+                "none_last" => {
+                    if self.none_last {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.none_last = true;
+                }
+                "default" => {
+                    if self.default {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.default = true;
+                }
+                "debug" => {
+                    if self.debug.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    let content;
+                    syn::parenthesized!(content in input);
+                    self.debug = Some(content.parse()?);
+                }
+                "epsilon" => {
+                    if self.epsilon.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    self.epsilon = Some(input.parse()?);
+                }
+                "unordered" => {
+                    if self.unordered {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.unordered = true;
+                }
+                "case_insensitive" => {
+                    if self.case_insensitive {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.case_insensitive = true;
+                }
+                "uninhabited" => {
+                    if self.uninhabited {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.uninhabited = true;
+                }
+                "transparent" => {
+                    if self.transparent {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.transparent = true;
+                }
+                "with" => {
+                    if self.with.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    self.with = Some(input.parse()?);
+                }
+                "to_bits" => {
+                    if self.to_bits.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    self.to_bits = Some(if input.peek(token::Paren) {
+                        let content;
+                        syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        FieldToBits::default()
+                    });
+                }
+                #[cfg(feature = "zeroize")]
+                "zeroize" => {
+                    if self.zeroize.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    let content;
+                    syn::parenthesized!(content in input);
+                    self.zeroize = Some(content.parse()?);
+                }
+                #[cfg(feature = "minicbor")]
+                "index" => Options::set_index(&mut self.index, &ident, input)?,
+                #[cfg(feature = "tabled")]
+                "tabled" => {
+                    if self.tabled.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    let content;
+                    syn::parenthesized!(content in input);
+                    self.tabled = Some(content.parse()?);
+                }
+                #[cfg(any(feature = "serde", feature = "tabled"))]
+                "rename" => Options::set_str(&mut self.rename, &ident, input)?,
+                _ => return Err(Error::new(ident.span(), "unsupported option")),
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(())
+    }
+
+    /// Parses the traits of `delegate(...)` or `skip(...)` into `traits`,
+    /// which only accepts those `supported` returns `true` for.
+    fn parse_traits(
+        traits: &mut Vec<(Traits, Span)>,
+        ident: &Ident,
+        input: ParseStream,
+        supported: fn(&Traits) -> bool,
+        unsupported: &str,
+    ) -> Result<()> {
+        if !traits.is_empty() {
+            return Err(Error::new(ident.span(), "duplicate option"));
+        }
+
+        let content;
+        syn::parenthesized!(content in input);
+
+        for path in Punctuated::<Path, Token![,]>::parse_terminated(&content)? {
+            let trait_ = Traits::from_path(&path)?;
+
+            if !supported(&trait_) {
+                return Err(Error::new(
+                    path.span(),
+                    format!("`{}` {}", trait_.ident(), unsupported),
+                ));
+            }
+
+            if traits.iter().any(|(existing, _)| existing == &trait_) {
+                return Err(Error::new(path.span(), "duplicate trait"));
+            }
+
+            traits.push((trait_, path.span()));
+        }
+
+        if traits.is_empty() {
+            return Err(Error::new(ident.span(), "expected at least one trait"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Supported traits derived by `#[derive(...)]` attributes of the item.
+///
+/// Only attributes placed after `derive_where` are part of its input, earlier
+/// ones can't be checked.
+pub fn std_derives(attrs: &[Attribute]) -> Vec<Traits> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        // Malformed attributes are left for the compiler to report.
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter_map(|path| Traits::from_path(&path).ok())
+        .collect()
+}
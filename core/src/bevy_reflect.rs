@@ -0,0 +1,83 @@
+//! Bounds for bevy_reflect's own derive: `Reflect` and `FromReflect` aren't
+//! implemented, but the bounds of their `derive_where` are attached to the
+//! item as `#[reflect(where ...)]`, and `#[reflect(no_field_bounds)]` keeps
+//! bevy_reflect from adding bounds on the types of fields. Its bound of
+//! `TypePath` on every type parameter remains.
+//!
+//! Without `FromReflect`, `#[reflect(from_reflect = false)]` is added. Fields
+//! skipped with `skip(Reflect)` are marked `#[reflect(ignore)]`, and so are
+//! `PhantomData` fields, which bevy_reflect doesn't implement its traits for.
+
+use quote::ToTokens;
+use syn::{parse_quote, spanned::Spanned, Attribute, Error, Result};
+
+use crate::{attr::DeriveWhere, data::Item, traits::Traits};
+
+/// `#[reflect(...)]` for the bevy_reflect traits of all `derive_wheres`, if
+/// any.
+pub fn generate_bounds(derive_wheres: &[DeriveWhere]) -> Result<Vec<Attribute>> {
+    let mut predicates = Vec::new();
+    let (mut reflect, mut from_reflect) = (None, None);
+
+    for derive_where in derive_wheres {
+        for (trait_, span) in &derive_where.traits {
+            match trait_ {
+                Traits::Reflect => reflect = Some(*span),
+                Traits::FromReflect => from_reflect = Some(*span),
+                _ => continue,
+            }
+
+            if derive_where.cfg.is_some() {
+                return Err(Error::new(
+                    *span,
+                    "bevy_reflect traits can't be derived under a `cfg`",
+                ));
+            }
+
+            if let Some(param) = derive_where.params.first() {
+                return Err(Error::new(
+                    param.span(),
+                    "bevy_reflect bounds can't use additional generic parameters",
+                ));
+            }
+
+            predicates.extend(derive_where.bounds.iter().map(ToTokens::to_token_stream));
+        }
+    }
+
+    let mut attrs = Vec::new();
+
+    match (reflect, from_reflect) {
+        (None, None) => return Ok(attrs),
+        (None, Some(span)) => return Err(Error::new(
+            span,
+            "`FromReflect` is derived by bevy_reflect's derive of `Reflect`, derive `Reflect` too",
+        )),
+        (Some(_), None) => attrs.push(parse_quote!(#[reflect(from_reflect = false)])),
+        (Some(_), Some(_)) => (),
+    }
+
+    attrs.push(parse_quote!(#[reflect(no_field_bounds)]));
+
+    if !predicates.is_empty() {
+        attrs.push(parse_quote!(#[reflect(where #(#predicates),*)]));
+    }
+
+    Ok(attrs)
+}
+
+/// `#[reflect(ignore)]` for each field skipped by `Reflect` or of type
+/// `PhantomData`, with the index of its struct or variant and its own index.
+pub fn generate_ignores(item: &Item) -> Vec<(usize, usize, Attribute)> {
+    item.datas()
+        .iter()
+        .enumerate()
+        .flat_map(|(data_index, data)| {
+            data.fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| field.skips(&Traits::Reflect) || field.is_phantom_data())
+                .map(move |(index, _)| (data_index, index, parse_quote!(#[reflect(ignore)])))
+        })
+        .collect()
+}
@@ -0,0 +1,129 @@
+//! Field attributes of `derivative` and `educe` understood with the `compat`
+//! option, so items can be migrated without rewriting them at once.
+//!
+//! Ignoring a field, like `#[derivative(Debug = "ignore")]` or
+//! `#[educe(Debug(ignore))]`, skips it. Bounds, like
+//! `#[derivative(Debug(bound = "T: Debug"))]`, are dropped, as the bounds of
+//! `derive_where` apply instead. The attributes are removed from the item,
+//! unless they have other options, like `#[derivative(Default(value = "1"))]`,
+//! which are left to a remaining `#[derive(Derivative)]` or `#[derive(Educe)]`.
+
+use syn::{
+    spanned::Spanned, Attribute, DeriveInput, Error, Lit, Meta, MetaNameValue, NestedMeta, Path,
+    Result,
+};
+
+use crate::{data::Item, traits::Traits};
+
+/// Returns `true` for `#[derivative(...)]` and `#[educe(...)]` attributes.
+fn is_compat(attr: &Attribute) -> bool {
+    attr.path.is_ident("derivative") || attr.path.is_ident("educe")
+}
+
+/// Returns `true` for `derivative` and `educe` attributes removed from the
+/// item, those with no options other than `ignore` and `bound`.
+pub fn is_stripped(attr: &Attribute) -> bool {
+    is_compat(attr)
+        && match attr.parse_meta() {
+            Ok(Meta::List(list)) => list
+                .nested
+                .into_iter()
+                .all(|nested| matches!(parse_trait(nested), Ok((_, true)))),
+            _ => true,
+        }
+}
+
+/// Adds the fields skipped by `derivative` and `educe` attributes to their
+/// options.
+pub fn apply(item: &mut Item) -> Result<()> {
+    for data in item.datas_mut() {
+        for field in &mut data.fields {
+            for attr in field.field.attrs.iter().filter(|attr| is_compat(attr)) {
+                let list = match attr.parse_meta()? {
+                    Meta::List(list) => list,
+                    meta => return Err(Error::new(meta.span(), "expected a list of traits")),
+                };
+
+                for nested in list.nested {
+                    if let (Some((trait_, path)), _) = parse_trait(nested)? {
+                        if field
+                            .options
+                            .skip
+                            .iter()
+                            .any(|(skipped, _)| skipped == &trait_)
+                        {
+                            return Err(Error::new(path.span(), "duplicate trait"));
+                        }
+
+                        field.options.skip.push((trait_, path.span()));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the options of one trait, returning it if the field is ignored by
+/// it, and whether there are no options other than `ignore` and `bound`.
+fn parse_trait(nested: NestedMeta) -> Result<(Option<(Traits, Path)>, bool)> {
+    let (path, ignore, understood) = match nested {
+        // `derivative`: `Debug = "ignore"`.
+        NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+            path,
+            lit: Lit::Str(lit),
+            ..
+        })) if lit.value() == "ignore" => (path, true, true),
+        NestedMeta::Meta(Meta::List(list)) => {
+            let mut ignore = false;
+            let mut understood = true;
+
+            for option in list.nested {
+                match option {
+                    // `educe`: `Debug(ignore)`.
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("ignore") => ignore = true,
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, .. }))
+                        if path.is_ident("bound") => {}
+                    _ => understood = false,
+                }
+            }
+
+            (list.path, ignore, understood)
+        }
+        _ => return Ok((None, false)),
+    };
+
+    if !ignore {
+        return Ok((None, understood));
+    }
+
+    let trait_ = Traits::from_path(&path)?;
+
+    if !trait_.is_skippable() {
+        return Err(Error::new(
+            path.span(),
+            format!("`{}` can't be skipped", trait_.ident()),
+        ));
+    }
+
+    Ok((Some((trait_, path)), understood))
+}
+
+/// Removes the `derivative` and `educe` attributes [`is_stripped`] returns
+/// `true` for from the fields of the item.
+pub fn strip(item: &mut DeriveInput) {
+    let fields: Vec<_> = match &mut item.data {
+        syn::Data::Struct(data) => data.fields.iter_mut().collect(),
+        syn::Data::Enum(data) => data
+            .variants
+            .iter_mut()
+            .flat_map(|variant| variant.fields.iter_mut())
+            .collect(),
+        syn::Data::Union(data) => data.fields.named.iter_mut().collect(),
+    };
+
+    for field in fields {
+        field.attrs.retain(|attr| !is_stripped(attr));
+    }
+}
@@ -0,0 +1,137 @@
+//! `assert_consistent!`, checking that derived traits agree with each other for
+//! a set of values, which catches misconfigured skips, keys or delegations.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{ParseStream, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Error, Expr, Path, Result, Token,
+};
+
+use crate::traits::Traits;
+
+/// Expands `assert_consistent!(Traits...; values...)`: compares all pairs of
+/// values, checking the listed traits against [`PartialEq`].
+pub fn assert_consistent(input: TokenStream) -> TokenStream {
+    match parse.parse2(input) {
+        Ok((traits, values)) => generate(&traits, &values),
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+fn parse(input: ParseStream) -> Result<(Vec<Traits>, Vec<Expr>)> {
+    let mut traits = Vec::new();
+
+    while !input.peek(Token![;]) {
+        let path: Path = input.parse()?;
+        let trait_ = Traits::from_path(&path)?;
+
+        if !matches!(
+            trait_,
+            Traits::Eq | Traits::Hash | Traits::Ord | Traits::PartialEq | Traits::PartialOrd
+        ) {
+            return Err(Error::new(
+                path.span(),
+                format!("`{}` can't be checked for consistency", trait_.ident()),
+            ));
+        }
+
+        traits.push(trait_);
+
+        if input.peek(Token![;]) {
+            break;
+        }
+
+        input.parse::<Token![,]>()?;
+    }
+
+    input.parse::<Token![;]>()?;
+
+    let values = Punctuated::<Expr, Token![,]>::parse_terminated(input)?;
+
+    if values.is_empty() {
+        return Err(input.error("expected at least one value"));
+    }
+
+    Ok((traits, values.into_iter().collect()))
+}
+
+fn generate(traits: &[Traits], values: &[Expr]) -> TokenStream {
+    let mut checks = vec![quote! {
+        assert_eq!(
+            __a == __b,
+            __b == __a,
+            "`PartialEq` isn't symmetric for values {} and {}",
+            __i,
+            __j,
+        );
+    }];
+
+    if traits.contains(&Traits::Eq) {
+        checks.push(quote! {
+            if __i == __j {
+                assert!(__a == __b, "`Eq` isn't reflexive for value {}", __i);
+            }
+        });
+    }
+
+    if traits.contains(&Traits::PartialOrd) {
+        checks.push(quote! {
+            assert_eq!(
+                ::core::cmp::PartialOrd::partial_cmp(__a, __b)
+                    == ::core::option::Option::Some(::core::cmp::Ordering::Equal),
+                __a == __b,
+                "`PartialOrd` disagrees with `PartialEq` for values {} and {}",
+                __i,
+                __j,
+            );
+        });
+    }
+
+    if traits.contains(&Traits::Ord) {
+        checks.push(quote! {
+            assert_eq!(
+                ::core::cmp::PartialOrd::partial_cmp(__a, __b),
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(__a, __b)),
+                "`PartialOrd` disagrees with `Ord` for values {} and {}",
+                __i,
+                __j,
+            );
+        });
+    }
+
+    if traits.contains(&Traits::Hash) {
+        checks.push(quote! {
+            if __a == __b {
+                assert_eq!(
+                    __hash(__a),
+                    __hash(__b),
+                    "`Hash` disagrees with `PartialEq` for values {} and {}",
+                    __i,
+                    __j,
+                );
+            }
+        });
+    }
+
+    quote! {
+        {
+            #[allow(dead_code)]
+            fn __hash<__T: ::core::hash::Hash + ?::core::marker::Sized>(value: &__T) -> u64 {
+                let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+                ::core::hash::Hash::hash(value, &mut hasher);
+                ::core::hash::Hasher::finish(&hasher)
+            }
+
+            let __values = [#(&#values),*];
+
+            for (__i, __a) in __values.iter().copied().enumerate() {
+                for (__j, __b) in __values.iter().copied().enumerate() {
+                    #(#checks)*
+                }
+            }
+        }
+    }
+}
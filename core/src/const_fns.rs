@@ -0,0 +1,241 @@
+//! `const_eq()` and `const_cmp()`, mirroring `PartialEq` and `Ord` for const
+//! contexts, requested by the `const_fns` option.
+//!
+//! Trait methods can't be called in a `const fn`, so fields of primitive types
+//! are compared with operators, and all others through their own `const_eq()`
+//! and `const_cmp()`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Error, Result, Type, Visibility};
+
+use crate::{
+    attr::Options,
+    data::{Data, FieldData, Item},
+    traits::Traits,
+};
+
+/// Types compared with operators in a `const fn`.
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128",
+    "usize",
+];
+
+pub fn generate(item: &Item, options: &Options, vis: &Visibility) -> Result<TokenStream> {
+    check(item, options)?;
+
+    let (eq, cmp) = if item.is_empty() {
+        (quote! { match *self {} }, quote! { match *self {} })
+    } else {
+        (generate_eq(item), generate_cmp(item))
+    };
+
+    Ok(quote! {
+        /// Compares with `other` like `PartialEq::eq`, in const contexts.
+        #[inline]
+        #vis const fn const_eq(&self, other: &Self) -> bool {
+            #eq
+        }
+
+        /// Orders with `other` like `Ord::cmp`, in const contexts.
+        #[inline]
+        #vis const fn const_cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+            #cmp
+        }
+    })
+}
+
+/// Checks that no option changes comparisons in a way that can't be mirrored
+/// in a `const fn`.
+fn check(item: &Item, options: &Options) -> Result<()> {
+    let error = |span, option: &str| {
+        Err(Error::new(
+            span,
+            format!("`const_fns` doesn't support `{}`", option),
+        ))
+    };
+
+    if !options.supports(46) {
+        return Err(Error::new(
+            Span::call_site(),
+            "`const_fns` requires Rust 1.46 for `match` in a `const fn`",
+        ));
+    }
+
+    if options.key.is_some() {
+        return error(Span::call_site(), "key");
+    }
+
+    for data in item.datas() {
+        if data.options.incomparable {
+            return error(data.name.span(), "incomparable");
+        }
+
+        if !data.options.incomparable_with.is_empty() {
+            return error(data.name.span(), "incomparable_with");
+        }
+
+        for field in &data.fields {
+            let span = field.field.ty.span();
+
+            if field.options.epsilon.is_some() {
+                return error(span, "epsilon");
+            } else if field.options.with.is_some() {
+                return error(span, "with");
+            } else if field.options.none_last {
+                return error(span, "none_last");
+            } else if field.options.unordered {
+                return error(span, "unordered");
+            } else if field.options.case_insensitive {
+                return error(span, "case_insensitive");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn generate_eq(item: &Item) -> TokenStream {
+    let arms = item.datas().iter().map(|data| {
+        if let Some(arm) = data.uninhabited_arm(1) {
+            return arm;
+        }
+
+        let self_pattern = data.self_pattern_for(&Traits::PartialEq);
+        let other_pattern = data.other_pattern_for(&Traits::PartialEq);
+        let fields = data.fields_for(&Traits::PartialEq);
+
+        let body = if fields.is_empty() {
+            quote! { true }
+        } else {
+            let comparisons = fields.into_iter().map(|field| {
+                let (self_ident, other_ident) = (&field.self_ident, &field.other_ident);
+                let ty = &field.field.ty;
+
+                if is_primitive(ty) {
+                    quote_spanned! {ty.span()=> *#self_ident == *#other_ident }
+                } else {
+                    quote_spanned! {ty.span()=> <#ty>::const_eq(#self_ident, #other_ident) }
+                }
+            });
+
+            quote! { #(#comparisons)&&* }
+        };
+
+        quote! {
+            (#self_pattern, #other_pattern) => #body
+        }
+    });
+
+    let rest = if item.is_multi_variant() {
+        quote! { _ => false, }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        match (self, other) {
+            #(#arms,)*
+            #rest
+        }
+    }
+}
+
+fn generate_cmp(item: &Item) -> TokenStream {
+    let arms = item.datas().iter().map(|data| {
+        if let Some(arm) = data.uninhabited_arm(1) {
+            return arm;
+        }
+
+        let self_pattern = data.self_pattern_for(&Traits::Ord);
+        let other_pattern = data.other_pattern_for(&Traits::Ord);
+        let mut fields = data.fields_for(&Traits::Ord);
+        fields.sort_by_key(|field| (field.options.ord.is_none(), field.options.ord));
+
+        let comparisons = fields.into_iter().map(compare);
+
+        quote! {
+            (#self_pattern, #other_pattern) => {
+                #(match #comparisons {
+                    ::core::cmp::Ordering::Equal => (),
+                    __cmp => return __cmp,
+                })*
+
+                ::core::cmp::Ordering::Equal
+            }
+        }
+    });
+
+    // Closures can't be called in a `const fn`, so discriminants are matched
+    // inline.
+    let rest = match item {
+        Item::Enum { variants, repr, .. } if item.is_multi_variant() => {
+            let discriminant = |this| {
+                let patterns = variants.iter().map(Data::wildcard_pattern);
+                let discriminants = variants.iter().map(|variant| &variant.discriminant);
+
+                quote! {
+                    match #this {
+                        #(#patterns => #discriminants,)*
+                    }
+                }
+            };
+            let (this, other) = (
+                discriminant(quote! { self }),
+                discriminant(quote! { other }),
+            );
+
+            quote! {
+                _ => {
+                    let __discriminant: #repr = #this;
+                    let __other_discriminant: #repr = #other;
+
+                    if __discriminant < __other_discriminant {
+                        ::core::cmp::Ordering::Less
+                    } else {
+                        ::core::cmp::Ordering::Greater
+                    }
+                }
+            }
+        }
+        _ => TokenStream::new(),
+    };
+
+    quote! {
+        match (self, other) {
+            #(#arms,)*
+            #rest
+        }
+    }
+}
+
+fn compare(field: &FieldData) -> TokenStream {
+    let (self_ident, other_ident) = (&field.self_ident, &field.other_ident);
+    let ty = &field.field.ty;
+
+    if is_primitive(ty) {
+        quote_spanned! {ty.span()=>
+            if *#self_ident < *#other_ident {
+                ::core::cmp::Ordering::Less
+            } else if *#self_ident > *#other_ident {
+                ::core::cmp::Ordering::Greater
+            } else {
+                ::core::cmp::Ordering::Equal
+            }
+        }
+    } else {
+        quote_spanned! {ty.span()=> <#ty>::const_cmp(#self_ident, #other_ident) }
+    }
+}
+
+fn is_primitive(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => ty
+            .path
+            .get_ident()
+            .is_some_and(|ident| PRIMITIVES.iter().any(|primitive| ident == primitive)),
+        Type::Group(ty) => is_primitive(&ty.elem),
+        Type::Paren(ty) => is_primitive(&ty.elem),
+        _ => false,
+    }
+}
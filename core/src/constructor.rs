@@ -0,0 +1,60 @@
+//! `new()`, taking all fields of a struct except those marked `default`,
+//! requested by the `constructor` option.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Error, Member, Result, Visibility};
+
+use crate::data::Item;
+
+pub fn generate(item: &Item, vis: &Visibility) -> Result<TokenStream> {
+    let data = match item {
+        Item::Struct(data) => data,
+        Item::Enum { .. } => {
+            return Err(Error::new(
+                Span::call_site(),
+                "`constructor` is only supported for structs",
+            ))
+        }
+    };
+
+    let mut params = Vec::new();
+    let mut bounds = Vec::new();
+    let mut fields = Vec::new();
+
+    for field in &data.fields {
+        let member = &field.member;
+        let ty = &field.field.ty;
+
+        if field.options.default {
+            bounds.push(quote! { #ty: ::core::default::Default });
+            fields.push(quote! { #member: ::core::default::Default::default() });
+        } else {
+            let param = match member {
+                Member::Named(ident) => ident.clone(),
+                Member::Unnamed(index) => format_ident!("field_{}", index.index),
+            };
+
+            params.push(quote! { #param: #ty });
+            fields.push(quote! { #member: #param });
+        }
+    }
+
+    // Only requires defaults when constructing, not for the whole impl.
+    let where_clause = if bounds.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! { where #(#bounds),* }
+    };
+    let path = &data.path;
+    let value = data.wrap(quote! { #path { #(#fields,)* } });
+
+    Ok(quote! {
+        /// Creates a value from its fields, fields marked `default` are set
+        /// to their default.
+        #[inline]
+        #vis fn new(#(#params),*) -> Self #where_clause {
+            #value
+        }
+    })
+}
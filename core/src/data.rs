@@ -0,0 +1,676 @@
+//! Representation of the item shared by all trait implementations.
+
+use std::iter;
+
+use proc_macro2::{Literal, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt, spanned::Spanned, Attribute, DeriveInput, Error, Field, Generics, Ident, Member,
+    Meta, NestedMeta, Path, Result, Type, TypePath,
+};
+
+use crate::{
+    attr::{FieldOptions, VariantOptions},
+    traits::Traits,
+};
+
+/// The item traits are derived for.
+pub enum Item<'a> {
+    Struct(Data<'a>),
+    Enum {
+        variants: Vec<Data<'a>>,
+        /// Type of the discriminant, taken from `#[repr(...)]` or `isize`.
+        repr: Ident,
+        /// Whether the type of the discriminant is given by `#[repr(...)]`.
+        explicit_repr: bool,
+    },
+}
+
+impl<'a> Item<'a> {
+    /// Parses the item, which describes the shape of `remote` instead of
+    /// itself if given.
+    pub fn new(input: &'a DeriveInput, remote: Option<&Path>) -> Result<Self> {
+        match &input.data {
+            syn::Data::Struct(data) => {
+                let mut data = Data::new(
+                    remote,
+                    &input.ident,
+                    None,
+                    &input.generics,
+                    &data.fields,
+                    None,
+                )?;
+                data.transparent = transparent(&input.attrs, &data)?;
+                Ok(Item::Struct(data))
+            }
+            syn::Data::Enum(data) => {
+                let mut variants = Vec::new();
+                // Last explicit discriminant and the offset from it, as implicit
+                // discriminants count up from the previous one.
+                let mut last = None;
+                let mut offset = 0_usize;
+
+                for variant in &data.variants {
+                    if let Some((_, expr)) = &variant.discriminant {
+                        last = Some(expr);
+                        offset = 0;
+                    }
+
+                    let offset_literal = Literal::usize_unsuffixed(offset);
+                    let discriminant = match last {
+                        Some(expr) if offset == 0 => quote! { #expr },
+                        Some(expr) => quote! { (#expr) + #offset_literal },
+                        None => quote! { #offset_literal },
+                    };
+
+                    let mut data = Data::new(
+                        remote,
+                        &variant.ident,
+                        Some(&variant.ident),
+                        &input.generics,
+                        &variant.fields,
+                        Some(discriminant),
+                    )?;
+                    data.options = VariantOptions::from_attrs(&variant.attrs)?;
+
+                    if let Some(field) = data.fields.iter().find(|f| f.options.transparent) {
+                        return Err(Error::new(
+                            field.field.span(),
+                            "only fields of structs can be `transparent`",
+                        ));
+                    }
+
+                    variants.push(data);
+
+                    offset += 1;
+                }
+
+                let repr = repr(&input.attrs);
+
+                Ok(Item::Enum {
+                    variants,
+                    explicit_repr: repr.is_some(),
+                    repr: repr.unwrap_or_else(|| format_ident!("isize")),
+                })
+            }
+            syn::Data::Union(data) => Err(Error::new(
+                data.union_token.span(),
+                "unions are not supported",
+            )),
+        }
+    }
+
+    /// Returns `true` if the item is the newtype wrapper of a remote type.
+    pub fn is_remote(&self) -> bool {
+        self.datas().first().is_some_and(|data| data.remote)
+    }
+
+    /// The struct or all variants of the enum.
+    pub fn datas(&self) -> &[Data<'a>] {
+        match self {
+            Item::Struct(data) => std::slice::from_ref(data),
+            Item::Enum { variants, .. } => variants,
+        }
+    }
+
+    /// The struct or all variants of the enum, mutably.
+    pub fn datas_mut(&mut self) -> &mut [Data<'a>] {
+        match self {
+            Item::Struct(data) => std::slice::from_mut(data),
+            Item::Enum { variants, .. } => variants,
+        }
+    }
+
+    /// Returns `true` for enums without variants, which can't be matched
+    /// through a reference.
+    pub fn is_empty(&self) -> bool {
+        self.datas().is_empty()
+    }
+
+    /// Returns `true` if `param` is used by any field of `trait_`, not counting
+    /// `PhantomData`, which implements all supported traits regardless.
+    pub fn uses_type_param(&self, param: &Ident, trait_: &Traits) -> bool {
+        self.datas()
+            .iter()
+            .flat_map(|data| data.fields_for(trait_))
+            .any(|field| uses_type_param(field.field.ty.to_token_stream(), param))
+    }
+
+    /// Field a wrapper trait like `Future` is forwarded to: the one delegated
+    /// to or marked `transparent`, or the only field of a struct. Delegating a
+    /// supertrait, like `Iterator` for `DoubleEndedIterator`, counts too.
+    pub fn wrapped_field(&self, trait_: &Traits) -> Option<&FieldData<'a>> {
+        match self {
+            Item::Struct(data) => data
+                .fields
+                .iter()
+                .find(|field| {
+                    iter::once(trait_)
+                        .chain(trait_.implied())
+                        .any(|trait_| field.delegates(trait_))
+                })
+                .or_else(|| data.transparent.map(|index| &data.fields[index]))
+                .or(match data.fields.as_slice() {
+                    [field] => Some(field),
+                    _ => None,
+                }),
+            Item::Enum { .. } => None,
+        }
+    }
+
+    /// Traits delegated to any field.
+    pub fn delegated_traits(&self) -> impl Iterator<Item = &(Traits, Span)> {
+        self.datas()
+            .iter()
+            .flat_map(|data| &data.fields)
+            .flat_map(|field| &field.options.delegate)
+    }
+
+    /// Returns `true` if both traits are implemented with the same fields of
+    /// every struct or variant.
+    pub fn same_fields(&self, a: &Traits, b: &Traits) -> bool {
+        self.datas().iter().all(|data| {
+            let members = |trait_| {
+                data.fields_for(trait_)
+                    .into_iter()
+                    .map(|field| &field.member)
+            };
+
+            members(a).eq(members(b))
+        })
+    }
+
+    /// Types of the fields `trait_` is implemented with, except those handled
+    /// by a `with` module, `to_bits` or `debug(...)` instead of their own impl
+    /// and those of uninhabited structs and variants.
+    pub fn field_types_for(&self, trait_: &Traits) -> Vec<&'a Type> {
+        self.datas()
+            .iter()
+            .filter(|data| !data.is_uninhabited())
+            .flat_map(|data| data.fields_for(trait_))
+            .filter(|field| {
+                field.options.with.is_none()
+                    && field.options.to_bits.is_none()
+                    && field.options.debug.is_none()
+            })
+            .map(|field| &field.field.ty)
+            .collect()
+    }
+
+    /// Traits skipped by any field.
+    pub fn skipped_traits(&self) -> impl Iterator<Item = &(Traits, Span)> {
+        self.datas()
+            .iter()
+            .flat_map(|data| &data.fields)
+            .flat_map(|field| &field.options.skip)
+    }
+
+    /// Fields skipped by one trait but not by another that has to agree with
+    /// it, like `Hash` with `PartialEq`, of those that are `derived`.
+    ///
+    /// `Hash` may skip more fields than `PartialEq`, but not fewer, while the
+    /// comparisons have to skip the same ones.
+    pub fn inconsistent_skips(&self, derived: &[&Traits]) -> Vec<(Span, String)> {
+        use Traits::*;
+
+        let derived = |trait_: &Traits| derived.iter().any(|derived| derived.is_same_trait(trait_));
+        let mut inconsistencies = Vec::new();
+
+        for field in self.datas().iter().flat_map(|data| &data.fields) {
+            let mut check = |skipped: &Traits, used: &Traits| {
+                // Fields with an `epsilon` have to be skipped by everything but
+                // `PartialEq`.
+                if field.options.epsilon.is_some() && used.is_same_trait(&PartialEq) {
+                    return;
+                }
+
+                if derived(skipped) && derived(used) && field.skips(skipped) && !field.skips(used) {
+                    inconsistencies.push((
+                        field.field.ty.span(),
+                        format!(
+                            "`{}` is skipped by `{}` but not by `{}`, so they disagree",
+                            field.member_name(),
+                            skipped.ident(),
+                            used.ident(),
+                        ),
+                    ));
+                }
+            };
+
+            check(&PartialEq, &Hash);
+
+            for (comparison, other) in [
+                (&PartialEq, &PartialOrd),
+                (&PartialEq, &Ord),
+                (&PartialOrd, &Ord),
+            ] {
+                check(comparison, other);
+                check(other, comparison);
+            }
+
+            // The format has to be read the same way it's written.
+            #[cfg(feature = "speedy")]
+            {
+                check(&SpeedyReadable, &SpeedyWritable);
+                check(&SpeedyWritable, &SpeedyReadable);
+            }
+        }
+
+        inconsistencies
+    }
+
+    /// Returns `true` if values can be of different structs or variants.
+    pub fn is_multi_variant(&self) -> bool {
+        self.datas().len() > 1
+    }
+}
+
+fn uses_type_param(tokens: TokenStream, param: &Ident) -> bool {
+    let mut tokens = tokens.into_iter();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "PhantomData" => {
+                // Skip the generic arguments, minding `->` in function types.
+                let mut depth = 0_usize;
+                let mut arrow = false;
+
+                for token in tokens.by_ref() {
+                    match &token {
+                        TokenTree::Punct(punct) if punct.as_char() == '<' => depth += 1,
+                        TokenTree::Punct(punct) if punct.as_char() == '>' && !arrow => {
+                            depth -= 1;
+                        }
+                        _ => (),
+                    }
+
+                    arrow = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '-');
+
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            TokenTree::Ident(ident) if &ident == param => return true,
+            TokenTree::Group(group) if uses_type_param(group.stream(), param) => return true,
+            _ => (),
+        }
+    }
+
+    false
+}
+
+/// Index of the field all delegatable traits are implemented with: the one
+/// marked `transparent`, or the only one not `PhantomData` if the struct is
+/// `#[repr(transparent)]`.
+fn transparent(attrs: &[Attribute], data: &Data) -> Result<Option<usize>> {
+    let mut marked = data
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.options.transparent);
+
+    if let Some((index, _)) = marked.next() {
+        if let Some((_, field)) = marked.next() {
+            return Err(Error::new(
+                field.field.span(),
+                "only one field can be `transparent`",
+            ));
+        }
+
+        return Ok(Some(index));
+    }
+
+    let is_transparent = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("transparent")));
+
+    if !is_transparent {
+        return Ok(None);
+    }
+
+    // Other zero-sized types can't be recognized, those structs are left alone.
+    let mut sized = data
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !field.is_phantom_data());
+
+    Ok(match (sized.next(), sized.next()) {
+        (Some((index, _)), None) => Some(index),
+        _ => None,
+    })
+}
+
+/// Integer type given in `#[repr(...)]`, if any.
+fn repr(attrs: &[Attribute]) -> Option<Ident> {
+    const INTEGERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path)) => path
+                .get_ident()
+                .filter(|ident| INTEGERS.iter().any(|integer| ident == integer))
+                .cloned(),
+            _ => None,
+        })
+}
+
+/// A struct or a single enum variant.
+pub struct Data<'a> {
+    /// Path used to construct or match it: `Self` or `Self::Variant`.
+    pub path: TokenStream,
+    /// Name of the struct or variant.
+    pub name: &'a Ident,
+    /// Name of the variant, `None` for structs.
+    pub ident: Option<&'a Ident>,
+    /// Generics of the item.
+    pub generics: &'a Generics,
+    /// Values are wrapped in the newtype of a remote type, so patterns and
+    /// constructors are `Self(path)` instead.
+    pub remote: bool,
+    pub fields: Vec<FieldData<'a>>,
+    /// Value of the discriminant of an enum variant.
+    pub discriminant: Option<TokenStream>,
+    /// Options of an enum variant, default for structs.
+    pub options: VariantOptions,
+    /// Index of the field all delegatable traits are implemented with, for
+    /// `transparent` structs.
+    pub transparent: Option<usize>,
+}
+
+impl<'a> Data<'a> {
+    fn new(
+        remote: Option<&Path>,
+        name: &'a Ident,
+        ident: Option<&'a Ident>,
+        generics: &'a Generics,
+        fields: &'a syn::Fields,
+        discriminant: Option<TokenStream>,
+    ) -> Result<Self> {
+        let fields: Vec<FieldData> = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| FieldData::new(index, field))
+            .collect::<Result<_>>()?;
+
+        for (index, field) in fields.iter().enumerate() {
+            for (trait_, span) in &field.options.delegate {
+                if fields[..index].iter().any(|field| field.delegates(trait_)) {
+                    return Err(Error::new(
+                        *span,
+                        format!("`{}` is already delegated to another field", trait_.ident()),
+                    ));
+                }
+            }
+        }
+
+        let base = match remote {
+            Some(remote) => quote! { #remote },
+            None => quote! { Self },
+        };
+
+        Ok(Self {
+            path: Self::path_in(&base, ident),
+            name,
+            ident,
+            generics,
+            remote: remote.is_some(),
+            fields,
+            discriminant,
+            options: VariantOptions::default(),
+            transparent: None,
+        })
+    }
+
+    /// Path to the struct or variant in `ty`: `ty` or `ty::Variant`.
+    fn path_in(ty: &TokenStream, ident: Option<&Ident>) -> TokenStream {
+        match ident {
+            Some(ident) => quote! { #ty::#ident },
+            None => ty.clone(),
+        }
+    }
+
+    /// Fields `trait_` is implemented with: only the field delegated to, if
+    /// any, or the `transparent` one, otherwise all of them not skipping it.
+    pub fn fields_for(&self, trait_: &Traits) -> Vec<&FieldData<'a>> {
+        let transparent = self
+            .transparent
+            .filter(|_| trait_.is_delegatable())
+            .map(|index| &self.fields[index]);
+
+        match self
+            .fields
+            .iter()
+            .find(|field| field.delegates(trait_))
+            .or(transparent)
+        {
+            Some(field) => vec![field],
+            None => self
+                .fields
+                .iter()
+                .filter(|field| !field.skips(trait_))
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if any field is uninhabited, so no value of the struct or
+    /// variant exists.
+    pub fn is_uninhabited(&self) -> bool {
+        self.fields.iter().any(FieldData::is_uninhabited)
+    }
+
+    /// Arm of a struct or variant with an uninhabited field, which is never
+    /// matched: `pattern => match *__field {}`. The scrutinee is a tuple if
+    /// `others` values are matched along with `self`.
+    pub fn uninhabited_arm(&self, others: usize) -> Option<TokenStream> {
+        let field = self.fields.iter().find(|field| field.is_uninhabited())?;
+        let pattern = self.pattern(vec![field], |field| &field.self_ident);
+        let self_ident = &field.self_ident;
+
+        let pattern = if others == 0 {
+            pattern
+        } else {
+            let wildcards = (0..others).map(|_| quote! { _ });
+            quote! { (#pattern, #(#wildcards),*) }
+        };
+
+        Some(quote! { #pattern => match *#self_ident {} })
+    }
+
+    /// Pattern binding all fields to [`FieldData::self_ident`].
+    pub fn self_pattern(&self) -> TokenStream {
+        self.pattern(self.fields.iter().collect(), |field| &field.self_ident)
+    }
+
+    /// Pattern binding all fields to [`FieldData::other_ident`].
+    pub fn other_pattern(&self) -> TokenStream {
+        self.pattern(self.fields.iter().collect(), |field| &field.other_ident)
+    }
+
+    /// Pattern binding the fields used by `trait_` to
+    /// [`FieldData::self_ident`].
+    pub fn self_pattern_for(&self, trait_: &Traits) -> TokenStream {
+        self.pattern(self.fields_for(trait_), |field| &field.self_ident)
+    }
+
+    /// Pattern binding the fields used by `trait_` to
+    /// [`FieldData::other_ident`].
+    pub fn other_pattern_for(&self, trait_: &Traits) -> TokenStream {
+        self.pattern(self.fields_for(trait_), |field| &field.other_ident)
+    }
+
+    /// Pattern binding the fields used by `trait_` to
+    /// [`FieldData::other_ident`] in the struct or variant of the same name and
+    /// shape in another type.
+    pub fn other_pattern_in(&self, ty: &TokenStream, trait_: &Traits) -> TokenStream {
+        let fields = self.fields_for(trait_);
+        let rest = fields.len() < self.fields.len();
+
+        Self::pattern_with(&Self::path_in(ty, self.ident), fields, rest, |field| {
+            &field.other_ident
+        })
+    }
+
+    /// Wraps a pattern or constructor of the struct or variant for
+    /// [`Data::remote`].
+    pub fn wrap(&self, value: TokenStream) -> TokenStream {
+        if self.remote {
+            quote! { Self(#value) }
+        } else {
+            value
+        }
+    }
+
+    /// Pattern matching any value of this struct or variant.
+    pub fn wildcard_pattern(&self) -> TokenStream {
+        let path = &self.path;
+        self.wrap(quote! { #path { .. } })
+    }
+
+    /// Pattern matching any value of the struct or variant of the same name in
+    /// another type.
+    pub fn wildcard_pattern_in(&self, ty: &TokenStream) -> TokenStream {
+        let path = Self::path_in(ty, self.ident);
+        quote! { #path { .. } }
+    }
+
+    fn pattern(
+        &self,
+        fields: Vec<&FieldData<'a>>,
+        binding: impl for<'b> Fn(&'b FieldData<'a>) -> &'b Ident,
+    ) -> TokenStream {
+        let rest = fields.len() < self.fields.len();
+        self.wrap(Self::pattern_with(&self.path, fields, rest, binding))
+    }
+
+    fn pattern_with(
+        path: &TokenStream,
+        fields: Vec<&FieldData<'a>>,
+        rest: bool,
+        binding: impl for<'b> Fn(&'b FieldData<'a>) -> &'b Ident,
+    ) -> TokenStream {
+        let rest = if rest {
+            quote! { .. }
+        } else {
+            TokenStream::new()
+        };
+        let members = fields.iter().map(|field| &field.member);
+        let bindings = fields.iter().map(|field| binding(field));
+
+        quote! { #path { #(#members: #bindings,)* #rest } }
+    }
+}
+
+/// A single field of a struct or variant.
+pub struct FieldData<'a> {
+    pub field: &'a Field,
+    /// Name or index of the field.
+    pub member: Member,
+    /// Binding of the field when matching `self`.
+    pub self_ident: Ident,
+    /// Binding of the field when matching `other`.
+    pub other_ident: Ident,
+    pub options: FieldOptions,
+}
+
+impl<'a> FieldData<'a> {
+    fn new(index: usize, field: &'a Field) -> Result<Self> {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+
+        let (member, name) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.unraw().to_string()),
+            None => (Member::Unnamed(index.into()), index.to_string()),
+        };
+
+        // Bindings are spanned to the field type, so errors about unsatisfied
+        // bounds point at the offending field.
+        let span = field.ty.span();
+
+        Ok(Self {
+            field,
+            member,
+            self_ident: format_ident!("__field_{}", name, span = span),
+            other_ident: format_ident!("__other_field_{}", name, span = span),
+            options,
+        })
+    }
+
+    /// Name of the field, or its index for tuples, as written in the source.
+    pub fn member_name(&self) -> String {
+        match &self.member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        }
+    }
+
+    /// Returns `true` if the field is `PhantomData`, which is recognized by
+    /// name only.
+    pub fn is_phantom_data(&self) -> bool {
+        matches!(
+            &self.field.ty,
+            Type::Path(TypePath { path, .. })
+                if path.segments.last().is_some_and(|segment| segment.ident == "PhantomData")
+        )
+    }
+
+    /// Returns `true` if the type of the field mentions the item, by its name
+    /// `ident` or `Self`, like `Option<Box<Self>>`. Bounds on such types would
+    /// need the very impl they bound.
+    pub fn is_recursive(&self, ident: &Ident) -> bool {
+        fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+            tokens.into_iter().any(|token| match token {
+                TokenTree::Ident(token) => token == *ident || token == "Self",
+                TokenTree::Group(group) => mentions(group.stream(), ident),
+                _ => false,
+            })
+        }
+
+        mentions(self.field.ty.to_token_stream(), ident)
+    }
+
+    /// Returns `true` if the field has no values, being of type `!` or marked
+    /// `uninhabited`.
+    pub fn is_uninhabited(&self) -> bool {
+        self.options.uninhabited || matches!(self.field.ty, Type::Never(_))
+    }
+
+    /// Returns `true` if `trait_` is implemented without this field. Skipping
+    /// `PartialEq` skips `Eq` too.
+    pub fn skips(&self, trait_: &Traits) -> bool {
+        // Comparing with other types skips the same fields.
+        let trait_ = match trait_ {
+            Traits::Eq | Traits::PartialEqOther(_) => &Traits::PartialEq,
+            Traits::PartialOrdOther(_) => &Traits::PartialOrd,
+            trait_ => trait_,
+        };
+
+        self.options
+            .skip
+            .iter()
+            .any(|(skipped, _)| skipped == trait_)
+    }
+
+    /// Returns `true` if `trait_` is implemented only in terms of this field.
+    pub fn delegates(&self, trait_: &Traits) -> bool {
+        self.options
+            .delegate
+            .iter()
+            .any(|(delegated, _)| delegated == trait_)
+    }
+}
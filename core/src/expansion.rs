@@ -0,0 +1,43 @@
+//! `assert_expansion!`, for crates wrapping `derive_where` to snapshot-test
+//! the impls generated for their items.
+
+use proc_macro2::TokenStream;
+
+/// Panics if expanding `input`, items with `derive_where` attributes, doesn't
+/// produce `expected`. Tokens are compared as strings, so spacing and line
+/// breaks don't matter.
+pub fn assert_expansion(input: TokenStream, expected: TokenStream) {
+    let actual = crate::derive_where_items(input).to_string();
+    let expected = expected.to_string();
+
+    assert!(
+        actual == expected,
+        "unexpected expansion\n  actual: {}\nexpected: {}",
+        actual,
+        expected,
+    );
+}
+
+/// Expands the items in the first braces and compares them with the second:
+///
+/// ```
+/// derive_restricted_core::assert_expansion! {
+///     {
+///         #[derive_where(; Copy)]
+///         struct Test;
+///     }
+///     {
+///         struct Test;
+///         impl ::core::marker::Copy for Test where {}
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_expansion {
+    ({ $($input:tt)* } { $($expected:tt)* }) => {
+        $crate::assert_expansion(
+            $crate::__quote::quote! { $($input)* },
+            $crate::__quote::quote! { $($expected)* },
+        )
+    };
+}
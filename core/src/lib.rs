@@ -0,0 +1,778 @@
+//! Parsing and code generation behind `derive_where`, for other procedural
+//! macros to reuse its custom bounds instead of reimplementing them.
+//!
+//! [`derive_where`], [`derive_where_items`] and [`derive_where_all`] are the
+//! complete macros.
+//! [`attr`] parses the attributes, [`data::Item`] represents the item and
+//! [`traits`] generates the bodies of the impls.
+//!
+//! The expansion only depends on the input, so it's the same across builds:
+//! nothing is hashed or generated from the environment, and the impls of each
+//! `derive_where` attribute are emitted in the order of their trait names.
+
+mod alias;
+mod assert;
+pub mod attr;
+#[cfg(feature = "bevy_reflect")]
+mod bevy_reflect;
+mod compat;
+#[cfg(feature = "test-helpers")]
+mod consistency;
+mod const_fns;
+mod constructor;
+pub mod data;
+#[cfg(feature = "test-helpers")]
+mod expansion;
+#[cfg(feature = "serde")]
+mod serde;
+pub mod traits;
+mod variants;
+mod warning;
+#[cfg(feature = "zerocopy")]
+mod zerocopy;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse::{ParseStream, Parser},
+    spanned::Spanned,
+    Attribute, DeriveInput, Error, ItemMod, LitStr, Path, Result,
+};
+
+use attr::{
+    is_derive_where, std_derives, strip_derive_where, strip_derive_where_tokens, Attributes,
+    DeriveWhere,
+};
+use data::Item;
+use traits::Traits;
+
+#[cfg(feature = "test-helpers")]
+pub use consistency::assert_consistent;
+#[cfg(feature = "test-helpers")]
+pub use expansion::assert_expansion;
+#[cfg(feature = "test-helpers")]
+#[doc(hidden)]
+pub use quote as __quote;
+
+/// Expands `#[derive_where(attr)] item`.
+pub fn derive_where(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    let item = match syn::parse2::<DeriveInput>(tokens.clone()) {
+        Ok(item) => item,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    // Aliases are expanded first, the item comes back with their traits.
+    if let Some(expanded) = alias::expand(&attr, &item) {
+        return expanded;
+    }
+
+    let (aliased, attr) = alias::unmark(attr);
+    let mut attributes = Attributes::default();
+
+    let expanded = match (|input: ParseStream| attributes.parse_attr(input)).parse2(attr) {
+        Ok(()) => expand_tokens(attributes, item, Some(tokens)),
+        Err(error) => error.to_compile_error(),
+    };
+
+    if aliased {
+        alias::resolve_at(expanded, Span::call_site())
+    } else {
+        expanded
+    }
+}
+
+/// Expands `derive_where_alias!(Name = traits)` to a `macro_rules!` that
+/// attributes listing `Name!` among their traits are expanded with.
+pub fn derive_where_alias(input: TokenStream) -> TokenStream {
+    alias::generate(input).unwrap_or_else(|error| error.to_compile_error())
+}
+
+/// Expands `derive_where_items! { input }`: any number of items with
+/// `#[derive_where(...)]` attributes.
+pub fn derive_where_items(input: TokenStream) -> TokenStream {
+    let parser = |input: ParseStream| {
+        let mut items = Vec::new();
+
+        while !input.is_empty() {
+            items.push(input.parse::<DeriveInput>()?);
+        }
+
+        Ok(items)
+    };
+
+    match parser.parse2(input) {
+        Ok(items) => items
+            .into_iter()
+            .map(|item| expand(Attributes::default(), item))
+            .collect(),
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+/// Expands `#[derive_where_all(attr)] mod module { ... }`: every struct and
+/// enum of the inline module is expanded as if it had `#[derive_where(attr)]`,
+/// in addition to its own `derive_where` attributes. Bounds on type parameters
+/// an item doesn't have are left out for it.
+pub fn derive_where_all(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let parse = |attr| {
+        let mut attributes = Attributes::default();
+        (|input: ParseStream| attributes.parse_attr(input))
+            .parse2(attr)
+            .map(|()| attributes)
+    };
+
+    let mut module = match syn::parse2::<ItemMod>(item) {
+        Ok(module) => module,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    // Only checked once, instead of for every item.
+    if let Err(error) = parse(attr.clone()) {
+        let error = error.to_compile_error();
+        return quote! {
+            #module
+            #error
+        };
+    }
+
+    let items = match &mut module.content {
+        Some((_, items)) => items,
+        None => {
+            return Error::new(module.ident.span(), "only inline modules are supported")
+                .to_compile_error()
+        }
+    };
+
+    for item in items {
+        let input: DeriveInput = match item.clone() {
+            syn::Item::Struct(item) => item.into(),
+            syn::Item::Enum(item) => item.into(),
+            _ => continue,
+        };
+        let mut attributes = parse(attr.clone()).expect("checked before");
+
+        // Items of the module don't have to share all generic parameters.
+        for derive_where in &mut attributes.derive_wheres {
+            derive_where.retain_bounds_for(&input.generics);
+        }
+
+        *item = syn::Item::Verbatim(expand(attributes, input));
+    }
+
+    module.into_token_stream()
+}
+
+/// Expands an item with `attributes` given in addition to its own
+/// `derive_where` attributes, emitting the item without them followed by the
+/// impls or the error.
+pub fn expand(attributes: Attributes, item: DeriveInput) -> TokenStream {
+    expand_tokens(attributes, item, None)
+}
+
+/// Expands an item like [`expand`], re-emitting its original `tokens` if given
+/// and only attributes have to be removed from it. Their spans are then kept
+/// exactly, for IDEs to map fields back to their source.
+fn expand_tokens(
+    attributes: Attributes,
+    mut item: DeriveInput,
+    tokens: Option<TokenStream>,
+) -> TokenStream {
+    let output = derive_where_internal(attributes, &item);
+
+    // Other derives on the item only see its attributes without ours.
+    strip_derive_where(&mut item);
+
+    match output {
+        Ok(Expansion {
+            remote,
+            attrs,
+            variant_attrs,
+            field_attrs,
+            compat,
+            impls,
+        }) => {
+            if let (None, true, true, true, Some(tokens)) = (
+                &remote,
+                attrs.is_empty(),
+                variant_attrs.is_empty(),
+                field_attrs.is_empty(),
+                &tokens,
+            ) {
+                // Like `compat::strip`, only attributes of fields are stripped,
+                // which are in the body of a struct or of a variant.
+                let fields = match item.data {
+                    syn::Data::Enum(_) => 2,
+                    _ => 1,
+                };
+                let item = strip_derive_where_tokens(tokens.clone(), &|attr, depth| {
+                    compat && depth == fields && compat::is_stripped(attr)
+                });
+
+                return quote! {
+                    #item
+                    #impls
+                };
+            }
+
+            if compat {
+                compat::strip(&mut item);
+            }
+
+            item.attrs.extend(attrs);
+
+            if let syn::Data::Enum(enum_) = &mut item.data {
+                for (variant, attr) in variant_attrs {
+                    enum_.variants[variant].attrs.push(attr);
+                }
+            }
+
+            for (data, field, attr) in field_attrs {
+                let fields = match &mut item.data {
+                    syn::Data::Struct(data) => &mut data.fields,
+                    syn::Data::Enum(enum_) => &mut enum_.variants[data].fields,
+                    syn::Data::Union(_) => unreachable!("unions are rejected"),
+                };
+
+                fields
+                    .iter_mut()
+                    .nth(field)
+                    .expect("field exists")
+                    .attrs
+                    .push(attr);
+            }
+
+            let item = match remote {
+                Some(remote) => remote_wrapper(&item, &remote),
+                None => item.into_token_stream(),
+            };
+
+            quote! {
+                #item
+                #impls
+            }
+        }
+        Err(error) => {
+            let error = error.to_compile_error();
+            let item = match tokens {
+                Some(tokens) => strip_derive_where_tokens(tokens, &|_, _| false),
+                None => item.into_token_stream(),
+            };
+
+            quote! {
+                #item
+                #error
+            }
+        }
+    }
+}
+
+/// Newtype replacing an item that describes the shape of a remote type, as
+/// traits can't be implemented for the remote type itself.
+fn remote_wrapper(item: &DeriveInput, remote: &Path) -> TokenStream {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        generics,
+        ..
+    } = item;
+
+    // The discriminant type only describes the remote enum.
+    let attrs = attrs.iter().filter(|attr| !attr.path.is_ident("repr"));
+    let params = &generics.params;
+    let (_, type_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #(#attrs)*
+        #vis struct #ident<#params>(pub #remote #type_generics) #where_clause;
+    }
+}
+
+/// Output of [`derive_where_internal`], applied to the item by [`expand`].
+struct Expansion {
+    /// Remote type the item has to be replaced with a wrapper of, if any.
+    remote: Option<Path>,
+    /// Attributes added to the item for other derives.
+    attrs: Vec<Attribute>,
+    /// Attributes added to variants for other derives, by their index.
+    variant_attrs: Vec<(usize, Attribute)>,
+    /// Attributes added to fields for other derives, by the index of their
+    /// struct or variant and their own index.
+    field_attrs: Vec<(usize, usize, Attribute)>,
+    /// Whether `derivative` and `educe` attributes have to be removed.
+    compat: bool,
+    impls: TokenStream,
+}
+
+/// Generates the impls and what else [`expand`] has to change about the item.
+fn derive_where_internal(mut attributes: Attributes, item: &DeriveInput) -> Result<Expansion> {
+    // Further `derive_where` attributes on the same item are handled here
+    // instead of expanding separately, so they can be checked together. The
+    // item is already configured: `cfg_attr`s after this attribute arrive as
+    // plain `derive_where`s or not at all, and ones before it are expanded
+    // before this attribute is invoked.
+    for attr in item.attrs.iter().filter(|attr| is_derive_where(attr)) {
+        attr.parse_args_with(|input: ParseStream| attributes.parse_attr(input))?;
+    }
+
+    let Attributes {
+        mut derive_wheres,
+        mut options,
+    } = attributes;
+
+    if let Some(tag @ None) = options.hash_tag.as_mut() {
+        *tag = Some(LitStr::new(
+            &item.ident.unraw().to_string(),
+            item.ident.span(),
+        ));
+    }
+
+    DeriveWhere::check_duplicates(&derive_wheres)?;
+
+    let std_derives = std_derives(&item.attrs);
+    DeriveWhere::check_std_derives(&derive_wheres, &std_derives)?;
+
+    if options.implied_traits {
+        DeriveWhere::add_implied(&mut derive_wheres, &std_derives);
+    }
+
+    let DeriveInput {
+        ident, generics, ..
+    } = item;
+
+    let mut data = Item::new(item, options.remote.as_ref())?;
+
+    if options.compat {
+        compat::apply(&mut data)?;
+    }
+
+    if let Some(remote) = &options.remote {
+        if data.is_empty() {
+            return Err(Error::new(
+                remote.span(),
+                "enums without variants can't be derived remotely",
+            ));
+        }
+
+        if let Some((_, span)) = derive_wheres
+            .iter()
+            .flat_map(|derive_where| &derive_where.traits)
+            .find(|(trait_, _)| matches!(trait_, Traits::Custom(_)))
+        {
+            return Err(Error::new(*span, "custom traits can't be derived remotely"));
+        }
+
+        if let Some((_, span)) = derive_wheres
+            .iter()
+            .flat_map(|derive_where| &derive_where.traits)
+            .find(|(trait_, _)| trait_.is_zerocopy())
+        {
+            return Err(Error::new(
+                *span,
+                "zerocopy traits can't be derived remotely",
+            ));
+        }
+    }
+
+    if let (true, None, Some(variant)) = (
+        options.ptr_eq,
+        options.incomparable_ord,
+        data.datas().iter().find(|data| data.options.incomparable),
+    ) {
+        return Err(Error::new(
+            variant.name.span(),
+            "`incomparable` variants aren't equal to themselves, which `ptr_eq` would assume",
+        ));
+    }
+
+    if let (Some(_), Some((_, span))) = (&options.key, data.delegated_traits().next()) {
+        return Err(Error::new(
+            *span,
+            "fields can't be delegated to when comparing by `key`",
+        ));
+    }
+
+    for (trait_, span) in data.delegated_traits().chain(data.skipped_traits()) {
+        if !derive_wheres
+            .iter()
+            .flat_map(|derive_where| &derive_where.traits)
+            .any(|(derived, _)| {
+                derived.is_same_trait(trait_)
+                    || matches!(
+                        (derived, trait_),
+                        (Traits::PartialEqOther(_), Traits::PartialEq)
+                            | (Traits::PartialOrdOther(_), Traits::PartialOrd)
+                    )
+            })
+        {
+            return Err(Error::new(
+                *span,
+                format!("`{}` isn't derived", trait_.ident()),
+            ));
+        }
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let mut impls = Vec::new();
+
+    let derived: Vec<_> = derive_wheres
+        .iter()
+        .flat_map(|derive_where| &derive_where.traits)
+        .map(|(trait_, _)| trait_)
+        .collect();
+
+    #[cfg(feature = "nightly")]
+    if options.structural_match && !derived.contains(&&Traits::PartialEq) {
+        return Err(Error::new(
+            Span::call_site(),
+            "`structural_match` requires `PartialEq` to be derived",
+        ));
+    }
+
+    for (span, message) in data.inconsistent_skips(&derived) {
+        if options.strict_skip {
+            return Err(Error::new(span, message));
+        } else if options.warns("inconsistent_skip") {
+            impls.push(warning::warning(span, "inconsistent_skip", &message));
+        }
+    }
+
+    for trait_ in derived
+        .iter()
+        .filter(|trait_| matches!(trait_, Traits::PartialOrd | Traits::Ord))
+    {
+        if let Some(field) = data
+            .datas()
+            .iter()
+            .flat_map(|data| data.fields_for(trait_))
+            .find(|field| field.options.unordered)
+        {
+            return Err(Error::new(
+                field.field.ty.span(),
+                format!(
+                    "`unordered` fields have no order, skip them in `{}`",
+                    trait_.ident()
+                ),
+            ));
+        }
+    }
+
+    // `epsilon` makes `PartialEq` non-transitive and disagree with any other
+    // comparison or hash of the field.
+    for trait_ in derived.iter().filter(|trait_| {
+        matches!(
+            trait_,
+            Traits::Eq | Traits::Hash | Traits::HashPortable | Traits::Ord | Traits::PartialOrd
+        )
+    }) {
+        if let Some(field) = data
+            .datas()
+            .iter()
+            .flat_map(|data| data.fields_for(trait_))
+            .find(|field| field.options.epsilon.is_some())
+        {
+            return Err(Error::new(
+                field.field.ty.span(),
+                format!(
+                    "`epsilon` only applies to `PartialEq`, skip the field in `{}`",
+                    trait_.ident()
+                ),
+            ));
+        }
+    }
+
+    for derive_where in derive_wheres
+        .iter()
+        .filter(|_| options.warns("unused_bound"))
+    {
+        for param in derive_where.unused_bounds(generics, &data) {
+            impls.push(warning::warning(
+                param.span(),
+                "unused_bound",
+                &format!(
+                    "`{}` isn't used by any field, its bounds can be removed",
+                    param
+                ),
+            ));
+        }
+    }
+
+    for derive_where in &derive_wheres {
+        let predicates = derive_where.predicates(generics);
+        let trait_generics = derive_where.generics(generics);
+        let (trait_impl_generics, ..) = trait_generics.split_for_impl();
+        let cfg = derive_where.cfg.as_ref().map(|cfg| quote! { #[cfg(#cfg)] });
+        let doc = if options.doc_hidden {
+            quote! { #[doc(hidden)] }
+        } else if options.doc_bounds {
+            let doc = if predicates.is_empty() {
+                String::from("Derived by `derive_where` without bounds.")
+            } else {
+                format!(
+                    "Derived by `derive_where` with bounds: `{}`.",
+                    quote! { #(#predicates),* }
+                )
+            };
+
+            quote! { #[doc = #doc] }
+        } else {
+            TokenStream::new()
+        };
+
+        if !derive_where.asserts.is_empty() {
+            let assertion =
+                assert::generate(ident, &trait_generics, &predicates, &derive_where.asserts);
+
+            impls.push(quote! {
+                #cfg
+                #assertion
+            });
+        }
+
+        if let Some(name) = &derive_where.export_bounds {
+            impls.push(quote! {
+                #cfg
+                #[allow(unused_macros)]
+                macro_rules! #name {
+                    (impl $trait:path { $($body:tt)* }) => {
+                        impl #trait_impl_generics $trait for #ident #type_generics
+                            where #(#predicates),*
+                        {
+                            $($body)*
+                        }
+                    };
+                }
+            });
+        }
+
+        // Impls follow the names of their traits, not the order they're listed
+        // in, so reordering the list doesn't change the expansion.
+        let mut traits: Vec<_> = derive_where.traits.iter().collect();
+        traits.sort_by_cached_key(|(trait_, _)| trait_.ident().to_string());
+
+        for (trait_, span) in traits {
+            if trait_.is_external() {
+                continue;
+            }
+
+            // zerocopy's derive implements the trait, bounded by the types of
+            // the fields, which our bounds have to satisfy.
+            if trait_.is_zerocopy() {
+                let types = data.field_types_for(trait_);
+
+                if !types.is_empty() {
+                    let assertion = assert::generate_fields(
+                        &trait_generics,
+                        &predicates,
+                        &trait_.path(&options),
+                        &types,
+                    );
+
+                    impls.push(quote! {
+                        #cfg
+                        #assertion
+                    });
+                }
+
+                continue;
+            }
+
+            trait_.check(&data, &options)?;
+
+            if options.assert_only {
+                if trait_.is_generic() {
+                    return Err(Error::new(
+                        *span,
+                        format!("`{}` can't be checked by `assert_only`", trait_.ident()),
+                    ));
+                }
+
+                let types = data.field_types_for(trait_);
+
+                if !types.is_empty() {
+                    let assertion = assert::generate_fields(
+                        &trait_generics,
+                        &predicates,
+                        &trait_.path(&options),
+                        &types,
+                    );
+
+                    impls.push(quote! {
+                        #cfg
+                        #assertion
+                    });
+                }
+
+                continue;
+            }
+
+            let self_ty = quote! { #ident #type_generics };
+            let impl_ = match trait_.generate_impl(
+                &data,
+                &options,
+                ident,
+                &self_ty,
+                &trait_generics,
+                &predicates,
+            ) {
+                Some(impl_) => impl_,
+                None => {
+                    let body = trait_.generate_body(&data, &options, &derive_where.traits);
+                    let t = trait_.path(&options);
+
+                    quote! {
+                        impl #trait_impl_generics #t for #self_ty
+                            where #(#predicates),*
+                        {
+                            #body
+                        }
+                    }
+                }
+            };
+
+            impls.push(quote! {
+                #cfg
+                #doc
+                #impl_
+            });
+
+            // Like the marker of `#[derive(PartialEq)]`, it has the bounds of
+            // the item only.
+            #[cfg(feature = "nightly")]
+            if options.structural_match && *trait_ == Traits::PartialEq {
+                impls.push(quote! {
+                    #cfg
+                    impl #impl_generics ::core::marker::StructuralPartialEq
+                        for #ident #type_generics #where_clause {}
+                });
+            }
+
+            if let Some(types) = trait_.generate_types(ident, &item.vis) {
+                impls.push(quote! {
+                    #cfg
+                    #types
+                });
+            }
+
+            if let Some(items) = trait_.generate_items(&data, item, &options) {
+                impls.push(quote! {
+                    #cfg
+                    const _: () = {
+                        #items
+                    };
+                });
+            }
+
+            if let Some(assertions) = trait_.generate_assertions(&data, &options) {
+                impls.push(quote! {
+                    #cfg
+                    const _: () = {
+                        impl #impl_generics #ident #type_generics
+                            where #(#predicates),*
+                        {
+                            #assertions
+                        }
+                    };
+                });
+            }
+        }
+    }
+
+    if !options.asserts.is_empty() {
+        let predicates: Vec<_> = where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+            .collect();
+
+        impls.push(assert::generate(
+            ident,
+            generics,
+            &predicates,
+            &options.asserts,
+        ));
+    }
+
+    let mut inherent = Vec::new();
+
+    if options.variant_names {
+        inherent.push(variants::generate_names(&data, &item.vis)?);
+    }
+
+    if options.variant_iter {
+        inherent.push(variants::generate_iter(&data, &item.vis)?);
+    }
+
+    if options.accessors {
+        inherent.push(variants::generate_accessors(&data, &item.vis)?);
+    }
+
+    if options.constructor {
+        inherent.push(constructor::generate(&data, &item.vis)?);
+    }
+
+    if options.const_fns {
+        inherent.push(const_fns::generate(&data, &options, &item.vis)?);
+    }
+
+    if !inherent.is_empty() {
+        impls.push(quote! {
+            impl #impl_generics #ident #type_generics #where_clause {
+                #(#inherent)*
+            }
+        });
+    }
+
+    // Each impl is shown as a warning on the item, as proc-macros can't print
+    // to the compiler's output otherwise.
+    if options.debug_expansion {
+        let expansions: Vec<_> = impls
+            .iter()
+            .map(|impl_| warning::warning(ident.span(), "expansion", &impl_.to_string()))
+            .collect();
+        impls.extend(expansions);
+    }
+
+    #[allow(unused_mut)]
+    let (mut attrs, mut variant_attrs, mut field_attrs) = (Vec::new(), Vec::new(), Vec::new());
+
+    #[cfg(feature = "serde")]
+    {
+        attrs.extend(serde::generate_bounds(&derive_wheres)?);
+
+        if options.serde_skip {
+            field_attrs = serde::generate_skips(&data);
+        }
+
+        let (renames, variant_renames, field_renames) =
+            serde::generate_renames(&derive_wheres, &options, &data)?;
+        attrs.extend(renames);
+        attrs.extend(serde::generate_tagging(&derive_wheres, &options, &data)?);
+        variant_attrs.extend(variant_renames);
+        field_attrs.extend(field_renames);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    {
+        let bounds = bevy_reflect::generate_bounds(&derive_wheres)?;
+
+        if !bounds.is_empty() {
+            attrs.extend(bounds);
+            field_attrs.extend(bevy_reflect::generate_ignores(&data));
+        }
+    }
+
+    #[cfg(feature = "zerocopy")]
+    attrs.extend(zerocopy::generate_derives(&derive_wheres, &options));
+
+    Ok(Expansion {
+        remote: options.remote,
+        attrs,
+        variant_attrs,
+        field_attrs,
+        compat: options.compat,
+        impls: quote! {
+            #(#impls)*
+        },
+    })
+}
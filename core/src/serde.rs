@@ -0,0 +1,236 @@
+//! Bounds for serde's own derives: `Serialize` and `Deserialize` aren't
+//! implemented, but the bounds of their `derive_where` are attached to the item
+//! as `#[serde(bound(...))]`, replacing the bounds serde would infer.
+//!
+//! With the `serde_skip` option, fields skipped by `PartialEq` are skipped by
+//! serde too, so serialized values are only as detailed as their equality.
+//!
+//! `rename` and `rename_all` on the item, its variants and its fields are
+//! passed on to serde's derives as `#[serde(rename = "...")]` and
+//! `#[serde(rename_all = "...")]`.
+//!
+//! Enums are externally tagged by default, like in serde. `tag`, `content` and
+//! `untagged` are passed on as well, choosing serde's internally tagged,
+//! adjacently tagged or untagged representation.
+
+use proc_macro2::Span;
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, spanned::Spanned, Attribute, Error, LitStr, Result};
+
+use crate::{
+    attr::{DeriveWhere, Options},
+    data::Item,
+    traits::Traits,
+};
+
+/// `#[serde(bound(...))]` for the serde traits of all `derive_wheres`, if any.
+pub fn generate_bounds(derive_wheres: &[DeriveWhere]) -> Result<Option<Attribute>> {
+    let mut bounds = Vec::new();
+
+    for derive_where in derive_wheres {
+        for (trait_, span) in &derive_where.traits {
+            let key = match trait_ {
+                Traits::Serialize => format_ident!("serialize"),
+                Traits::Deserialize => format_ident!("deserialize"),
+                _ => continue,
+            };
+
+            if derive_where.cfg.is_some() {
+                return Err(Error::new(
+                    *span,
+                    "serde traits can't be derived under a `cfg`",
+                ));
+            }
+
+            if let Some(param) = derive_where.params.first() {
+                return Err(Error::new(
+                    param.span(),
+                    "serde bounds can't use additional generic parameters",
+                ));
+            }
+
+            let predicates = LitStr::new(&derive_where.bounds.to_token_stream().to_string(), *span);
+            bounds.push(quote! { #key = #predicates });
+        }
+    }
+
+    Ok(if bounds.is_empty() {
+        None
+    } else {
+        Some(parse_quote!(#[serde(bound(#(#bounds),*))]))
+    })
+}
+
+/// `#[serde(skip)]` for each field skipped by `PartialEq`, with the index of
+/// its struct or variant and its own index.
+pub fn generate_skips(item: &Item) -> Vec<(usize, usize, Attribute)> {
+    item.datas()
+        .iter()
+        .enumerate()
+        .flat_map(|(data_index, data)| {
+            data.fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| field.skips(&Traits::PartialEq))
+                .map(move |(index, _)| (data_index, index, parse_quote!(#[serde(skip)])))
+        })
+        .collect()
+}
+
+/// Cases serde's `rename_all` supports.
+const CASES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// `#[serde(rename = "...")]` and `#[serde(rename_all = "...")]` for the item,
+/// for its variants by their index, and for its fields by the index of their
+/// struct or variant and their own index.
+#[allow(clippy::type_complexity)]
+pub fn generate_renames(
+    derive_wheres: &[DeriveWhere],
+    options: &Options,
+    item: &Item,
+) -> Result<(
+    Vec<Attribute>,
+    Vec<(usize, Attribute)>,
+    Vec<(usize, usize, Attribute)>,
+)> {
+    let attr = |rename: &Option<LitStr>, rename_all: &Option<LitStr>| -> Result<Vec<Attribute>> {
+        let mut attrs = Vec::new();
+
+        if let Some(rename) = rename {
+            attrs.push(parse_quote!(#[serde(rename = #rename)]));
+        }
+
+        if let Some(rename_all) = rename_all {
+            if !CASES.contains(&rename_all.value().as_str()) {
+                return Err(Error::new(
+                    rename_all.span(),
+                    format!("unknown case, expected one of: {}", CASES.join(", ")),
+                ));
+            }
+
+            attrs.push(parse_quote!(#[serde(rename_all = #rename_all)]));
+        }
+
+        Ok(attrs)
+    };
+
+    // Renamed fields may only be meant for the headers of `Tabled`.
+    #[cfg(feature = "tabled")]
+    let field_renames = {
+        let mut traits = derive_wheres
+            .iter()
+            .flat_map(|derive_where| &derive_where.traits)
+            .map(|(trait_, _)| trait_);
+
+        traits.clone().any(Traits::is_serde) || traits.all(|trait_| *trait_ != Traits::Tabled)
+    };
+    #[cfg(not(feature = "tabled"))]
+    let field_renames = true;
+
+    let mut renames =
+        options
+            .rename
+            .iter()
+            .chain(&options.rename_all)
+            .chain(item.datas().iter().flat_map(|data| {
+                data.options
+                    .rename
+                    .iter()
+                    .chain(&data.options.rename_all)
+                    .chain(
+                        data.fields
+                            .iter()
+                            .filter(|_| field_renames)
+                            .flat_map(|field| &field.options.rename),
+                    )
+            }));
+
+    if let Some(rename) = renames.next() {
+        require_serde(derive_wheres, rename.span(), "renaming")?;
+    }
+
+    let attrs = attr(&options.rename, &options.rename_all)?;
+    let mut variant_attrs = Vec::new();
+    let mut field_attrs = Vec::new();
+
+    for (data_index, data) in item.datas().iter().enumerate() {
+        for variant_attr in attr(&data.options.rename, &data.options.rename_all)? {
+            variant_attrs.push((data_index, variant_attr));
+        }
+
+        for (index, field) in data.fields.iter().enumerate().filter(|_| field_renames) {
+            for field_attr in attr(&field.options.rename, &None)? {
+                field_attrs.push((data_index, index, field_attr));
+            }
+        }
+    }
+
+    Ok((attrs, variant_attrs, field_attrs))
+}
+
+/// `#[serde(tag = "...")]`, `#[serde(tag = "...", content = "...")]` or
+/// `#[serde(untagged)]` for the item, if given.
+pub fn generate_tagging(
+    derive_wheres: &[DeriveWhere],
+    options: &Options,
+    item: &Item,
+) -> Result<Option<Attribute>> {
+    let span = match (&options.tag, &options.content) {
+        (Some(tag), _) => tag.span(),
+        (None, Some(content)) => {
+            return Err(Error::new(
+                content.span(),
+                "`content` requires `tag` to be given too",
+            ))
+        }
+        (None, None) if options.untagged => Span::call_site(),
+        (None, None) => return Ok(None),
+    };
+
+    require_serde(derive_wheres, span, "tagging")?;
+
+    if options.untagged && options.tag.is_some() {
+        return Err(Error::new(span, "`untagged` enums can't have a `tag`"));
+    }
+
+    if let Item::Struct(_) = item {
+        if options.untagged || options.content.is_some() {
+            return Err(Error::new(
+                span,
+                "only enums can be adjacently tagged or untagged",
+            ));
+        }
+    }
+
+    Ok(Some(match (&options.tag, &options.content) {
+        (Some(tag), Some(content)) => parse_quote!(#[serde(tag = #tag, content = #content)]),
+        (Some(tag), None) => parse_quote!(#[serde(tag = #tag)]),
+        (None, _) => parse_quote!(#[serde(untagged)]),
+    }))
+}
+
+/// Returns an error naming `what` requires serde if none of its traits are
+/// derived.
+fn require_serde(derive_wheres: &[DeriveWhere], span: Span, what: &str) -> Result<()> {
+    if derive_wheres
+        .iter()
+        .flat_map(|derive_where| &derive_where.traits)
+        .any(|(trait_, _)| trait_.is_serde())
+    {
+        Ok(())
+    } else {
+        Err(Error::new(
+            span,
+            format!("{} requires deriving `Serialize` or `Deserialize`", what),
+        ))
+    }
+}
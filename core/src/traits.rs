@@ -0,0 +1,1004 @@
+//! Supported traits and their implementations.
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "clap")]
+mod clap;
+mod clone;
+mod custom;
+mod debug;
+#[cfg(feature = "diesel")]
+mod diesel;
+mod display;
+mod eq;
+#[cfg(feature = "fake")]
+mod fake;
+mod from_str;
+mod future;
+mod hash;
+#[cfg(feature = "std")]
+mod io;
+mod iterator;
+mod key;
+#[cfg(feature = "minicbor")]
+mod minicbor;
+mod other;
+mod partial_eq;
+mod partial_ord;
+#[cfg(feature = "parity-scale-codec")]
+mod scale;
+#[cfg(feature = "speedy")]
+mod speedy;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "tabled")]
+mod tabled;
+#[cfg(feature = "zeroize")]
+mod zeroize;
+
+use std::iter;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, ToTokens};
+use syn::{
+    parse::ParseStream, spanned::Spanned, AngleBracketedGenericArguments, DeriveInput, Error,
+    GenericArgument, Generics, Ident, Path, PathArguments, PathSegment, Result, Token, Type,
+    Visibility, WherePredicate,
+};
+
+pub use custom::Custom;
+pub use debug::{DebugTemplate, FieldDebug};
+pub use hash::FieldToBits;
+pub use other::Other;
+pub use partial_ord::IncomparableOrd;
+#[cfg(feature = "tabled")]
+pub use tabled::FieldTabled;
+#[cfg(feature = "zeroize")]
+pub use zeroize::FieldZeroize;
+
+use crate::{attr::Options, data::Item};
+
+#[derive(Clone, PartialEq)]
+pub enum Traits {
+    /// arbitrary's `Arbitrary`, guarding against unbounded recursion.
+    #[cfg(feature = "arbitrary")]
+    Arbitrary,
+    /// minicbor's `Decode`, only recognized by its path.
+    #[cfg(feature = "minicbor")]
+    CborDecode,
+    /// minicbor's `Encode`, only recognized by its path.
+    #[cfg(feature = "minicbor")]
+    CborEncode,
+    Clone,
+    Copy,
+    Custom(Custom),
+    Debug,
+    /// fake's `Dummy<Faker>`, generating test data.
+    #[cfg(feature = "fake")]
+    Dummy,
+    /// diesel's `AsExpression` of a wrapper, the expression of the wrapped
+    /// field.
+    #[cfg(feature = "diesel")]
+    DieselAsExpression,
+    /// diesel's `FromSql` of a wrapper, deserializing the wrapped field.
+    #[cfg(feature = "diesel")]
+    DieselFromSql,
+    /// diesel's `ToSql` of a wrapper, serializing the wrapped field.
+    #[cfg(feature = "diesel")]
+    DieselToSql,
+    Display,
+    /// `DoubleEndedIterator` of a wrapper, iterating the wrapped field.
+    DoubleEndedIterator,
+    Eq,
+    /// `ExactSizeIterator` of a wrapper, iterating the wrapped field.
+    ExactSizeIterator,
+    /// zerocopy's `FromBytes`, derived by zerocopy's own derive.
+    #[cfg(feature = "zerocopy")]
+    FromBytes,
+    FromStr,
+    /// `FromStr(ascii_case_insensitive)`, ignoring ASCII case when parsing.
+    FromStrCaseInsensitive,
+    /// `Future` of a wrapper, polling the wrapped field.
+    Future,
+    Hash,
+    /// `Hash(portable)`, hashing the same across compiler versions and
+    /// platforms.
+    HashPortable,
+    /// zerocopy's `IntoBytes`, derived by zerocopy's own derive.
+    #[cfg(feature = "zerocopy")]
+    IntoBytes,
+    /// `Iterator` of a wrapper, iterating the wrapped field.
+    Iterator,
+    Ord,
+    PartialEq,
+    /// `PartialEq<Other>`, comparing with another type of the same shape.
+    PartialEqOther(Other),
+    PartialOrd,
+    /// `PartialOrd<Other>`, comparing with another type of the same shape.
+    PartialOrdOther(Other),
+    /// serde's `Deserialize`, only contributing bounds to serde's derive.
+    #[cfg(feature = "serde")]
+    Deserialize,
+    /// bevy_reflect's `FromReflect`, only contributing bounds to bevy_reflect's
+    /// derive of `Reflect`.
+    #[cfg(feature = "bevy_reflect")]
+    FromReflect,
+    /// bevy_reflect's `Reflect`, only contributing bounds to bevy_reflect's
+    /// derive.
+    #[cfg(feature = "bevy_reflect")]
+    Reflect,
+    /// serde's `Serialize`, only contributing bounds to serde's derive.
+    #[cfg(feature = "serde")]
+    Serialize,
+    /// parity-scale-codec's `Decode`, only recognized by its path.
+    #[cfg(feature = "parity-scale-codec")]
+    ScaleDecode,
+    /// parity-scale-codec's `Encode`, only recognized by its path.
+    #[cfg(feature = "parity-scale-codec")]
+    ScaleEncode,
+    /// parity-scale-codec's `MaxEncodedLen`.
+    #[cfg(feature = "parity-scale-codec")]
+    ScaleMaxEncodedLen,
+    /// `std::io::Read` of a wrapper, reading from the wrapped field.
+    #[cfg(feature = "std")]
+    Read,
+    /// `std::io::Seek` of a wrapper, seeking the wrapped field.
+    #[cfg(feature = "std")]
+    Seek,
+    /// `std::io::Write` of a wrapper, writing to the wrapped field.
+    #[cfg(feature = "std")]
+    Write,
+    /// speedy's `Readable`, reading the fields in declaration order.
+    #[cfg(feature = "speedy")]
+    SpeedyReadable,
+    /// speedy's `Writable`, writing the fields in declaration order.
+    #[cfg(feature = "speedy")]
+    SpeedyWritable,
+    /// sqlx's `Decode` of a wrapper, decoding the wrapped field.
+    #[cfg(feature = "sqlx")]
+    SqlxDecode,
+    /// sqlx's `Encode` of a wrapper, encoding the wrapped field.
+    #[cfg(feature = "sqlx")]
+    SqlxEncode,
+    /// sqlx's `Type` of a wrapper, the type of the wrapped field.
+    #[cfg(feature = "sqlx")]
+    SqlxType,
+    /// tabled's `Tabled`, a column for each field of a struct.
+    #[cfg(feature = "tabled")]
+    Tabled,
+    /// clap's `ValueEnum`, for enums without fields.
+    #[cfg(feature = "clap")]
+    ValueEnum,
+    #[cfg(feature = "zeroize")]
+    Zeroize,
+}
+
+impl Traits {
+    /// Parses a trait from its bare name or its full path through `core` or
+    /// `std`, e.g. `Clone` or `::core::clone::Clone`. Comparisons can be
+    /// given another type to compare with, e.g. `PartialEq<Other>`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        use Traits::*;
+
+        let error = || Error::new(path.span(), "unsupported trait");
+
+        let mut segments = path.segments.iter().rev();
+        let last = segments.next().ok_or_else(error)?;
+
+        if segments.any(|segment| !segment.arguments.is_empty()) {
+            return Err(error());
+        }
+
+        // `Encode`, `Decode` and `Type` are common names, those of minicbor,
+        // parity-scale-codec and sqlx are only recognized by their path.
+        #[cfg(any(feature = "minicbor", feature = "parity-scale-codec", feature = "sqlx"))]
+        if let [krate, name] = path.segments.iter().map(|s| &s.ident).collect::<Vec<_>>()[..] {
+            if last.arguments.is_empty() {
+                match (krate.to_string().as_str(), name.to_string().as_str()) {
+                    #[cfg(feature = "minicbor")]
+                    ("minicbor", "Decode") => return Ok(CborDecode),
+                    #[cfg(feature = "minicbor")]
+                    ("minicbor", "Encode") => return Ok(CborEncode),
+                    #[cfg(feature = "parity-scale-codec")]
+                    ("parity_scale_codec", "Decode") => return Ok(ScaleDecode),
+                    #[cfg(feature = "parity-scale-codec")]
+                    ("parity_scale_codec", "Encode") => return Ok(ScaleEncode),
+                    #[cfg(feature = "parity-scale-codec")]
+                    ("parity_scale_codec", "MaxEncodedLen") => return Ok(ScaleMaxEncodedLen),
+                    #[cfg(feature = "sqlx")]
+                    ("sqlx", "Decode") => return Ok(SqlxDecode),
+                    #[cfg(feature = "sqlx")]
+                    ("sqlx", "Encode") => return Ok(SqlxEncode),
+                    #[cfg(feature = "sqlx")]
+                    ("sqlx", "Type") => return Ok(SqlxType),
+                    _ => (),
+                }
+            }
+        }
+
+        let trait_ = match (last.ident.to_string().as_str(), &last.arguments) {
+            ("PartialEq", arguments @ PathArguments::AngleBracketed(_)) => {
+                PartialEqOther(Other::from_arguments(arguments)?)
+            }
+            ("PartialOrd", arguments @ PathArguments::AngleBracketed(_)) => {
+                PartialOrdOther(Other::from_arguments(arguments)?)
+            }
+            (_, PathArguments::AngleBracketed(_) | PathArguments::Parenthesized(_)) => {
+                return Err(error())
+            }
+            #[cfg(feature = "arbitrary")]
+            ("Arbitrary", _) => Arbitrary,
+            #[cfg(feature = "diesel")]
+            ("AsExpression", _) => DieselAsExpression,
+            ("Clone", _) => Clone,
+            ("Copy", _) => Copy,
+            ("Debug", _) => Debug,
+            #[cfg(feature = "fake")]
+            ("Dummy", _) => Dummy,
+            ("Display", _) => Display,
+            ("DoubleEndedIterator", _) => DoubleEndedIterator,
+            ("Eq", _) => Eq,
+            ("ExactSizeIterator", _) => ExactSizeIterator,
+            #[cfg(feature = "diesel")]
+            ("FromSql", _) => DieselFromSql,
+            ("FromStr", _) => FromStr,
+            ("Future", _) => Future,
+            ("Hash", _) => Hash,
+            ("Iterator", _) => Iterator,
+            #[cfg(feature = "parity-scale-codec")]
+            ("MaxEncodedLen", _) => ScaleMaxEncodedLen,
+            ("Ord", _) => Ord,
+            ("PartialEq", _) => PartialEq,
+            ("PartialOrd", _) => PartialOrd,
+            #[cfg(feature = "speedy")]
+            ("Readable", _) => SpeedyReadable,
+            #[cfg(feature = "diesel")]
+            ("ToSql", _) => DieselToSql,
+            #[cfg(feature = "serde")]
+            ("Deserialize", _) => Deserialize,
+            #[cfg(feature = "bevy_reflect")]
+            ("FromReflect", _) => FromReflect,
+            #[cfg(feature = "bevy_reflect")]
+            ("Reflect", _) => Reflect,
+            #[cfg(feature = "serde")]
+            ("Serialize", _) => Serialize,
+            #[cfg(feature = "std")]
+            ("Read", _) => Read,
+            #[cfg(feature = "std")]
+            ("Seek", _) => Seek,
+            #[cfg(feature = "std")]
+            ("Write", _) => Write,
+            #[cfg(feature = "speedy")]
+            ("Writable", _) => SpeedyWritable,
+            #[cfg(feature = "tabled")]
+            ("Tabled", _) => Tabled,
+            #[cfg(feature = "clap")]
+            ("ValueEnum", _) => ValueEnum,
+            #[cfg(feature = "zeroize")]
+            ("Zeroize", _) => Zeroize,
+            #[cfg(feature = "zerocopy")]
+            ("FromBytes", _) => FromBytes,
+            #[cfg(feature = "zerocopy")]
+            ("IntoBytes", _) => IntoBytes,
+            _ => return Err(error()),
+        };
+
+        let segments: Vec<_> = path.segments.iter().map(|s| &s.ident).collect();
+
+        match segments.as_slice() {
+            [_] if path.leading_colon.is_none() => Ok(trait_),
+            #[cfg(feature = "serde")]
+            [krate, _] if trait_.is_serde() && krate == &"serde" => Ok(trait_),
+            #[cfg(feature = "bevy_reflect")]
+            [krate, _] if trait_.is_reflect() && krate == &"bevy_reflect" => Ok(trait_),
+            #[cfg(feature = "zeroize")]
+            [krate, _] if trait_ == Zeroize && krate == &"zeroize" => Ok(trait_),
+            #[cfg(feature = "zerocopy")]
+            [krate, _] if trait_.is_zerocopy() && krate == &"zerocopy" => Ok(trait_),
+            #[cfg(feature = "tabled")]
+            [krate, _] if trait_ == Tabled && krate == &"tabled" => Ok(trait_),
+            #[cfg(feature = "clap")]
+            [krate, _] if trait_ == ValueEnum && krate == &"clap" => Ok(trait_),
+            #[cfg(feature = "arbitrary")]
+            [krate, _] if trait_ == Arbitrary && krate == &"arbitrary" => Ok(trait_),
+            #[cfg(feature = "fake")]
+            [krate, _] if trait_ == Dummy && krate == &"fake" => Ok(trait_),
+            #[cfg(feature = "speedy")]
+            [krate, _] if trait_.is_speedy() && krate == &"speedy" => Ok(trait_),
+            #[cfg(feature = "diesel")]
+            [krate, module, _] if trait_.is_diesel() => {
+                if krate == &"diesel" && module == &trait_.module() {
+                    Ok(trait_)
+                } else {
+                    Err(error())
+                }
+            }
+            #[cfg(feature = "std")]
+            [krate, module, _] if trait_.is_std() => {
+                if krate == &"std" && module == &trait_.module() {
+                    Ok(trait_)
+                } else {
+                    Err(error())
+                }
+            }
+            [krate, module, _]
+                if (krate == &"core" || krate == &"std") && module == &trait_.module() =>
+            {
+                Ok(trait_)
+            }
+            _ => Err(error()),
+        }
+    }
+
+    /// Applies options given in parentheses after the trait, e.g.
+    /// `Hash(portable)`.
+    pub fn parse_options(self, input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        if !input.is_empty() {
+            return Err(input.error("expected a single option"));
+        }
+
+        match (self, ident.to_string().as_str()) {
+            (Traits::FromStr, "ascii_case_insensitive") => Ok(Traits::FromStrCaseInsensitive),
+            (Traits::Hash, "portable") => Ok(Traits::HashPortable),
+            _ => Err(Error::new(ident.span(), "unsupported option")),
+        }
+    }
+
+    /// Returns `true` if both implement the same trait, like `Hash` and
+    /// `Hash(portable)`, so they can't both be derived.
+    pub fn is_same_trait(&self, other: &Self) -> bool {
+        let options = Options::default();
+
+        self == other
+            || self.path(&options).to_token_stream().to_string()
+                == other.path(&options).to_token_stream().to_string()
+    }
+
+    /// Module of the trait in `core`, or in its own crate for diesel's traits.
+    fn module(&self) -> &'static str {
+        use Traits::*;
+
+        match self {
+            Clone => "clone",
+            #[cfg(feature = "arbitrary")]
+            Arbitrary => unreachable!("`Arbitrary` isn't in `core`"),
+            #[cfg(feature = "fake")]
+            Dummy => unreachable!("`Dummy` isn't in `core`"),
+            #[cfg(feature = "minicbor")]
+            CborDecode | CborEncode => unreachable!("minicbor traits aren't in `core`"),
+            Copy => "marker",
+            Custom(_) => unreachable!("custom traits are given by path"),
+            Debug | Display => "fmt",
+            #[cfg(feature = "diesel")]
+            DieselAsExpression => "expression",
+            #[cfg(feature = "diesel")]
+            DieselFromSql => "deserialize",
+            #[cfg(feature = "diesel")]
+            DieselToSql => "serialize",
+            Eq | Ord | PartialEq | PartialEqOther(_) | PartialOrd | PartialOrdOther(_) => "cmp",
+            FromStr | FromStrCaseInsensitive => "str",
+            Future => "future",
+            Hash | HashPortable => "hash",
+            DoubleEndedIterator | ExactSizeIterator | Iterator => "iter",
+            #[cfg(feature = "serde")]
+            Deserialize | Serialize => unreachable!("serde traits aren't in `core`"),
+            #[cfg(feature = "bevy_reflect")]
+            FromReflect | Reflect => unreachable!("bevy_reflect traits aren't in `core`"),
+            #[cfg(feature = "std")]
+            Read | Seek | Write => "io",
+            #[cfg(feature = "speedy")]
+            SpeedyReadable | SpeedyWritable => unreachable!("speedy traits aren't in `core`"),
+            #[cfg(feature = "parity-scale-codec")]
+            ScaleDecode | ScaleEncode | ScaleMaxEncodedLen => {
+                unreachable!("parity-scale-codec traits aren't in `core`")
+            }
+            #[cfg(feature = "sqlx")]
+            SqlxDecode | SqlxEncode | SqlxType => unreachable!("sqlx traits aren't in `core`"),
+            #[cfg(feature = "tabled")]
+            Tabled => unreachable!("`Tabled` isn't in `core`"),
+            #[cfg(feature = "clap")]
+            ValueEnum => unreachable!("`ValueEnum` isn't in `core`"),
+            #[cfg(feature = "zeroize")]
+            Zeroize => unreachable!("`Zeroize` isn't in `core`"),
+            #[cfg(feature = "zerocopy")]
+            FromBytes | IntoBytes => unreachable!("zerocopy traits aren't in `core`"),
+        }
+    }
+
+    pub fn ident(&self) -> Ident {
+        use Traits::*;
+
+        format_ident!(
+            "{}",
+            match self {
+                #[cfg(feature = "arbitrary")]
+                Arbitrary => "Arbitrary",
+                #[cfg(feature = "fake")]
+                Dummy => "Dummy",
+                #[cfg(feature = "minicbor")]
+                CborDecode => "Decode",
+                #[cfg(feature = "minicbor")]
+                CborEncode => "Encode",
+                Clone => "Clone",
+                Copy => "Copy",
+                Custom(custom) => return custom.ident(),
+                Debug => "Debug",
+                #[cfg(feature = "diesel")]
+                DieselAsExpression => "AsExpression",
+                #[cfg(feature = "diesel")]
+                DieselFromSql => "FromSql",
+                #[cfg(feature = "diesel")]
+                DieselToSql => "ToSql",
+                Display => "Display",
+                DoubleEndedIterator => "DoubleEndedIterator",
+                Eq => "Eq",
+                ExactSizeIterator => "ExactSizeIterator",
+                FromStr | FromStrCaseInsensitive => "FromStr",
+                Future => "Future",
+                Hash | HashPortable => "Hash",
+                Iterator => "Iterator",
+                Ord => "Ord",
+                PartialEq | PartialEqOther(_) => "PartialEq",
+                PartialOrd | PartialOrdOther(_) => "PartialOrd",
+                #[cfg(feature = "serde")]
+                Deserialize => "Deserialize",
+                #[cfg(feature = "bevy_reflect")]
+                FromReflect => "FromReflect",
+                #[cfg(feature = "bevy_reflect")]
+                Reflect => "Reflect",
+                #[cfg(feature = "serde")]
+                Serialize => "Serialize",
+                #[cfg(feature = "std")]
+                Read => "Read",
+                #[cfg(feature = "std")]
+                Seek => "Seek",
+                #[cfg(feature = "std")]
+                Write => "Write",
+                #[cfg(feature = "speedy")]
+                SpeedyReadable => "Readable",
+                #[cfg(feature = "parity-scale-codec")]
+                ScaleDecode => "Decode",
+                #[cfg(feature = "parity-scale-codec")]
+                ScaleEncode => "Encode",
+                #[cfg(feature = "parity-scale-codec")]
+                ScaleMaxEncodedLen => "MaxEncodedLen",
+                #[cfg(feature = "speedy")]
+                SpeedyWritable => "Writable",
+                #[cfg(feature = "sqlx")]
+                SqlxDecode => "Decode",
+                #[cfg(feature = "sqlx")]
+                SqlxEncode => "Encode",
+                #[cfg(feature = "sqlx")]
+                SqlxType => "Type",
+                #[cfg(feature = "tabled")]
+                Tabled => "Tabled",
+                #[cfg(feature = "clap")]
+                ValueEnum => "ValueEnum",
+                #[cfg(feature = "zeroize")]
+                Zeroize => "Zeroize",
+                #[cfg(feature = "zerocopy")]
+                FromBytes => "FromBytes",
+                #[cfg(feature = "zerocopy")]
+                IntoBytes => "IntoBytes",
+            }
+        )
+    }
+
+    /// Absolute path to the trait, built directly instead of parsed, as it's
+    /// needed for every trait of every item. Integration crates are taken from
+    /// their `<crate>_crate` option if given.
+    pub fn path(&self, options: &Options) -> Path {
+        let (krate, module) = match self {
+            Traits::Custom(custom) => return custom.path.clone(),
+            #[cfg(feature = "serde")]
+            Traits::Deserialize | Traits::Serialize => ("serde", None),
+            #[cfg(feature = "bevy_reflect")]
+            Traits::FromReflect | Traits::Reflect => ("bevy_reflect", None),
+            #[cfg(feature = "tabled")]
+            Traits::Tabled => ("tabled", None),
+            #[cfg(feature = "clap")]
+            Traits::ValueEnum => ("clap", None),
+            #[cfg(feature = "minicbor")]
+            Traits::CborDecode | Traits::CborEncode => ("minicbor", None),
+            #[cfg(feature = "parity-scale-codec")]
+            Traits::ScaleDecode | Traits::ScaleEncode | Traits::ScaleMaxEncodedLen => {
+                ("parity_scale_codec", None)
+            }
+            #[cfg(feature = "zeroize")]
+            Traits::Zeroize => ("zeroize", None),
+            #[cfg(feature = "zerocopy")]
+            Traits::FromBytes | Traits::IntoBytes => ("zerocopy", None),
+            #[cfg(feature = "arbitrary")]
+            Traits::Arbitrary => ("arbitrary", None),
+            #[cfg(feature = "fake")]
+            Traits::Dummy => ("fake", None),
+            #[cfg(feature = "speedy")]
+            trait_ if trait_.is_speedy() => ("speedy", None),
+            #[cfg(feature = "sqlx")]
+            trait_ if trait_.is_sqlx() => ("sqlx", None),
+            #[cfg(feature = "std")]
+            trait_ if trait_.is_std() => ("std", Some(self.module())),
+            #[cfg(feature = "diesel")]
+            trait_ if trait_.is_diesel() => ("diesel", Some(self.module())),
+            _ => ("core", Some(self.module())),
+        };
+
+        let span = Span::call_site();
+        let mut path = options.crate_path(krate);
+
+        if let Some(module) = module {
+            path.segments
+                .push(PathSegment::from(Ident::new(module, span)));
+        }
+
+        let mut last = PathSegment::from(self.ident());
+
+        if let Traits::PartialEqOther(other) | Traits::PartialOrdOther(other) = self {
+            last.arguments = PathArguments::AngleBracketed(AngleBracketedGenericArguments {
+                colon2_token: None,
+                lt_token: Default::default(),
+                args: iter::once(GenericArgument::Type(Type::Path(other.ty.clone()))).collect(),
+                gt_token: Default::default(),
+            });
+        }
+
+        path.segments.push(last);
+        path
+    }
+
+    /// Supertraits of this trait, which are derived along with it when
+    /// implied traits are enabled.
+    pub fn implied(&self) -> &'static [Self] {
+        use Traits::*;
+
+        match self {
+            Copy => &[Clone],
+            Eq | PartialOrd => &[PartialEq],
+            Ord => &[PartialOrd, Eq, PartialEq],
+            DoubleEndedIterator | ExactSizeIterator => &[Iterator],
+            Clone
+            | Custom(_)
+            | Debug
+            | Display
+            | FromStr
+            | FromStrCaseInsensitive
+            | Future
+            | Hash
+            | HashPortable
+            | Iterator
+            | PartialEq
+            | PartialEqOther(_)
+            | PartialOrdOther(_) => &[],
+            #[cfg(feature = "arbitrary")]
+            Arbitrary => &[],
+            #[cfg(feature = "fake")]
+            Dummy => &[],
+            #[cfg(feature = "minicbor")]
+            CborDecode | CborEncode => &[],
+            #[cfg(feature = "serde")]
+            Deserialize | Serialize => &[],
+            #[cfg(feature = "bevy_reflect")]
+            FromReflect => &[Reflect],
+            #[cfg(feature = "bevy_reflect")]
+            Reflect => &[],
+            #[cfg(feature = "std")]
+            Read | Seek | Write => &[],
+            #[cfg(feature = "speedy")]
+            SpeedyReadable | SpeedyWritable => &[],
+            #[cfg(feature = "parity-scale-codec")]
+            ScaleDecode | ScaleEncode => &[],
+            #[cfg(feature = "parity-scale-codec")]
+            ScaleMaxEncodedLen => &[ScaleEncode],
+            #[cfg(feature = "sqlx")]
+            SqlxDecode | SqlxEncode | SqlxType => &[],
+            #[cfg(feature = "diesel")]
+            DieselAsExpression | DieselFromSql | DieselToSql => &[],
+            #[cfg(feature = "tabled")]
+            Tabled => &[],
+            #[cfg(feature = "clap")]
+            ValueEnum => &[Clone],
+            #[cfg(feature = "zeroize")]
+            Zeroize => &[],
+            #[cfg(feature = "zerocopy")]
+            FromBytes | IntoBytes => &[],
+        }
+    }
+
+    /// Returns `true` if the trait can be implemented in terms of a single
+    /// field or key.
+    pub fn is_delegatable(&self) -> bool {
+        use Traits::*;
+
+        matches!(
+            self,
+            Eq | Hash | HashPortable | Ord | PartialEq | PartialOrd
+        )
+    }
+
+    /// Returns `true` for traits of wrappers, implemented by forwarding to the
+    /// wrapped field.
+    pub fn is_wrapper(&self) -> bool {
+        use Traits::*;
+
+        match self {
+            DoubleEndedIterator | ExactSizeIterator | Future | Iterator => true,
+            #[cfg(feature = "std")]
+            Read | Seek | Write => true,
+            #[cfg(feature = "sqlx")]
+            SqlxDecode | SqlxEncode | SqlxType => true,
+            #[cfg(feature = "diesel")]
+            DieselAsExpression | DieselFromSql | DieselToSql => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` for traits with generic parameters, which fields can't
+    /// be checked against by their path alone.
+    pub fn is_generic(&self) -> bool {
+        use Traits::*;
+
+        match self {
+            PartialEqOther(_) | PartialOrdOther(_) => true,
+            #[cfg(feature = "arbitrary")]
+            Arbitrary => true,
+            #[cfg(feature = "fake")]
+            Dummy => true,
+            #[cfg(feature = "minicbor")]
+            CborDecode | CborEncode => true,
+            #[cfg(feature = "diesel")]
+            trait_ if trait_.is_diesel() => true,
+            #[cfg(feature = "speedy")]
+            trait_ if trait_.is_speedy() => true,
+            #[cfg(feature = "sqlx")]
+            trait_ if trait_.is_sqlx() => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` for traits only in `std`, not in `core`.
+    #[cfg(feature = "std")]
+    fn is_std(&self) -> bool {
+        matches!(self, Traits::Read | Traits::Seek | Traits::Write)
+    }
+
+    /// Returns `true` for diesel's traits, which are generic over the SQL type
+    /// and backend.
+    #[cfg(feature = "diesel")]
+    fn is_diesel(&self) -> bool {
+        matches!(
+            self,
+            Traits::DieselAsExpression | Traits::DieselFromSql | Traits::DieselToSql
+        )
+    }
+
+    /// Returns `true` for speedy's traits, which are generic over the context.
+    #[cfg(feature = "speedy")]
+    fn is_speedy(&self) -> bool {
+        matches!(self, Traits::SpeedyReadable | Traits::SpeedyWritable)
+    }
+
+    /// Returns `true` for sqlx's traits, which are generic over the database.
+    #[cfg(feature = "sqlx")]
+    fn is_sqlx(&self) -> bool {
+        matches!(
+            self,
+            Traits::SqlxDecode | Traits::SqlxEncode | Traits::SqlxType
+        )
+    }
+
+    /// Returns `true` for serde's traits, which aren't implemented but add
+    /// their bounds to serde's derive.
+    pub fn is_serde(&self) -> bool {
+        match self {
+            #[cfg(feature = "serde")]
+            Traits::Deserialize | Traits::Serialize => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` for bevy_reflect's traits, which aren't implemented but
+    /// add their bounds to bevy_reflect's derive.
+    pub fn is_reflect(&self) -> bool {
+        match self {
+            #[cfg(feature = "bevy_reflect")]
+            Traits::FromReflect | Traits::Reflect => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` for zerocopy's traits, which aren't implemented but
+    /// derived by zerocopy's own derive, added to the item.
+    pub fn is_zerocopy(&self) -> bool {
+        match self {
+            #[cfg(feature = "zerocopy")]
+            Traits::FromBytes | Traits::IntoBytes => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` for traits implemented by their crate's own derive,
+    /// which only the bounds are passed on to.
+    pub fn is_external(&self) -> bool {
+        self.is_serde() || self.is_reflect()
+    }
+
+    /// Returns `true` if fields can opt out of the trait with `skip(...)`.
+    pub fn is_skippable(&self) -> bool {
+        use Traits::*;
+
+        match self {
+            Debug | Hash | Ord | PartialEq | PartialOrd => true,
+            #[cfg(feature = "arbitrary")]
+            Arbitrary => true,
+            #[cfg(feature = "fake")]
+            Dummy => true,
+            #[cfg(feature = "minicbor")]
+            CborDecode | CborEncode => true,
+            #[cfg(feature = "bevy_reflect")]
+            Reflect => true,
+            #[cfg(feature = "speedy")]
+            SpeedyReadable | SpeedyWritable => true,
+            #[cfg(feature = "parity-scale-codec")]
+            ScaleDecode | ScaleEncode => true,
+            #[cfg(feature = "tabled")]
+            Tabled => true,
+            #[cfg(feature = "zeroize")]
+            Zeroize => true,
+            _ => false,
+        }
+    }
+
+    /// Checks that the trait can be derived for the item.
+    pub fn check(&self, item: &Item, options: &Options) -> Result<()> {
+        match self {
+            Traits::Debug => {
+                if options.debug_discriminant && matches!(item, Item::Struct(_)) {
+                    Err(Error::new(
+                        Span::call_site(),
+                        "`debug_discriminant` is only supported for enums",
+                    ))
+                } else if options.debug_type_names && !options.supports(38) {
+                    Err(Error::new(
+                        Span::call_site(),
+                        "`debug_type_names` requires Rust 1.38 for `type_name`",
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            Traits::Display => display::check(item),
+            #[cfg(feature = "tabled")]
+            Traits::Tabled => tabled::check(item),
+            #[cfg(feature = "clap")]
+            Traits::ValueEnum => clap::check(item),
+            #[cfg(feature = "parity-scale-codec")]
+            Traits::ScaleDecode | Traits::ScaleEncode => scale::check(item),
+            #[cfg(feature = "minicbor")]
+            Traits::CborDecode | Traits::CborEncode => minicbor::check(item),
+            Traits::HashPortable => match &options.key {
+                // The type of the key isn't known, so it can't be hashed portably.
+                Some(key) => Err(Error::new(
+                    key.span(),
+                    "`Hash(portable)` can't be combined with `key` or `via`",
+                )),
+                None => hash::check_portable(item),
+            },
+            Traits::Eq | Traits::Ord | Traits::PartialOrd if options.key.is_none() => {
+                partial_ord::check(item, self, options.incomparable_ord)
+            }
+            #[cfg(feature = "nightly")]
+            Traits::PartialEq if options.structural_match => {
+                partial_eq::check_structural(item, options)
+            }
+            Traits::FromStr => from_str::check(item, false),
+            trait_ if trait_.is_wrapper() => trait_.check_wrapper(item),
+            Traits::FromStrCaseInsensitive => from_str::check(item, true),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the item wraps a single field the trait can be forwarded to.
+    fn check_wrapper(&self, item: &Item) -> Result<()> {
+        if item.is_remote() {
+            return Err(Error::new(
+                Span::call_site(),
+                format!("`{}` can't be derived remotely", self.ident()),
+            ));
+        }
+
+        match item.wrapped_field(self) {
+            Some(_) => Ok(()),
+            None => Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "`{0}` needs a struct with a single field, or a field marked `delegate({0})` or `transparent`",
+                    self.ident()
+                ),
+            )),
+        }
+    }
+
+    /// Generates the items of the trait implementation, in terms of the `key`
+    /// option instead of the fields if given and supported by the trait.
+    ///
+    /// `siblings` are the traits derived with the same bounds, which the
+    /// implementation forwards to where possible to keep the expansion small:
+    /// `Clone` to `Copy` and `PartialOrd` to `Ord`.
+    pub fn generate_body(
+        &self,
+        item: &Item,
+        options: &Options,
+        siblings: &[(Traits, Span)],
+    ) -> TokenStream {
+        use Traits::*;
+
+        let hash_tag = hash::generate_tag(options);
+        let ptr_eq = partial_eq::generate_ptr_eq(self, options);
+
+        if let Some(key) = options.key.as_ref().filter(|_| self.is_delegatable()) {
+            return key::generate_body(self, key, &hash_tag, &ptr_eq);
+        }
+
+        let derived = |trait_: &Traits| siblings.iter().any(|(sibling, _)| sibling == trait_);
+
+        match self {
+            #[cfg(feature = "arbitrary")]
+            Arbitrary => unreachable!("generated by `generate_impl`"),
+            #[cfg(feature = "fake")]
+            Dummy => unreachable!("generated by `generate_impl`"),
+            #[cfg(feature = "minicbor")]
+            CborDecode | CborEncode => unreachable!("generated by `generate_impl`"),
+            Clone if derived(&Copy) && !clone::uses_with(item) => clone::generate_body_copy(),
+            Clone => clone::generate_body(item),
+            Copy => TokenStream::new(),
+            Custom(custom) => custom::generate_body(item, custom),
+            Debug => debug::generate_body(item, options),
+            Display => display::generate_body(item),
+            DoubleEndedIterator | ExactSizeIterator | Iterator => {
+                iterator::generate_body(item, self)
+            }
+            Eq => TokenStream::new(),
+            FromStr | FromStrCaseInsensitive => unreachable!("generated by `generate_impl`"),
+            Future => future::generate_body(item),
+            Hash => hash::generate_body(item, options, false, &hash_tag),
+            HashPortable => hash::generate_body(item, options, true, &hash_tag),
+            PartialOrd if derived(&Ord) && item.same_fields(&PartialOrd, &Ord) => {
+                partial_ord::generate_body_ord()
+            }
+            Ord | PartialOrd | PartialOrdOther(_) => {
+                partial_ord::generate_body(item, self, options)
+            }
+            PartialEq => partial_eq::generate_body(item, options, &ptr_eq),
+            PartialEqOther(other) => partial_eq::generate_body_other(item, other),
+            #[cfg(feature = "serde")]
+            Deserialize | Serialize => unreachable!("serde traits aren't implemented"),
+            #[cfg(feature = "bevy_reflect")]
+            FromReflect | Reflect => unreachable!("bevy_reflect traits aren't implemented"),
+            #[cfg(feature = "std")]
+            Read | Seek | Write => io::generate_body(item, self, options),
+            #[cfg(feature = "speedy")]
+            SpeedyReadable | SpeedyWritable => unreachable!("generated by `generate_impl`"),
+            #[cfg(feature = "parity-scale-codec")]
+            ScaleDecode | ScaleEncode | ScaleMaxEncodedLen => {
+                scale::generate_body(item, self, options)
+            }
+            #[cfg(feature = "sqlx")]
+            SqlxDecode | SqlxEncode | SqlxType => unreachable!("generated by `generate_impl`"),
+            #[cfg(feature = "diesel")]
+            DieselAsExpression | DieselFromSql | DieselToSql => {
+                unreachable!("generated by `generate_impl`")
+            }
+            #[cfg(feature = "tabled")]
+            Tabled => tabled::generate_body(item),
+            #[cfg(feature = "clap")]
+            ValueEnum => clap::generate_body(item, options),
+            #[cfg(feature = "zeroize")]
+            Zeroize => zeroize::generate_body(item, options),
+            #[cfg(feature = "zerocopy")]
+            FromBytes | IntoBytes => unreachable!("zerocopy traits aren't implemented"),
+        }
+    }
+
+    /// Generates the whole trait implementation for traits with generic
+    /// parameters of their own, or naming types generated for the item, which
+    /// [`Traits::generate_body`] can't be used for. `ident` is the name of the
+    /// item, and `generics` and `predicates`
+    /// are those of the other impls.
+    // Only traits bounding the types of fields use all parameters, `ident`
+    // in particular, to leave out recursive ones.
+    #[cfg_attr(
+        not(any(
+            feature = "arbitrary",
+            feature = "fake",
+            feature = "minicbor",
+            feature = "speedy"
+        )),
+        allow(unused_variables)
+    )]
+    pub fn generate_impl(
+        &self,
+        item: &Item,
+        options: &Options,
+        ident: &Ident,
+        self_ty: &TokenStream,
+        generics: &Generics,
+        predicates: &[&WherePredicate],
+    ) -> Option<TokenStream> {
+        match self {
+            #[cfg(feature = "arbitrary")]
+            Traits::Arbitrary => Some(arbitrary::generate_impl(
+                item, options, ident, self_ty, generics, predicates,
+            )),
+            #[cfg(feature = "fake")]
+            Traits::Dummy => Some(fake::generate_impl(
+                item, options, ident, self_ty, generics, predicates,
+            )),
+            #[cfg(feature = "minicbor")]
+            Traits::CborDecode | Traits::CborEncode => Some(minicbor::generate_impl(
+                item, options, self, ident, self_ty, generics, predicates,
+            )),
+            #[cfg(feature = "diesel")]
+            trait_ if trait_.is_diesel() => Some(diesel::generate_impl(
+                item, options, trait_, self_ty, generics, predicates,
+            )),
+            #[cfg(feature = "speedy")]
+            trait_ if trait_.is_speedy() => Some(speedy::generate_impl(
+                item, options, trait_, ident, self_ty, generics, predicates,
+            )),
+            #[cfg(feature = "sqlx")]
+            trait_ if trait_.is_sqlx() => Some(sqlx::generate_impl(
+                item, options, trait_, self_ty, generics, predicates,
+            )),
+            Traits::FromStr | Traits::FromStrCaseInsensitive => Some(from_str::generate_impl(
+                item,
+                *self == Traits::FromStrCaseInsensitive,
+                ident,
+                self_ty,
+                generics,
+                predicates,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Generates methods for an inherent impl with the same bounds as the trait
+    /// implementation, checking requirements the trait impl can't express.
+    pub fn generate_assertions(&self, item: &Item, options: &Options) -> Option<TokenStream> {
+        match (self, &options.key) {
+            (Traits::Eq, Some(key)) => Some(key::generate_assertions(key)),
+            (Traits::Eq, None) => eq::generate_assertions(item),
+            _ => None,
+        }
+    }
+
+    /// Generates types the trait implementation names, like the error of
+    /// `FromStr`, next to the item and visible like it.
+    pub fn generate_types(&self, ident: &Ident, vis: &Visibility) -> Option<TokenStream> {
+        match self {
+            Traits::FromStr | Traits::FromStrCaseInsensitive => {
+                Some(from_str::generate_error(ident, vis))
+            }
+            _ => None,
+        }
+    }
+
+    /// Generates items next to the trait implementation, upholding guarantees
+    /// its body relies on.
+    #[cfg_attr(not(feature = "parity-scale-codec"), allow(unused_variables))]
+    pub fn generate_items(
+        &self,
+        item: &Item,
+        input: &DeriveInput,
+        options: &Options,
+    ) -> Option<TokenStream> {
+        match self {
+            Traits::Future => Some(future::generate_items(item, input)),
+            #[cfg(feature = "parity-scale-codec")]
+            Traits::ScaleDecode | Traits::ScaleEncode => scale::generate_items(item, options),
+            _ => None,
+        }
+    }
+}
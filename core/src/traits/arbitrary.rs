@@ -0,0 +1,244 @@
+//! [`Arbitrary`](https://docs.rs/arbitrary) implementation of arbitrary,
+//! choosing variants by a `u32` tag like arbitrary's own derive.
+//!
+//! Fields whose type mentions the item itself recurse, so items with such
+//! fields count their depth in a thread-local: from a depth of 20 on, or when
+//! the data runs out, enums only choose variants without recursive fields, and
+//! structs fail with `NotEnoughData`. `try_size_hint` is guarded by
+//! arbitrary's `try_recursion_guard`.
+//!
+//! The impl is generic over the lifetime of the data, so it's generated whole,
+//! with bounds on the types of the other fields: recursive fields don't add
+//! bounds, which would require the impl itself.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, spanned::Spanned, GenericParam, Generics, Ident, Lifetime, WherePredicate};
+
+use crate::{
+    attr::Options,
+    data::{Data, Item},
+    traits::Traits,
+};
+
+/// Depth from which recursion is avoided.
+const MAX_DEPTH: usize = 20;
+
+pub fn generate_impl(
+    item: &Item,
+    options: &Options,
+    ident: &Ident,
+    self_ty: &TokenStream,
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+) -> TokenStream {
+    let krate = options.crate_path("arbitrary");
+    let mut generics = generics.clone();
+    let lifetime = Lifetime::new("'__arbitrary", Span::call_site());
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+    let (impl_generics, ..) = generics.split_for_impl();
+
+    let mut bounds = Vec::new();
+    let mut constructors = Vec::new();
+    let mut hints = Vec::new();
+    // Uninhabited structs and variants can't be generated.
+    let datas: Vec<_> = item
+        .datas()
+        .iter()
+        .filter(|data| !data.is_uninhabited())
+        .collect();
+
+    for data in &datas {
+        let mut field_hints = Vec::new();
+        let fields = data.fields.iter().map(|field| {
+            let member = &field.member;
+            let ty = &field.field.ty;
+
+            if field.skips(&Traits::Arbitrary) {
+                bounds.push(quote! { #ty: ::core::default::Default });
+                quote! { #member: ::core::default::Default::default() }
+            } else {
+                if !field.is_recursive(ident) {
+                    bounds.push(quote! { #ty: #krate::Arbitrary<#lifetime> });
+                }
+
+                field_hints.push(quote_spanned! {ty.span()=>
+                    <#ty as #krate::Arbitrary<#lifetime>>::try_size_hint(depth)?
+                });
+                quote_spanned! {ty.span()=>
+                    #member: #krate::Arbitrary::arbitrary(u)?
+                }
+            }
+        });
+        let fields: Vec<_> = fields.collect();
+        let path = &data.path;
+
+        constructors.push(data.wrap(quote! { #path { #(#fields,)* } }));
+        hints.push(quote! { #krate::size_hint::and_all(&[#(#field_hints),*]) });
+    }
+
+    let recursive: Vec<_> = datas
+        .iter()
+        .map(|data| is_data_recursive(data, ident))
+        .collect();
+    let any_recursive = recursive.contains(&true);
+
+    let (body, hint) = match item {
+        Item::Struct(_) if datas.is_empty() => (
+            quote! { ::core::result::Result::Err(#krate::Error::IncorrectFormat) },
+            quote! { (0, ::core::option::Option::Some(0)) },
+        ),
+        Item::Struct(_) => {
+            let guard = if any_recursive {
+                quote! {
+                    if __depth >= #MAX_DEPTH {
+                        return ::core::result::Result::Err(#krate::Error::NotEnoughData);
+                    }
+                }
+            } else {
+                TokenStream::new()
+            };
+
+            (
+                quote! {
+                    #guard
+                    ::core::result::Result::Ok(#(#constructors)*)
+                },
+                quote! { #(#hints)* },
+            )
+        }
+        Item::Enum { .. } if datas.is_empty() => (
+            quote! { ::core::result::Result::Err(#krate::Error::EmptyChoose) },
+            quote! { (0, ::core::option::Option::Some(0)) },
+        ),
+        Item::Enum { .. } => {
+            let choose = |constructors: &[&TokenStream]| {
+                let count = constructors.len() as u64;
+                let indices = (0..count).map(Literal::u64_unsuffixed);
+
+                quote! {
+                    match (u64::from(__tag) * #count) >> 32 {
+                        #(#indices => #constructors,)*
+                        _ => ::core::unreachable!(),
+                    }
+                }
+            };
+            let all: Vec<_> = constructors.iter().collect();
+            let choose_all = choose(&all);
+
+            let body = if any_recursive {
+                let leaves: Vec<_> = constructors
+                    .iter()
+                    .zip(&recursive)
+                    .filter(|(_, recursive)| !**recursive)
+                    .map(|(constructor, _)| constructor)
+                    .collect();
+
+                if leaves.is_empty() {
+                    quote! {
+                        if __depth >= #MAX_DEPTH {
+                            return ::core::result::Result::Err(#krate::Error::NotEnoughData);
+                        }
+
+                        let __tag = <u32 as #krate::Arbitrary>::arbitrary(u)?;
+                        ::core::result::Result::Ok(#choose_all)
+                    }
+                } else {
+                    let choose_leaves = choose(&leaves);
+
+                    quote! {
+                        let __leaves_only = __depth >= #MAX_DEPTH || u.is_empty();
+                        let __tag = <u32 as #krate::Arbitrary>::arbitrary(u)?;
+
+                        ::core::result::Result::Ok(if __leaves_only {
+                            #choose_leaves
+                        } else {
+                            #choose_all
+                        })
+                    }
+                }
+            } else {
+                quote! {
+                    let __tag = <u32 as #krate::Arbitrary>::arbitrary(u)?;
+                    ::core::result::Result::Ok(#choose_all)
+                }
+            };
+
+            (
+                body,
+                quote! {
+                    #krate::size_hint::and(
+                        <u32 as #krate::Arbitrary>::try_size_hint(depth)?,
+                        #krate::size_hint::or_all(&[#(#hints),*]),
+                    )
+                },
+            )
+        }
+    };
+
+    let body = if any_recursive {
+        quote! {
+            ::std::thread_local! {
+                static __DEPTH: ::core::cell::Cell<usize> = ::core::cell::Cell::new(0);
+            }
+
+            struct __Guard;
+
+            impl ::core::ops::Drop for __Guard {
+                fn drop(&mut self) {
+                    __DEPTH.with(|depth| depth.set(depth.get() - 1));
+                }
+            }
+
+            let __depth = __DEPTH.with(|depth| {
+                let __depth = depth.get();
+                depth.set(__depth + 1);
+                __depth
+            });
+            let _guard = __Guard;
+
+            #body
+        }
+    } else {
+        body
+    };
+
+    quote! {
+        impl #impl_generics #krate::Arbitrary<#lifetime> for #self_ty
+            where #(#predicates,)* #(#bounds),*
+        {
+            fn arbitrary(
+                u: &mut #krate::Unstructured<#lifetime>,
+            ) -> #krate::Result<Self> {
+                #body
+            }
+
+            #[inline]
+            fn size_hint(depth: usize) -> (usize, ::core::option::Option<usize>) {
+                ::core::result::Result::unwrap_or_default(Self::try_size_hint(depth))
+            }
+
+            #[inline]
+            fn try_size_hint(
+                depth: usize,
+            ) -> ::core::result::Result<
+                (usize, ::core::option::Option<usize>),
+                #krate::MaxRecursionReached,
+            > {
+                #krate::size_hint::try_recursion_guard(depth, |depth| {
+                    ::core::result::Result::Ok(#hint)
+                })
+            }
+        }
+    }
+}
+
+/// Returns `true` if any field of the struct or variant isn't skipped and
+/// mentions the item.
+fn is_data_recursive(data: &Data, ident: &Ident) -> bool {
+    data.fields
+        .iter()
+        .any(|field| !field.skips(&Traits::Arbitrary) && field.is_recursive(ident))
+}
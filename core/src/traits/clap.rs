@@ -0,0 +1,57 @@
+//! [`ValueEnum`](https://docs.rs/clap) implementation of clap for enums
+//! without fields, taking the same text for each variant as the derived
+//! [`Display`](core::fmt::Display) and [`FromStr`](core::str::FromStr).
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Error, Result};
+
+use super::{display, from_str};
+use crate::{attr::Options, data::Item};
+
+/// Checks that the item is an enum without fields and that every text parses
+/// to a single variant.
+pub fn check(item: &Item) -> Result<()> {
+    match item {
+        Item::Struct(_) => Err(Error::new(
+            Span::call_site(),
+            "`ValueEnum` is only supported for enums",
+        )),
+        Item::Enum { .. } => from_str::check(item, false),
+    }
+}
+
+pub fn generate_body(item: &Item, options: &Options) -> TokenStream {
+    let krate = options.crate_path("clap");
+    let values: Vec<_> = item
+        .datas()
+        .iter()
+        .map(|data| {
+            let path = &data.path;
+            data.wrap(quote! { #path {} })
+        })
+        .collect();
+
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let patterns = item.datas().iter().map(|data| data.wildcard_pattern());
+        let texts = item.datas().iter().map(display::text);
+
+        quote! {
+            ::core::option::Option::Some(#krate::builder::PossibleValue::new(match self {
+                #(#patterns => #texts,)*
+            }))
+        }
+    };
+
+    quote! {
+        fn value_variants<'__a>() -> &'__a [Self] {
+            &[#(#values),*]
+        }
+
+        fn to_possible_value(&self) -> ::core::option::Option<#krate::builder::PossibleValue> {
+            #body
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! [`Clone`](core::clone::Clone) implementation.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+use crate::data::Item;
+
+pub fn generate_body(item: &Item) -> TokenStream {
+    let clone_from = generate_clone_from(item);
+
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let arms = item.datas().iter().map(|data| {
+            if let Some(arm) = data.uninhabited_arm(0) {
+                return arm;
+            }
+
+            let pattern = data.self_pattern();
+            let path = &data.path;
+            let members = data.fields.iter().map(|field| &field.member);
+            let clones = data.fields.iter().map(|field| {
+                let self_ident = &field.self_ident;
+
+                match &field.options.with {
+                    Some(with) => quote_spanned! {field.field.ty.span()=>
+                        #with::clone(#self_ident)
+                    },
+                    None => quote_spanned! {field.field.ty.span()=>
+                        ::core::clone::Clone::clone(#self_ident)
+                    },
+                }
+            });
+
+            let clone = data.wrap(quote! { #path { #(#members: #clones),* } });
+
+            quote! {
+                #pattern => #clone
+            }
+        });
+
+        quote! {
+            match self {
+                #(#arms,)*
+            }
+        }
+    };
+
+    quote! {
+        #[inline]
+        fn clone(&self) -> Self {
+            #body
+        }
+
+        #clone_from
+    }
+}
+
+/// Returns `true` if any field is cloned through its `with` module, which
+/// copying would bypass.
+pub fn uses_with(item: &Item) -> bool {
+    item.datas()
+        .iter()
+        .flat_map(|data| &data.fields)
+        .any(|field| field.options.with.is_some())
+}
+
+/// Copies the value, if `Copy` is derived with the same bounds.
+pub fn generate_body_copy() -> TokenStream {
+    quote! {
+        #[inline]
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+}
+
+/// Clones field by field into the existing value, reusing its allocations,
+/// unless the variants differ.
+fn generate_clone_from(item: &Item) -> TokenStream {
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let arms = item.datas().iter().map(|data| {
+            if let Some(arm) = data.uninhabited_arm(1) {
+                return arm;
+            }
+
+            let self_pattern = data.self_pattern();
+            let other_pattern = data.other_pattern();
+            let clones = data.fields.iter().map(|field| {
+                let self_ident = &field.self_ident;
+                let other_ident = &field.other_ident;
+
+                match &field.options.with {
+                    Some(with) => quote_spanned! {field.field.ty.span()=>
+                        *#self_ident = #with::clone(#other_ident);
+                    },
+                    None => quote_spanned! {field.field.ty.span()=>
+                        ::core::clone::Clone::clone_from(#self_ident, #other_ident);
+                    },
+                }
+            });
+
+            quote! {
+                (#self_pattern, #other_pattern) => { #(#clones)* }
+            }
+        });
+
+        let rest = if item.is_multi_variant() {
+            quote! {
+                (__this, __source) => *__this = ::core::clone::Clone::clone(__source),
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        quote! {
+            match (self, source) {
+                #(#arms)*
+                #rest
+            }
+        }
+    };
+
+    quote! {
+        #[inline]
+        fn clone_from(&mut self, source: &Self) {
+            #body
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! User traits, implemented by a `macro_rules!` template.
+//!
+//! `custom(path::Trait, path::template)` generates the impl with the bounds of
+//! the attribute and invokes the template inside it, passing each struct or
+//! variant as its path followed by its fields in braces:
+//!
+//! ```text
+//! template! {
+//!     Self::Variant { (member, binding, other_binding, Type) ... }
+//!     ...
+//! }
+//! ```
+//!
+//! Which a template can match with:
+//!
+//! ```text
+//! ($($path:path { $(($member:tt, $binding:ident, $other:ident, $ty:ty))* })*)
+//! ```
+//!
+//! The template generates the items of the impl, usually matching `self` with
+//! `$path { $($member: $binding),* }` patterns.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, Path, Result, Token,
+};
+
+use crate::data::Item;
+
+/// A user trait and the template generating its items.
+#[derive(Clone)]
+pub struct Custom {
+    pub path: Path,
+    pub template: Path,
+}
+
+impl Custom {
+    pub fn ident(&self) -> Ident {
+        self.path
+            .segments
+            .last()
+            .expect("paths have at least one segment")
+            .ident
+            .clone()
+    }
+}
+
+impl PartialEq for Custom {
+    fn eq(&self, other: &Self) -> bool {
+        self.path.to_token_stream().to_string() == other.path.to_token_stream().to_string()
+    }
+}
+
+impl Parse for Custom {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Ident>()?;
+
+        let content;
+        syn::parenthesized!(content in input);
+
+        let path = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let template = content.parse()?;
+        content.parse::<Option<Token![,]>>()?;
+
+        if !content.is_empty() {
+            return Err(content.error("expected `custom(Trait, template)`"));
+        }
+
+        Ok(Self { path, template })
+    }
+}
+
+pub fn generate_body(item: &Item, custom: &Custom) -> TokenStream {
+    let template = &custom.template;
+
+    let datas = item.datas().iter().map(|data| {
+        let path = &data.path;
+        let fields = data.fields.iter().map(|field| {
+            let member = &field.member;
+            let self_ident = &field.self_ident;
+            let other_ident = &field.other_ident;
+            let ty = &field.field.ty;
+
+            quote! { (#member, #self_ident, #other_ident, #ty) }
+        });
+
+        quote! { #path { #(#fields)* } }
+    });
+
+    quote! {
+        #template! {
+            #(#datas)*
+        }
+    }
+}
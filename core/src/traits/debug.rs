@@ -0,0 +1,583 @@
+//! [`Debug`](core::fmt::Debug) implementation, rendered like the std derive or
+//! by a template given with the `debug_template(...)` option. Fields can
+//! change their own rendering with the `debug(...)` field option or the `fmt`
+//! of their `with` module, or be left out with `skip(Debug)`.
+//!
+//! With `debug_type_names`, names are followed by the types the generic
+//! parameters are instantiated with, like `Wrapper<u32>`, as given by
+//! [`type_name`](core::any::type_name).
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Ident, LitInt, LitStr, Member, Result, Token,
+};
+
+use super::Traits;
+use crate::{
+    attr::Options,
+    data::{Data, FieldData, Item},
+};
+
+/// Overall rendering of values, for strict log formats:
+/// `debug_template(no_name, open = "[", close = "]", separator = "=", delimiter = "; ")`.
+///
+/// The alternate `{:#?}` form isn't supported by templates.
+#[derive(Default)]
+pub struct DebugTemplate {
+    /// Omit the name of the struct or variant.
+    pub no_name: bool,
+    /// Written before the fields, ` { ` or `(` by default.
+    pub open: Option<LitStr>,
+    /// Written after the fields, ` }` or `)` by default.
+    pub close: Option<LitStr>,
+    /// Written between field names and values, `: ` by default.
+    pub separator: Option<LitStr>,
+    /// Written between fields, `, ` by default.
+    pub delimiter: Option<LitStr>,
+}
+
+impl Parse for DebugTemplate {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut template = Self::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            let string = match ident.to_string().as_str() {
+                "no_name" if !template.no_name => {
+                    template.no_name = true;
+                    None
+                }
+                "open" => Some(&mut template.open),
+                "close" => Some(&mut template.close),
+                "separator" => Some(&mut template.separator),
+                "delimiter" => Some(&mut template.delimiter),
+                "no_name" => return Err(Error::new(ident.span(), "duplicate option")),
+                _ => return Err(Error::new(ident.span(), "unsupported option")),
+            };
+
+            if let Some(string) = string {
+                if string.is_some() {
+                    return Err(Error::new(ident.span(), "duplicate option"));
+                }
+
+                input.parse::<Token![=]>()?;
+                *string = Some(input.parse()?);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(template)
+    }
+}
+
+/// Rendering of a single field: `debug(truncate = 16)`, `debug(hex)` or
+/// `debug(max_chars = 64, escape)`.
+#[derive(Default)]
+pub struct FieldDebug {
+    /// Render at most this many elements of an array, slice or `Vec`, followed
+    /// by the total length.
+    pub truncate: Option<usize>,
+    /// Render bytes as compact hex, or as a hexdump with `{:#?}`.
+    pub hex: bool,
+    /// Render at most this many characters of a string, followed by the total
+    /// number of characters.
+    pub max_chars: Option<usize>,
+    /// Escape all but printable ASCII characters of a string, like
+    /// [`str::escape_default`], instead of only those `Debug` escapes.
+    pub escape: bool,
+}
+
+impl FieldDebug {
+    /// Returns `true` if the field is rendered as a string.
+    fn is_str(&self) -> bool {
+        self.max_chars.is_some() || self.escape
+    }
+}
+
+impl Parse for FieldDebug {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut debug = Self::default();
+
+        if input.is_empty() {
+            return Err(input.error("expected at least one option"));
+        }
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            match ident.to_string().as_str() {
+                "truncate" => {
+                    if debug.truncate.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    debug.truncate = Some(input.parse::<LitInt>()?.base10_parse()?);
+                }
+                "hex" => {
+                    if debug.hex {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    debug.hex = true;
+                }
+                "max_chars" => {
+                    if debug.max_chars.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    debug.max_chars = Some(input.parse::<LitInt>()?.base10_parse()?);
+                }
+                "escape" => {
+                    if debug.escape {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    debug.escape = true;
+                }
+                _ => return Err(Error::new(ident.span(), "unsupported option")),
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        if debug.hex && debug.truncate.is_some() {
+            return Err(Error::new(
+                input.span(),
+                "only one of `truncate` and `hex` can be given",
+            ));
+        }
+
+        if debug.is_str() && (debug.hex || debug.truncate.is_some()) {
+            return Err(Error::new(
+                input.span(),
+                "`max_chars` and `escape` can't be combined with `truncate` or `hex`",
+            ));
+        }
+
+        Ok(debug)
+    }
+}
+
+pub fn generate_body(item: &Item, options: &Options) -> TokenStream {
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let arms = item.datas().iter().map(|data| {
+            if let Some(arm) = data.uninhabited_arm(0) {
+                return arm;
+            }
+
+            let pattern = data.self_pattern_for(&Traits::Debug);
+            let discriminant = match (item, &data.discriminant) {
+                (Item::Enum { repr, .. }, Some(discriminant)) if options.debug_discriminant => {
+                    Some(quote! {{
+                        let __discriminant: #repr = #discriminant;
+                        __discriminant
+                    }})
+                }
+                _ => None,
+            };
+            let header = Header {
+                discriminant,
+                type_names: if options.debug_type_names {
+                    data.generics
+                        .type_params()
+                        .map(|param| {
+                            let ident = &param.ident;
+                            quote! { ::core::any::type_name::<#ident>() }
+                        })
+                        .collect()
+                } else {
+                    Vec::new()
+                },
+            };
+            let fields = fields(data, options.debug_sorted);
+            let body = match &options.debug_template {
+                Some(template) => format_template(data, &fields, header, template),
+                None => format_std(data, &fields, header),
+            };
+
+            quote! {
+                #pattern => { #body }
+            }
+        });
+
+        let helpers = generate_helpers(item);
+
+        quote! {
+            #helpers
+
+            match self {
+                #(#arms)*
+            }
+        }
+    };
+
+    quote! {
+        fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            #body
+        }
+    }
+}
+
+/// Local types rendering fields with a `debug(...)` option, only generated if
+/// used.
+fn generate_helpers(item: &Item) -> TokenStream {
+    let options = || {
+        item.datas()
+            .iter()
+            .flat_map(|data| &data.fields)
+            .filter_map(|field| field.options.debug.as_ref())
+    };
+    let mut helpers = TokenStream::new();
+
+    if options().any(|debug| debug.truncate.is_some()) {
+        helpers.extend(quote! {
+            struct __DebugTruncate<'a, T>(&'a [T], usize);
+
+            impl<T: ::core::fmt::Debug> ::core::fmt::Debug for __DebugTruncate<'_, T> {
+                fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let mut __builder = ::core::fmt::Formatter::debug_list(__f);
+
+                    if self.0.len() > self.1 {
+                        ::core::fmt::DebugList::entries(&mut __builder, &self.0[..self.1]);
+                        ::core::fmt::DebugList::entry(
+                            &mut __builder,
+                            &::core::format_args!("\u{2026} ({} total)", self.0.len()),
+                        );
+                    } else {
+                        ::core::fmt::DebugList::entries(&mut __builder, self.0);
+                    }
+
+                    ::core::fmt::DebugList::finish(&mut __builder)
+                }
+            }
+        });
+    }
+
+    if options().any(FieldDebug::is_str) {
+        helpers.extend(quote! {
+            struct __DebugStr<'a>(&'a str, ::core::option::Option<usize>, bool);
+
+            impl ::core::fmt::Debug for __DebugStr<'_> {
+                fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let __end = self
+                        .1
+                        .and_then(|__max| self.0.char_indices().nth(__max))
+                        .map(|(__index, _)| __index);
+                    let __shown = &self.0[..__end.unwrap_or(self.0.len())];
+
+                    if self.2 {
+                        ::core::write!(__f, "\"{}\"", __shown.escape_default())?;
+                    } else {
+                        ::core::fmt::Debug::fmt(__shown, __f)?;
+                    }
+
+                    if __end.is_some() {
+                        ::core::write!(__f, "\u{2026} ({} total)", self.0.chars().count())?;
+                    }
+
+                    ::core::result::Result::Ok(())
+                }
+            }
+        });
+    }
+
+    if options().any(|debug| debug.hex) {
+        helpers.extend(quote! {
+            struct __DebugHex<'a>(&'a [u8]);
+
+            impl ::core::fmt::Debug for __DebugHex<'_> {
+                fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    if ::core::fmt::Formatter::alternate(__f) {
+                        for (__index, __line) in self.0.chunks(16).enumerate() {
+                            if __index > 0 {
+                                ::core::fmt::Formatter::write_str(__f, "\n")?;
+                            }
+
+                            ::core::write!(__f, "{:08x}:", __index * 16)?;
+
+                            for __byte in __line {
+                                ::core::write!(__f, " {:02x}", __byte)?;
+                            }
+                        }
+                    } else {
+                        for __byte in self.0 {
+                            ::core::write!(__f, "{:02x}", __byte)?;
+                        }
+                    }
+
+                    ::core::result::Result::Ok(())
+                }
+            }
+        });
+    }
+
+    if item
+        .datas()
+        .iter()
+        .flat_map(|data| data.fields_for(&Traits::Debug))
+        .any(|field| field.options.with.is_some())
+    {
+        helpers.extend(quote! {
+            struct __DebugWith<F>(F);
+
+            impl<F> ::core::fmt::Debug for __DebugWith<F>
+            where
+                F: ::core::ops::Fn(&mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+            {
+                fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    (self.0)(__f)
+                }
+            }
+        });
+    }
+
+    helpers
+}
+
+/// Value rendered for a field, wrapped in a helper if the field has a
+/// `debug(...)` option or a `with` module.
+fn field_value(field: &FieldData) -> TokenStream {
+    let self_ident = &field.self_ident;
+
+    match field.options.debug.as_ref() {
+        Some(FieldDebug {
+            truncate: Some(truncate),
+            ..
+        }) => quote_spanned! {field.field.ty.span()=>
+            &__DebugTruncate(::core::convert::AsRef::<[_]>::as_ref(#self_ident), #truncate)
+        },
+        Some(FieldDebug { hex: true, .. }) => quote_spanned! {field.field.ty.span()=>
+            &__DebugHex(::core::convert::AsRef::<[u8]>::as_ref(#self_ident))
+        },
+        Some(debug) if debug.is_str() => {
+            let max_chars = match debug.max_chars {
+                Some(max_chars) => quote! { ::core::option::Option::Some(#max_chars) },
+                None => quote! { ::core::option::Option::None },
+            };
+            let escape = debug.escape;
+
+            quote_spanned! {field.field.ty.span()=>
+                &__DebugStr(::core::convert::AsRef::<str>::as_ref(#self_ident), #max_chars, #escape)
+            }
+        }
+        _ => match &field.options.with {
+            Some(with) => quote_spanned! {field.field.ty.span()=>
+                &__DebugWith(|__f: &mut ::core::fmt::Formatter<'_>| #with::fmt(#self_ident, __f))
+            },
+            None => quote! { #self_ident },
+        },
+    }
+}
+
+/// Fields rendered, in declaration order or sorted by name. Tuple fields are
+/// always in order.
+fn fields<'a>(data: &'a Data, sorted: bool) -> Vec<&'a FieldData<'a>> {
+    let mut fields = data.fields_for(&Traits::Debug);
+
+    if sorted && is_named(data) {
+        fields.sort_by_key(|field| field.member_name());
+    }
+
+    fields
+}
+
+fn is_named(data: &Data) -> bool {
+    matches!(
+        data.fields.first().map(|field| &field.member),
+        Some(Member::Named(_))
+    )
+}
+
+/// What is written along with the name.
+struct Header {
+    /// Value of the discriminant, written after the name.
+    discriminant: Option<TokenStream>,
+    /// Names of the types of the generic parameters, written right after the
+    /// name if there is one.
+    type_names: Vec<TokenStream>,
+}
+
+impl Header {
+    /// Returns `true` if the name can't be written by the builders alone.
+    fn is_extended(&self, name: &str) -> bool {
+        self.discriminant.is_some() || (!name.is_empty() && !self.type_names.is_empty())
+    }
+}
+
+/// Writes the name, followed by the type names and the discriminant if given.
+fn write_name(name: &str, header: &Header) -> TokenStream {
+    if !header.is_extended(name) {
+        return quote! { ::core::fmt::Formatter::write_str(__f, #name) };
+    }
+
+    let mut format = String::new();
+    let mut args = Vec::new();
+
+    if !name.is_empty() {
+        format.push_str("{}");
+        args.push(quote! { #name });
+
+        if !header.type_names.is_empty() {
+            format.push('<');
+            format.push_str(&vec!["{}"; header.type_names.len()].join(", "));
+            format.push('>');
+            args.extend(header.type_names.iter().cloned());
+        }
+    }
+
+    if let Some(discriminant) = &header.discriminant {
+        if !format.is_empty() {
+            format.push(' ');
+        }
+
+        format.push_str("(= {})");
+        args.push(discriminant.clone());
+    }
+
+    quote! { ::core::write!(__f, #format, #(#args),*) }
+}
+
+/// Renders like the std derive, through the builders of the formatter.
+///
+/// With a discriminant or type names, the name is written up front and the
+/// builders get an empty one.
+fn format_std(data: &Data, fields: &[&FieldData], header: Header) -> TokenStream {
+    let name = data.name.unraw().to_string();
+    let write_name = write_name(&name, &header);
+
+    if data.fields.is_empty() {
+        return write_name;
+    }
+
+    let (prefix, name) = if header.is_extended(&name) {
+        (quote! { #write_name?; }, String::new())
+    } else {
+        (TokenStream::new(), name)
+    };
+
+    if is_named(data) {
+        let fields = fields.iter().map(|field| {
+            let value = field_value(field);
+            let field_name = field.member_name();
+
+            quote_spanned! {field.field.ty.span()=>
+                ::core::fmt::DebugStruct::field(&mut __builder, #field_name, #value);
+            }
+        });
+
+        quote! {
+            #prefix
+            let mut __builder = ::core::fmt::Formatter::debug_struct(__f, #name);
+            #(#fields)*
+            ::core::fmt::DebugStruct::finish(&mut __builder)
+        }
+    } else {
+        let entries = fields.iter().map(|field| {
+            let value = field_value(field);
+
+            quote_spanned! {field.field.ty.span()=>
+                ::core::fmt::DebugTuple::field(&mut __builder, #value);
+            }
+        });
+
+        let builder = quote! {
+            let mut __builder = ::core::fmt::Formatter::debug_tuple(__f, #name);
+            #(#entries)*
+            ::core::fmt::DebugTuple::finish(&mut __builder)
+        };
+
+        match fields {
+            // Unnamed builders render a single field like a 1-tuple, `(value,)`.
+            [field] if !prefix.is_empty() => {
+                let value = field_value(field);
+
+                quote! {
+                    #prefix
+
+                    if ::core::fmt::Formatter::alternate(__f) {
+                        #builder
+                    } else {
+                        ::core::fmt::Formatter::write_str(__f, "(")?;
+                        ::core::fmt::Debug::fmt(#value, __f)?;
+                        ::core::fmt::Formatter::write_str(__f, ")")
+                    }
+                }
+            }
+            _ => quote! {
+                #prefix
+                #builder
+            },
+        }
+    }
+}
+
+/// Renders by writing the pieces of the template around the fields.
+fn format_template(
+    data: &Data,
+    fields: &[&FieldData],
+    header: Header,
+    template: &DebugTemplate,
+) -> TokenStream {
+    let string = |value: &Option<LitStr>, default: &str| match value {
+        Some(value) => value.value(),
+        None => default.to_owned(),
+    };
+
+    let named = is_named(data);
+    let name = if template.no_name {
+        String::new()
+    } else {
+        data.name.unraw().to_string()
+    };
+
+    let write_name = write_name(&name, &header);
+
+    if data.fields.is_empty() {
+        return write_name;
+    }
+
+    let open = string(&template.open, if named { " { " } else { "(" });
+    let close = string(&template.close, if named { " }" } else { ")" });
+    let separator = string(&template.separator, ": ");
+    let delimiter = string(&template.delimiter, ", ");
+
+    let fields = fields.iter().enumerate().map(|(index, field)| {
+        let value = field_value(field);
+        let delimiter = if index > 0 { delimiter.as_str() } else { "" };
+        let prefix = if named {
+            format!("{}{}{}", delimiter, field.member_name(), separator)
+        } else {
+            delimiter.to_owned()
+        };
+
+        quote_spanned! {field.field.ty.span()=>
+            ::core::fmt::Formatter::write_str(__f, #prefix)?;
+            ::core::fmt::Debug::fmt(#value, __f)?;
+        }
+    });
+
+    quote! {
+        #write_name?;
+        ::core::fmt::Formatter::write_str(__f, #open)?;
+        #(#fields)*
+        ::core::fmt::Formatter::write_str(__f, #close)
+    }
+}
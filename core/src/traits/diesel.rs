@@ -0,0 +1,146 @@
+//! [`ToSql`](https://docs.rs/diesel), `FromSql` and `AsExpression`
+//! implementations of diesel for wrappers, forwarding to the wrapped field for
+//! any SQL type `__ST` and backend `__DB` it supports.
+//!
+//! Like the sqlx traits, the impls have generic parameters of their own, so
+//! they're generated whole. `FromSql` sets all other fields to their default,
+//! and `AsExpression` is implemented for references to the wrapper too, which
+//! diesel's `Insertable` relies on.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, GenericParam, Generics, Lifetime, WherePredicate};
+
+use crate::{attr::Options, data::Item, traits::Traits};
+
+pub fn generate_impl(
+    item: &Item,
+    options: &Options,
+    trait_: &Traits,
+    self_ty: &TokenStream,
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+) -> TokenStream {
+    let krate = options.crate_path("diesel");
+    let field = item.wrapped_field(trait_).expect("checked before");
+    let ty = &field.field.ty;
+    let member = &field.member;
+
+    let mut generics = generics.clone();
+    generics.params.push(parse_quote!(__ST));
+
+    match trait_ {
+        Traits::DieselAsExpression => {
+            let lifetime = Lifetime::new("'__diesel", Span::call_site());
+            let bounds = quote! {
+                __ST: #krate::sql_types::SqlType + #krate::expression::TypedExpressionType
+            };
+            let mut ref_generics = generics.clone();
+            ref_generics
+                .params
+                .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+            let (impl_generics, ..) = generics.split_for_impl();
+            let (ref_impl_generics, ..) = ref_generics.split_for_impl();
+
+            quote! {
+                impl #impl_generics #krate::expression::AsExpression<__ST> for #self_ty
+                    where #(#predicates,)* #bounds, #ty: #krate::expression::AsExpression<__ST>
+                {
+                    type Expression = <#ty as #krate::expression::AsExpression<__ST>>::Expression;
+
+                    #[inline]
+                    fn as_expression(self) -> Self::Expression {
+                        #krate::expression::AsExpression::as_expression(self.#member)
+                    }
+                }
+
+                impl #ref_impl_generics #krate::expression::AsExpression<__ST> for &#lifetime #self_ty
+                    where
+                        #(#predicates,)*
+                        #bounds,
+                        &#lifetime #ty: #krate::expression::AsExpression<__ST>
+                {
+                    type Expression =
+                        <&#lifetime #ty as #krate::expression::AsExpression<__ST>>::Expression;
+
+                    #[inline]
+                    fn as_expression(self) -> Self::Expression {
+                        #krate::expression::AsExpression::as_expression(&self.#member)
+                    }
+                }
+            }
+        }
+        Traits::DieselFromSql => {
+            generics
+                .params
+                .push(parse_quote!(__DB: #krate::backend::Backend));
+            let (impl_generics, ..) = generics.split_for_impl();
+
+            let data = match item {
+                Item::Struct(data) => data,
+                Item::Enum { .. } => unreachable!("checked before"),
+            };
+            let mut bounds = vec![quote! { #ty: #krate::deserialize::FromSql<__ST, __DB> }];
+            let mut fields = Vec::new();
+
+            for other in &data.fields {
+                let other_member = &other.member;
+
+                if other_member == member {
+                    fields.push(quote! { #member: __field });
+                } else {
+                    let ty = &other.field.ty;
+                    bounds.push(quote! { #ty: ::core::default::Default });
+                    fields.push(quote! { #other_member: ::core::default::Default::default() });
+                }
+            }
+
+            let path = &data.path;
+
+            quote! {
+                impl #impl_generics #krate::deserialize::FromSql<__ST, __DB> for #self_ty
+                    where #(#predicates,)* #(#bounds),*
+                {
+                    #[inline]
+                    fn from_sql(
+                        bytes: <__DB as #krate::backend::Backend>::RawValue<'_>,
+                    ) -> #krate::deserialize::Result<Self> {
+                        let __field =
+                            <#ty as #krate::deserialize::FromSql<__ST, __DB>>::from_sql(bytes)?;
+                        ::core::result::Result::Ok(#path { #(#fields,)* })
+                    }
+
+                    #[inline]
+                    fn from_nullable_sql(
+                        bytes: ::core::option::Option<<__DB as #krate::backend::Backend>::RawValue<'_>>,
+                    ) -> #krate::deserialize::Result<Self> {
+                        let __field =
+                            <#ty as #krate::deserialize::FromSql<__ST, __DB>>::from_nullable_sql(bytes)?;
+                        ::core::result::Result::Ok(#path { #(#fields,)* })
+                    }
+                }
+            }
+        }
+        Traits::DieselToSql => {
+            generics
+                .params
+                .push(parse_quote!(__DB: #krate::backend::Backend));
+            let (impl_generics, ..) = generics.split_for_impl();
+
+            quote! {
+                impl #impl_generics #krate::serialize::ToSql<__ST, __DB> for #self_ty
+                    where #(#predicates,)* #ty: #krate::serialize::ToSql<__ST, __DB>
+                {
+                    #[inline]
+                    fn to_sql<'__b>(
+                        &'__b self,
+                        out: &mut #krate::serialize::Output<'__b, '_, __DB>,
+                    ) -> #krate::serialize::Result {
+                        <#ty as #krate::serialize::ToSql<__ST, __DB>>::to_sql(&self.#member, out)
+                    }
+                }
+            }
+        }
+        _ => unreachable!("unexpected trait"),
+    }
+}
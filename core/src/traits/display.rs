@@ -0,0 +1,48 @@
+//! [`Display`](core::fmt::Display) implementation for items without fields,
+//! writing the name of the variant or the text given by its `display` option.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, Error, Result};
+
+use crate::data::{Data, Item};
+
+/// Checks that no struct or variant has fields.
+pub fn check(item: &Item) -> Result<()> {
+    match item.datas().iter().find(|data| !data.fields.is_empty()) {
+        Some(data) => Err(Error::new(
+            data.name.span(),
+            "`Display` requires variants without fields",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Text written for the struct or variant.
+pub fn text(data: &Data) -> String {
+    match &data.options.display {
+        Some(display) => display.value(),
+        None => data.name.unraw().to_string(),
+    }
+}
+
+pub fn generate_body(item: &Item) -> TokenStream {
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let patterns = item.datas().iter().map(Data::wildcard_pattern);
+        let texts = item.datas().iter().map(text);
+
+        quote! {
+            ::core::fmt::Formatter::pad(__f, match self {
+                #(#patterns => #texts,)*
+            })
+        }
+    };
+
+    quote! {
+        fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+            #body
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! [`Eq`](core::cmp::Eq) implementation.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+use crate::{data::Item, traits::Traits};
+
+/// Asserts that all fields implement `Eq`, like the standard derive does,
+/// except those of uninhabited structs and variants, which are never compared.
+///
+/// This can't be done in the `Eq` impl itself, so it's a method of a separate
+/// inherent impl with the same bounds.
+pub fn generate_assertions(item: &Item) -> Option<TokenStream> {
+    let types: Vec<_> = item
+        .datas()
+        .iter()
+        .filter(|data| !data.is_uninhabited())
+        .flat_map(|data| data.fields_for(&Traits::Eq))
+        .map(|field| &field.field.ty)
+        .collect();
+
+    if types.is_empty() {
+        return None;
+    }
+
+    let assertions = types.iter().map(|ty| {
+        quote_spanned! {ty.span()=>
+            let _: __AssertEq<#ty>;
+        }
+    });
+
+    Some(quote! {
+        #[allow(dead_code)]
+        #[doc(hidden)]
+        fn __derive_where_assert_eq(&self) {
+            struct __AssertEq<__T: ::core::cmp::Eq + ?::core::marker::Sized>(
+                ::core::marker::PhantomData<__T>,
+            );
+
+            #(#assertions)*
+        }
+    })
+}
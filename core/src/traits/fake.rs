@@ -0,0 +1,92 @@
+//! [`Dummy`](https://docs.rs/fake) implementation of fake, with the default
+//! [`Faker`](https://docs.rs/fake) as its config and variants chosen uniformly.
+//!
+//! The impl is generic over the RNG, so it's generated whole, with bounds on
+//! the types of the fields: `Dummy<Faker>` or `Default` if they're skipped.
+//! Types mentioning the item itself, like `Option<Box<Self>>`, get no bounds,
+//! which would need the impl itself.
+//! `PhantomData` is built directly, fake's own impl would require its
+//! parameter to be `Dummy` too.
+
+use proc_macro2::{Literal, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Generics, Ident, WherePredicate};
+
+use crate::{attr::Options, data::Item, traits::Traits};
+
+pub fn generate_impl(
+    item: &Item,
+    options: &Options,
+    ident: &Ident,
+    self_ty: &TokenStream,
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+) -> TokenStream {
+    let krate = options.crate_path("fake");
+    let (impl_generics, ..) = generics.split_for_impl();
+
+    let mut bounds = Vec::new();
+    // Uninhabited structs and variants can't be generated.
+    let constructors: Vec<_> = item
+        .datas()
+        .iter()
+        .filter(|data| !data.is_uninhabited())
+        .map(|data| {
+            let fields = data.fields.iter().map(|field| {
+                let member = &field.member;
+                let ty = &field.field.ty;
+
+                if field.is_phantom_data() {
+                    quote! { #member: ::core::marker::PhantomData }
+                } else if field.skips(&Traits::Dummy) {
+                    bounds.push(quote! { #ty: ::core::default::Default });
+                    quote! { #member: ::core::default::Default::default() }
+                } else {
+                    if !field.is_recursive(ident) {
+                        bounds.push(quote! { #ty: #krate::Dummy<#krate::Faker> });
+                    }
+
+                    quote_spanned! {ty.span()=>
+                        #member: #krate::Dummy::dummy_with_rng(config, rng)
+                    }
+                }
+            });
+            let fields: Vec<_> = fields.collect();
+            let path = &data.path;
+
+            data.wrap(quote! { #path { #(#fields,)* } })
+        })
+        .collect();
+
+    let body = match (item, constructors.as_slice()) {
+        (Item::Struct(_), [constructor]) => quote! { #constructor },
+        (_, []) => quote! {
+            ::core::panic!("can't generate a dummy of an uninhabited type")
+        },
+        (_, constructors) => {
+            let count = constructors.len();
+            let indices = (0..count).map(Literal::usize_unsuffixed);
+
+            quote! {
+                match #krate::Rng::random_range(rng, 0..#count) {
+                    #(#indices => #constructors,)*
+                    _ => ::core::unreachable!(),
+                }
+            }
+        }
+    };
+
+    quote! {
+        impl #impl_generics #krate::Dummy<#krate::Faker> for #self_ty
+            where #(#predicates,)* #(#bounds),*
+        {
+            #[allow(unused_variables)]
+            fn dummy_with_rng<__R: #krate::Rng + ?::core::marker::Sized>(
+                config: &#krate::Faker,
+                rng: &mut __R,
+            ) -> Self {
+                #body
+            }
+        }
+    }
+}
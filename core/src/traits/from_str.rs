@@ -0,0 +1,139 @@
+//! [`FromStr`](core::str::FromStr) implementation for items without fields,
+//! parsing exactly the text written by the derived
+//! [`Display`](core::fmt::Display), optionally ignoring ASCII case.
+//!
+//! Other text is rejected with an error type generated next to the item, like
+//! `ParseTestError` for `Test`, with the visibility of the item. It carries the
+//! rejected text and implements `std::error::Error`, so it requires `std`.
+
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Error, Generics, Ident, Result, Visibility, WherePredicate};
+
+use super::display;
+use crate::data::Item;
+
+/// Checks that no struct or variant has fields and that every text parses to a
+/// single variant.
+pub fn check(item: &Item, case_insensitive: bool) -> Result<()> {
+    display::check(item)?;
+
+    let mut texts = HashMap::new();
+
+    for data in item.datas() {
+        let mut text = display::text(data);
+
+        if case_insensitive {
+            text.make_ascii_lowercase();
+        }
+
+        if let Some(previous) = texts.insert(text, data.name) {
+            return Err(Error::new(
+                data.name.span(),
+                format!("`FromStr` can't distinguish this from `{}`", previous),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Name of the error type of `ident`, like `ParseIntError` of `std`.
+fn error_ident(ident: &Ident) -> Ident {
+    format_ident!("Parse{}Error", ident)
+}
+
+pub fn generate_impl(
+    item: &Item,
+    case_insensitive: bool,
+    ident: &Ident,
+    self_ty: &TokenStream,
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+) -> TokenStream {
+    let (impl_generics, ..) = generics.split_for_impl();
+    let error = error_ident(ident);
+    let err = quote! {
+        ::core::result::Result::Err(#error {
+            input: ::std::string::String::from(__s),
+        })
+    };
+    let values = item.datas().iter().map(|data| {
+        let path = &data.path;
+        data.wrap(quote! { #path {} })
+    });
+
+    let body = if case_insensitive {
+        let texts = item.datas().iter().map(display::text);
+
+        quote! {
+            #(if __s.eq_ignore_ascii_case(#texts) {
+                ::core::result::Result::Ok(#values)
+            } else)* {
+                #err
+            }
+        }
+    } else {
+        let texts = item.datas().iter().map(display::text);
+
+        quote! {
+            match __s {
+                #(#texts => ::core::result::Result::Ok(#values),)*
+                _ => #err,
+            }
+        }
+    };
+
+    quote! {
+        impl #impl_generics ::core::str::FromStr for #self_ty
+        where
+            #(#predicates),*
+        {
+            type Err = #error;
+
+            fn from_str(__s: &str) -> ::core::result::Result<Self, #error> {
+                #body
+            }
+        }
+    }
+}
+
+/// Generates the error type of `ident`, visible like the item.
+pub fn generate_error(ident: &Ident, vis: &Visibility) -> TokenStream {
+    let error = error_ident(ident);
+    let doc = format!(
+        " Error parsing [`{}`] from text that isn't written by any of its values.",
+        ident
+    );
+    let message = format!("invalid `{}`: {{:?}}", ident);
+
+    quote! {
+        #[doc = #doc]
+        #[derive(
+            ::core::clone::Clone,
+            ::core::fmt::Debug,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+        )]
+        #vis struct #error {
+            input: ::std::string::String,
+        }
+
+        impl #error {
+            /// Text that was rejected.
+            #vis fn input(&self) -> &str {
+                &self.input
+            }
+        }
+
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(__f, #message, self.input)
+            }
+        }
+
+        impl ::std::error::Error for #error {}
+    }
+}
@@ -0,0 +1,61 @@
+//! [`Future`](core::future::Future) implementation for wrappers, polling the
+//! wrapped field through a pin projection.
+//!
+//! The projection is sound as long as the field is structurally pinned: the
+//! wrapper may only be `Unpin` if the field is, which the generated `Unpin`
+//! impl enforces, and it must not implement `Drop`, which could move the field
+//! out of a pinned wrapper.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::{data::Item, traits::Traits};
+
+pub fn generate_body(item: &Item) -> TokenStream {
+    let field = item.wrapped_field(&Traits::Future).expect("checked before");
+    let ty = &field.field.ty;
+    let member = &field.member;
+
+    quote! {
+        type Output = <#ty as ::core::future::Future>::Output;
+
+        #[allow(unsafe_code)]
+        fn poll(
+            self: ::core::pin::Pin<&mut Self>,
+            __cx: &mut ::core::task::Context<'_>,
+        ) -> ::core::task::Poll<Self::Output> {
+            // SAFETY: the field is structurally pinned, see the `Unpin` impl and
+            // the `Drop` guard next to this impl.
+            let __field = unsafe {
+                ::core::pin::Pin::map_unchecked_mut(self, |__this| &mut __this.#member)
+            };
+            ::core::future::Future::poll(__field, __cx)
+        }
+    }
+}
+
+/// Makes the wrapper only `Unpin` if the field is, and fails to compile if it
+/// implements `Drop`.
+pub fn generate_items(item: &Item, input: &DeriveInput) -> TokenStream {
+    let field = item.wrapped_field(&Traits::Future).expect("checked before");
+    let ty = &field.field.ty;
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause
+        .into_iter()
+        .flat_map(|where_clause| &where_clause.predicates);
+
+    quote! {
+        impl #impl_generics ::core::marker::Unpin for #ident #type_generics
+            where #(#predicates,)* #ty: ::core::marker::Unpin
+        {}
+
+        trait __MustNotImplDrop {}
+
+        #[allow(drop_bounds)]
+        impl<__T: ::core::ops::Drop> __MustNotImplDrop for __T {}
+
+        impl #impl_generics __MustNotImplDrop for #ident #type_generics #where_clause {}
+    }
+}
@@ -0,0 +1,421 @@
+//! [`Hash`](core::hash::Hash) implementation.
+//!
+//! Enums with an explicit `#[repr(...)]` hash their discriminant as that
+//! type, others hash [`mem::discriminant`](core::mem::discriminant), whose
+//! hash depends on the compiler and platform.
+//!
+//! The portable mode hashes the discriminant of such enums as little-endian
+//! bytes, unless it's `isize` or `usize`, and the index of the variant as
+//! little-endian `u32` otherwise. Integer fields are written as little-endian
+//! bytes too, `isize` and `usize` as 64 bits, and strings and slices are
+//! prefixed by their length as a little-endian `u64`. Other fields are hashed
+//! by their own implementations, so they have to be portable themselves;
+//! collections known to hash their length as `usize` and `unordered` fields
+//! are rejected, as are `key` and `via`, whose type isn't known.
+//!
+//! Float fields marked `to_bits` are hashed by their bits instead, with `-0.0`
+//! hashed like `0.0` as they are equal.
+//!
+//! `unordered` fields sum up the hashes of their elements, each by `std`'s
+//! `DefaultHasher`, so they require `std`. `case_insensitive` fields hash
+//! their ASCII lowercase bytes followed by `0xff`, like `str` does, so values
+//! equal ignoring case hash the same.
+//!
+//! Fields of primitive integer types, recognized by name, are written to the
+//! hasher directly by its `write_*` method, as their `Hash` impls do, which
+//! saves going through `Hash` for key-like structs.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    spanned::Spanned,
+    Error, GenericArgument, Ident, Index, PathArguments, Result, Token, Type,
+};
+
+use crate::{
+    attr::Options,
+    data::{FieldData, Item},
+    traits::Traits,
+};
+
+/// Hashing of an `f32` or `f64` field by its bits: `to_bits` or
+/// `to_bits(canonical_nan)`.
+#[derive(Default)]
+pub struct FieldToBits {
+    /// Hash all NaNs alike, regardless of their sign and payload.
+    pub canonical_nan: bool,
+}
+
+impl Parse for FieldToBits {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut to_bits = Self::default();
+
+        if input.is_empty() {
+            return Err(input.error("expected at least one option"));
+        }
+
+        let ident: Ident = input.parse()?;
+
+        match ident.to_string().as_str() {
+            "canonical_nan" => to_bits.canonical_nan = true,
+            _ => return Err(Error::new(ident.span(), "unsupported option")),
+        }
+
+        input.parse::<Option<Token![,]>>()?;
+
+        if !input.is_empty() {
+            return Err(input.error("expected a single option"));
+        }
+
+        Ok(to_bits)
+    }
+}
+
+/// Hashes the tag of the `hash_tag` option, avoiding collisions with other
+/// types in the same table.
+pub fn generate_tag(options: &Options) -> TokenStream {
+    match options.hash_tag.as_ref().and_then(Option::as_ref) {
+        Some(tag) => quote! { ::core::hash::Hash::hash(#tag, __state); },
+        None => TokenStream::new(),
+    }
+}
+
+pub fn generate_body(
+    item: &Item,
+    options: &Options,
+    portable: bool,
+    tag: &TokenStream,
+) -> TokenStream {
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        // Enums with an explicit `#[repr(...)]` hash the value of their
+        // discriminant in each arm.
+        let repr = match item {
+            Item::Enum {
+                repr,
+                explicit_repr: true,
+                ..
+            } if item.is_multi_variant() && !(portable && is_pointer_sized(repr)) => Some(repr),
+            _ => None,
+        };
+
+        let discriminant = if portable || repr.is_some() {
+            TokenStream::new()
+        } else if item.is_multi_variant() {
+            let value = if item.is_remote() {
+                quote! { &self.0 }
+            } else {
+                quote! { self }
+            };
+
+            quote! {
+                ::core::hash::Hash::hash(&::core::mem::discriminant(#value), __state);
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        let arms = item.datas().iter().enumerate().map(|(index, data)| {
+            if let Some(arm) = data.uninhabited_arm(0) {
+                return arm;
+            }
+
+            let index = if let (Some(repr), Some(discriminant)) = (repr, &data.discriminant) {
+                if portable {
+                    quote! {
+                        ::core::hash::Hasher::write(__state, &<#repr>::to_le_bytes(#discriminant));
+                    }
+                } else {
+                    quote! {
+                        <#repr as ::core::hash::Hash>::hash(&(#discriminant), __state);
+                    }
+                }
+            } else if portable && item.is_multi_variant() {
+                let index = index as u32;
+
+                quote! {
+                    ::core::hash::Hasher::write(__state, &#index.to_le_bytes());
+                }
+            } else {
+                TokenStream::new()
+            };
+            // Values of an incomparable variant are all equal with a fallback
+            // for `Ord`, so their fields can't be hashed.
+            let (pattern, fields) =
+                if data.options.incomparable && options.incomparable_ord.is_some() {
+                    (data.wildcard_pattern(), Vec::new())
+                } else {
+                    (
+                        data.self_pattern_for(&Traits::Hash),
+                        data.fields_for(&Traits::Hash),
+                    )
+                };
+            let hashes = fields.into_iter().map(|field| hash(field, portable));
+
+            quote! {
+                #pattern => {
+                    #index
+                    #(#hashes)*
+                }
+            }
+        });
+
+        quote! {
+            #tag
+            #discriminant
+
+            match self {
+                #(#arms)*
+            }
+        }
+    };
+
+    quote! {
+        fn hash<__H: ::core::hash::Hasher>(&self, __state: &mut __H) {
+            #body
+        }
+    }
+}
+
+/// Returns `true` for `isize` and `usize`, whose bytes differ in length
+/// between platforms.
+fn is_pointer_sized(repr: &Ident) -> bool {
+    repr == "isize" || repr == "usize"
+}
+
+/// Checks that all fields can be hashed portably.
+pub fn check_portable(item: &Item) -> Result<()> {
+    for field in item
+        .datas()
+        .iter()
+        .flat_map(|data| data.fields_for(&Traits::Hash))
+    {
+        if field.options.unordered {
+            return Err(Error::new(
+                field.field.ty.span(),
+                "`unordered` fields can't be hashed portably",
+            ));
+        } else if field.options.to_bits.is_none()
+            && field.options.with.is_none()
+            && !field.options.case_insensitive
+        {
+            hash_portable(&field.self_ident.to_token_stream(), &field.field.ty)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Hashes a field, by its bits if it's marked `to_bits`, regardless of the
+/// order of its elements if `unordered`, ignoring ASCII case if
+/// `case_insensitive` or through its `with` module if given.
+fn hash(field: &FieldData, portable: bool) -> TokenStream {
+    let self_ident = &field.self_ident;
+    let ty = &field.field.ty;
+
+    if field.options.unordered {
+        // Elements are hashed on their own and summed up, which doesn't depend
+        // on their order. `DefaultHasher::new` always hashes the same way.
+        return quote_spanned! {ty.span()=>
+            {
+                let mut __sum: u64 = 0;
+
+                for __element in #self_ident.iter() {
+                    let mut __hasher = ::std::collections::hash_map::DefaultHasher::new();
+                    ::core::hash::Hash::hash(__element, &mut __hasher);
+                    __sum = __sum.wrapping_add(::core::hash::Hasher::finish(&__hasher));
+                }
+
+                ::core::hash::Hash::hash(&#self_ident.len(), __state);
+                ::core::hash::Hash::hash(&__sum, __state);
+            }
+        };
+    }
+
+    if field.options.case_insensitive {
+        return quote_spanned! {ty.span()=>
+            for __byte in ::core::convert::AsRef::<str>::as_ref(#self_ident).bytes() {
+                ::core::hash::Hasher::write_u8(__state, __byte.to_ascii_lowercase());
+            }
+
+            ::core::hash::Hasher::write_u8(__state, 0xff);
+        };
+    }
+
+    match &field.options.to_bits {
+        Some(to_bits) => {
+            let canonical_nan = if to_bits.canonical_nan {
+                quote! {
+                    let __value = if __value.is_nan() { <#ty>::NAN } else { __value };
+                }
+            } else {
+                TokenStream::new()
+            };
+
+            let write_bits = if portable {
+                quote! {
+                    ::core::hash::Hasher::write(__state, &__value.to_bits().to_le_bytes());
+                }
+            } else {
+                quote! {
+                    ::core::hash::Hash::hash(&__value.to_bits(), __state);
+                }
+            };
+
+            quote_spanned! {ty.span()=>
+                {
+                    let __value: #ty = *#self_ident;
+                    let __value = if __value == 0.0 { 0.0 } else { __value };
+                    #canonical_nan
+                    #write_bits
+                }
+            }
+        }
+        None => match &field.options.with {
+            Some(with) => quote_spanned! {ty.span()=>
+                #with::hash(#self_ident, __state);
+            },
+            None if portable => {
+                hash_portable(&self_ident.to_token_stream(), ty).expect("checked before")
+            }
+            None => match integer(ty) {
+                Some(integer) => {
+                    let write = format_ident!("write_{}", integer);
+
+                    quote_spanned! {ty.span()=>
+                        ::core::hash::Hasher::#write(__state, *#self_ident);
+                    }
+                }
+                None => quote_spanned! {ty.span()=>
+                    ::core::hash::Hash::hash(#self_ident, __state);
+                },
+            },
+        },
+    }
+}
+
+/// Primitive integer types, which are hashed by the `Hasher` method of the
+/// same name.
+const INTEGERS: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Returns the name of the primitive integer type `ty`, if it's one.
+fn integer(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => ty
+            .path
+            .get_ident()
+            .filter(|ident| INTEGERS.iter().any(|integer| ident == integer)),
+        Type::Group(ty) => integer(&ty.elem),
+        Type::Paren(ty) => integer(&ty.elem),
+        _ => None,
+    }
+}
+
+/// Collections whose `Hash` impls write their length as `usize`.
+const NON_PORTABLE: &[&str] = &["BTreeMap", "BTreeSet", "LinkedList", "VecDeque"];
+
+/// Hashes `value`, a reference to a `ty`, the same way on every platform.
+/// Types not known to `std` are hashed by their own implementation.
+fn hash_portable(value: &TokenStream, ty: &Type) -> Result<TokenStream> {
+    let write = |bytes: TokenStream| {
+        quote! {
+            ::core::hash::Hasher::write(__state, &#bytes);
+        }
+    };
+    let length = |len: TokenStream| write(quote! { (#len as u64).to_le_bytes() });
+    let elements = |elem: &Type| -> Result<TokenStream> {
+        let element = hash_portable(&quote! { __element }, elem)?;
+
+        Ok(quote! {
+            for __element in <[#elem]>::iter(#value) {
+                #element
+            }
+        })
+    };
+
+    match ty {
+        Type::Group(ty) => hash_portable(value, &ty.elem),
+        Type::Paren(ty) => hash_portable(value, &ty.elem),
+        Type::Reference(ty) => hash_portable(&quote! { *#value }, &ty.elem),
+        // Arrays have a fixed length, which isn't hashed.
+        Type::Array(ty) => elements(&ty.elem),
+        Type::Slice(ty) => {
+            let elem = &ty.elem;
+            let length = length(quote! { <[#elem]>::len(#value) });
+            let elements = elements(elem)?;
+
+            Ok(quote! { #length #elements })
+        }
+        Type::Tuple(ty) => ty
+            .elems
+            .iter()
+            .enumerate()
+            .map(|(index, elem)| {
+                let index = Index::from(index);
+                hash_portable(&quote! { &(#value).#index }, elem)
+            })
+            .collect(),
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last().expect("empty path");
+            let argument = match &segment.arguments {
+                PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+                    match &arguments.args[0] {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            match (segment.ident.to_string().as_str(), argument) {
+                ("isize", None) => Ok(write(quote! { (*#value as i64).to_le_bytes() })),
+                ("usize", None) => Ok(write(quote! { (*#value as u64).to_le_bytes() })),
+                (_, None) if integer(ty).is_some() => {
+                    Ok(write(quote! { <#ty>::to_le_bytes(*#value) }))
+                }
+                ("bool", None) => Ok(write(quote! { [*#value as u8] })),
+                ("char", None) => Ok(write(quote! { (*#value as u32).to_le_bytes() })),
+                ("str", None) | ("String", None) => {
+                    let length = length(quote! { <str>::len(#value) });
+                    let bytes = write(quote! { <str>::as_bytes(#value) });
+
+                    Ok(quote! { #length #bytes })
+                }
+                ("Vec", Some(elem)) => hash_portable(value, &parse_quote!([#elem])),
+                ("Box", Some(elem)) | ("Rc", Some(elem)) | ("Arc", Some(elem)) => {
+                    hash_portable(&quote! { &**#value }, elem)
+                }
+                ("Option", Some(elem)) => {
+                    let (none, some) = (write(quote! { [0] }), write(quote! { [1] }));
+                    let inner = hash_portable(&quote! { __value }, elem)?;
+
+                    Ok(quote! {
+                        match #value {
+                            ::core::option::Option::Some(__value) => {
+                                #some
+                                #inner
+                            }
+                            ::core::option::Option::None => {
+                                #none
+                            }
+                        }
+                    })
+                }
+                (ident, _) if NON_PORTABLE.contains(&ident) => Err(Error::new(
+                    ty.span(),
+                    format!(
+                        "`{}` hashes its length as `usize`, which isn't portable, use `with` or skip the field",
+                        ident
+                    ),
+                )),
+                _ => Ok(quote! { ::core::hash::Hash::hash(#value, __state); }),
+            }
+        }
+        _ => Ok(quote! { ::core::hash::Hash::hash(#value, __state); }),
+    }
+}
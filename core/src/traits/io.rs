@@ -0,0 +1,97 @@
+//! [`Read`](std::io::Read), [`Write`](std::io::Write) and
+//! [`Seek`](std::io::Seek) implementations for wrappers, forwarding to the
+//! wrapped field.
+//!
+//! Methods with default implementations are forwarded too where the wrapped
+//! type may have a faster one, if the `msrv` option allows them.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{attr::Options, data::Item, traits::Traits};
+
+pub fn generate_body(item: &Item, trait_: &Traits, options: &Options) -> TokenStream {
+    let field = item.wrapped_field(trait_).expect("checked before");
+    let member = &field.member;
+
+    match trait_ {
+        Traits::Read => quote! {
+            #[inline]
+            fn read(&mut self, __buf: &mut [u8]) -> ::std::io::Result<usize> {
+                ::std::io::Read::read(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn read_vectored(
+                &mut self,
+                __bufs: &mut [::std::io::IoSliceMut<'_>],
+            ) -> ::std::io::Result<usize> {
+                ::std::io::Read::read_vectored(&mut self.#member, __bufs)
+            }
+
+            #[inline]
+            fn read_to_end(&mut self, __buf: &mut ::std::vec::Vec<u8>) -> ::std::io::Result<usize> {
+                ::std::io::Read::read_to_end(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn read_to_string(
+                &mut self,
+                __buf: &mut ::std::string::String,
+            ) -> ::std::io::Result<usize> {
+                ::std::io::Read::read_to_string(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn read_exact(&mut self, __buf: &mut [u8]) -> ::std::io::Result<()> {
+                ::std::io::Read::read_exact(&mut self.#member, __buf)
+            }
+        },
+        Traits::Write => quote! {
+            #[inline]
+            fn write(&mut self, __buf: &[u8]) -> ::std::io::Result<usize> {
+                ::std::io::Write::write(&mut self.#member, __buf)
+            }
+
+            #[inline]
+            fn write_vectored(
+                &mut self,
+                __bufs: &[::std::io::IoSlice<'_>],
+            ) -> ::std::io::Result<usize> {
+                ::std::io::Write::write_vectored(&mut self.#member, __bufs)
+            }
+
+            #[inline]
+            fn flush(&mut self) -> ::std::io::Result<()> {
+                ::std::io::Write::flush(&mut self.#member)
+            }
+
+            #[inline]
+            fn write_all(&mut self, __buf: &[u8]) -> ::std::io::Result<()> {
+                ::std::io::Write::write_all(&mut self.#member, __buf)
+            }
+        },
+        Traits::Seek => {
+            let stream_position = if options.supports(51) {
+                quote! {
+                    #[inline]
+                    fn stream_position(&mut self) -> ::std::io::Result<u64> {
+                        ::std::io::Seek::stream_position(&mut self.#member)
+                    }
+                }
+            } else {
+                TokenStream::new()
+            };
+
+            quote! {
+                #[inline]
+                fn seek(&mut self, __pos: ::std::io::SeekFrom) -> ::std::io::Result<u64> {
+                    ::std::io::Seek::seek(&mut self.#member, __pos)
+                }
+
+                #stream_position
+            }
+        }
+        _ => unreachable!("unexpected trait"),
+    }
+}
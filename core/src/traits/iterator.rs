@@ -0,0 +1,44 @@
+//! [`Iterator`](core::iter::Iterator),
+//! [`DoubleEndedIterator`](core::iter::DoubleEndedIterator) and
+//! [`ExactSizeIterator`](core::iter::ExactSizeIterator) implementations for
+//! wrappers, forwarding to the wrapped field.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{data::Item, traits::Traits};
+
+pub fn generate_body(item: &Item, trait_: &Traits) -> TokenStream {
+    let field = item.wrapped_field(trait_).expect("checked before");
+    let ty = &field.field.ty;
+    let member = &field.member;
+
+    match trait_ {
+        Traits::Iterator => quote! {
+            type Item = <#ty as ::core::iter::Iterator>::Item;
+
+            #[inline]
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                ::core::iter::Iterator::next(&mut self.#member)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                ::core::iter::Iterator::size_hint(&self.#member)
+            }
+        },
+        Traits::DoubleEndedIterator => quote! {
+            #[inline]
+            fn next_back(&mut self) -> ::core::option::Option<Self::Item> {
+                ::core::iter::DoubleEndedIterator::next_back(&mut self.#member)
+            }
+        },
+        Traits::ExactSizeIterator => quote! {
+            #[inline]
+            fn len(&self) -> usize {
+                ::core::iter::ExactSizeIterator::len(&self.#member)
+            }
+        },
+        _ => unreachable!("unexpected trait"),
+    }
+}
@@ -0,0 +1,94 @@
+//! Comparison and hashing implementations in terms of a key expression, given
+//! by the `key = expr` or `via = function` options, instead of the fields.
+
+use proc_macro2::{Group, Spacing, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::Expr;
+
+use super::Traits;
+
+/// Replaces `self` in the key expression, to evaluate it for `other`. `self`
+/// as the start of a path, like `self::key(...)`, is kept.
+fn replace_self(tokens: TokenStream, with: &TokenTree) -> TokenStream {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut replaced = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        replaced.push(match token {
+            TokenTree::Ident(ident) if ident == "self" => match tokens.peek() {
+                Some(TokenTree::Punct(punct))
+                    if punct.as_char() == ':' && punct.spacing() == Spacing::Joint =>
+                {
+                    TokenTree::Ident(ident)
+                }
+                _ => with.clone(),
+            },
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), replace_self(group.stream(), with));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced)
+            }
+            token => token,
+        });
+    }
+
+    replaced.into_iter().collect()
+}
+
+pub fn generate_body(
+    trait_: &Traits,
+    key: &Expr,
+    hash_tag: &TokenStream,
+    ptr_eq: &TokenStream,
+) -> TokenStream {
+    let self_key = key.to_token_stream();
+    let other_key = replace_self(self_key.clone(), &TokenTree::Ident(format_ident!("other")));
+
+    match trait_ {
+        Traits::Eq => TokenStream::new(),
+        Traits::Hash | Traits::HashPortable => quote! {
+            fn hash<__H: ::core::hash::Hasher>(&self, __state: &mut __H) {
+                #hash_tag
+                ::core::hash::Hash::hash(&(#self_key), __state);
+            }
+        },
+        Traits::Ord => quote! {
+            #[inline]
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                #ptr_eq
+                ::core::cmp::Ord::cmp(&(#self_key), &(#other_key))
+            }
+        },
+        Traits::PartialEq => quote! {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                #ptr_eq
+                ::core::cmp::PartialEq::eq(&(#self_key), &(#other_key))
+            }
+        },
+        Traits::PartialOrd => quote! {
+            #[inline]
+            fn partial_cmp(
+                &self,
+                other: &Self,
+            ) -> ::core::option::Option<::core::cmp::Ordering> {
+                #ptr_eq
+                ::core::cmp::PartialOrd::partial_cmp(&(#self_key), &(#other_key))
+            }
+        },
+        _ => unreachable!("unexpected trait"),
+    }
+}
+
+/// Checks that the key implements [`Eq`], instead of the fields.
+pub fn generate_assertions(key: &Expr) -> TokenStream {
+    quote! {
+        #[allow(dead_code)]
+        #[doc(hidden)]
+        fn __derive_where_assert_eq(&self) {
+            fn __assert_eq<__T: ::core::cmp::Eq + ?::core::marker::Sized>(_: &__T) {}
+            __assert_eq(&(#key));
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! [`Encode`](https://docs.rs/minicbor) and `Decode` implementations of
+//! minicbor, in the array encoding of minicbor's own derives: the fields of a
+//! struct or variant are encoded as an array, each at the position of its
+//! index, and enums as an array of the index of the variant and its fields.
+//!
+//! Indices are the positions of fields and variants unless given by
+//! `index(n)`. Positions without a field, like those of fields skipped with
+//! `skip(minicbor::Encode, minicbor::Decode)` and of `PhantomData` fields, are
+//! encoded as `null`. When decoding, they're skipped, missing trailing fields
+//! are taken from `Decode::nil`, and skipped fields are set to their default.
+//!
+//! The impls are generic over the context `__C`, and `Decode` over the lifetime
+//! of the input, so they're generated whole, with bounds on the types of the
+//! fields encoded or decoded, except those mentioning the item itself.
+
+use std::collections::HashSet;
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_quote, spanned::Spanned, Error, GenericParam, Generics, Ident, Lifetime, LitInt, Result,
+    WherePredicate,
+};
+
+use crate::{
+    attr::Options,
+    data::{Data, FieldData, Item},
+    traits::Traits,
+};
+
+/// Index given by `index(n)`, or the position otherwise.
+fn index(position: usize, index: &Option<LitInt>) -> u32 {
+    match index {
+        Some(index) => index.base10_parse().expect("checked when parsing"),
+        None => position as u32,
+    }
+}
+
+/// Index of the variant.
+fn variant_index(position: usize, data: &Data) -> Literal {
+    Literal::u32_unsuffixed(index(position, &data.options.index))
+}
+
+/// Fields encoded by `trait_`, with their index, ordered by it, and the length
+/// of the array holding them.
+fn fields<'a, 'b>(data: &'b Data<'a>, trait_: &Traits) -> (Vec<(u32, &'b FieldData<'a>)>, u32) {
+    let mut fields: Vec<_> = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(position, field)| (index(position, &field.options.index), field))
+        .collect();
+    let len = fields.iter().map(|(index, _)| index + 1).max().unwrap_or(0);
+
+    fields.retain(|(_, field)| !field.skips(trait_) && !field.is_phantom_data());
+    fields.sort_by_key(|(index, _)| *index);
+
+    (fields, len)
+}
+
+/// Checks that indices are unique.
+pub fn check(item: &Item) -> Result<()> {
+    let check = |indices: &mut HashSet<u32>, index: u32, span: Span| {
+        if indices.insert(index) {
+            Ok(())
+        } else {
+            Err(Error::new(span, format!("duplicate index {}", index)))
+        }
+    };
+
+    let mut variants = HashSet::new();
+
+    for (position, data) in item.datas().iter().enumerate() {
+        if let Item::Enum { .. } = item {
+            let span = data
+                .options
+                .index
+                .as_ref()
+                .map_or(data.name.span(), LitInt::span);
+            check(&mut variants, index(position, &data.options.index), span)?;
+        }
+
+        let mut fields = HashSet::new();
+
+        for (position, field) in data.fields.iter().enumerate() {
+            let span = field
+                .options
+                .index
+                .as_ref()
+                .map_or(field.field.span(), LitInt::span);
+            check(&mut fields, index(position, &field.options.index), span)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn generate_impl(
+    item: &Item,
+    options: &Options,
+    trait_: &Traits,
+    ident: &Ident,
+    self_ty: &TokenStream,
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+) -> TokenStream {
+    let krate = options.crate_path("minicbor");
+    let mut generics = generics.clone();
+    let lifetime = Lifetime::new("'__minicbor", Span::call_site());
+
+    if let Traits::CborDecode = trait_ {
+        generics
+            .params
+            .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+    }
+
+    generics.params.push(parse_quote!(__C));
+    let (impl_generics, ..) = generics.split_for_impl();
+
+    match trait_ {
+        Traits::CborDecode => {
+            let mut bounds = Vec::new();
+            let mut decoders = Vec::new();
+
+            for data in item.datas() {
+                if data.is_uninhabited() {
+                    decoders.push(quote! {
+                        return ::core::result::Result::Err(#krate::decode::Error::message(
+                            "uninhabited enum variant",
+                        ))
+                    });
+                    continue;
+                }
+
+                let (fields, _) = fields(data, trait_);
+                let mut statements = Vec::new();
+                let mut next = 0;
+
+                for (index, field) in &fields {
+                    let ty = &field.field.ty;
+                    let self_ident = &field.self_ident;
+                    let gap = *index - next;
+                    let literal = Literal::u32_unsuffixed(*index);
+
+                    if !field.is_recursive(ident) {
+                        bounds.push(quote! { #ty: #krate::Decode<#lifetime, __C> });
+                    }
+
+                    if gap > 0 {
+                        statements.push(quote! {
+                            for _ in 0..#gap {
+                                if __position < __len {
+                                    #krate::Decoder::skip(d)?;
+                                    __position += 1;
+                                }
+                            }
+                        });
+                    }
+
+                    statements.push(quote_spanned! {ty.span()=>
+                        let #self_ident = if #literal < __len {
+                            __position += 1;
+                            #krate::Decode::decode(d, ctx)?
+                        } else {
+                            match #krate::Decode::nil() {
+                                ::core::option::Option::Some(value) => value,
+                                ::core::option::Option::None => {
+                                    return ::core::result::Result::Err(
+                                        #krate::decode::Error::message("missing field"),
+                                    )
+                                }
+                            }
+                        };
+                    });
+                    next = *index + 1;
+                }
+
+                let values = data.fields.iter().map(|field| {
+                    let member = &field.member;
+                    let self_ident = &field.self_ident;
+
+                    if fields
+                        .iter()
+                        .any(|(_, decoded)| decoded.member == field.member)
+                    {
+                        quote! { #member: #self_ident }
+                    } else {
+                        let ty = &field.field.ty;
+                        bounds.push(quote! { #ty: ::core::default::Default });
+                        quote! { #member: ::core::default::Default::default() }
+                    }
+                });
+                let values: Vec<_> = values.collect();
+                let path = &data.path;
+                let value = data.wrap(quote! { #path { #(#values,)* } });
+
+                decoders.push(quote! {
+                    {
+                        let __len = match #krate::Decoder::array(d)? {
+                            ::core::option::Option::Some(__len) => __len,
+                            ::core::option::Option::None => {
+                                return ::core::result::Result::Err(
+                                    #krate::decode::Error::message("indefinite arrays aren't supported"),
+                                )
+                            }
+                        };
+                        let mut __position = 0;
+                        #(#statements)*
+
+                        while __position < __len {
+                            #krate::Decoder::skip(d)?;
+                            __position += 1;
+                        }
+
+                        #value
+                    }
+                });
+            }
+
+            let body = match item {
+                Item::Struct(data) if data.is_uninhabited() => quote! { #(#decoders)* },
+                Item::Struct(_) => quote! { ::core::result::Result::Ok(#(#decoders)*) },
+                Item::Enum { variants, .. } => {
+                    let indices = variants
+                        .iter()
+                        .enumerate()
+                        .map(|(position, data)| variant_index(position, data));
+
+                    quote! {
+                        if #krate::Decoder::array(d)? != ::core::option::Option::Some(2) {
+                            return ::core::result::Result::Err(#krate::decode::Error::message(
+                                "expected an array of the variant and its fields",
+                            ));
+                        }
+
+                        ::core::result::Result::Ok(match #krate::Decoder::u32(d)? {
+                            #(#indices => #decoders,)*
+                            _ => {
+                                return ::core::result::Result::Err(
+                                    #krate::decode::Error::message("invalid enum variant"),
+                                )
+                            }
+                        })
+                    }
+                }
+            };
+
+            quote! {
+                impl #impl_generics #krate::Decode<#lifetime, __C> for #self_ty
+                    where #(#predicates,)* #(#bounds),*
+                {
+                    #[allow(unused_variables)]
+                    fn decode(
+                        d: &mut #krate::Decoder<#lifetime>,
+                        ctx: &mut __C,
+                    ) -> ::core::result::Result<Self, #krate::decode::Error> {
+                        #body
+                    }
+                }
+            }
+        }
+        Traits::CborEncode => {
+            let mut bounds = Vec::new();
+
+            let body = if item.is_empty() {
+                quote! { match *self {} }
+            } else {
+                let arms = item.datas().iter().enumerate().map(|(position, data)| {
+                    if let Some(arm) = data.uninhabited_arm(0) {
+                        return arm;
+                    }
+
+                    let pattern = data.self_pattern_for(trait_);
+                    let variant = data.discriminant.as_ref().map(|_| {
+                        let index = variant_index(position, data);
+
+                        quote! {
+                            #krate::Encoder::array(e, 2)?;
+                            #krate::Encoder::u32(e, #index)?;
+                        }
+                    });
+                    let (fields, len) = fields(data, trait_);
+                    let mut encoders = Vec::new();
+                    let mut next = 0;
+
+                    for (index, field) in fields {
+                        let ty = &field.field.ty;
+                        let self_ident = &field.self_ident;
+
+                        if !field.is_recursive(ident) {
+                            bounds.push(quote! { #ty: #krate::Encode<__C> });
+                        }
+
+                        for _ in next..index {
+                            encoders.push(quote! { #krate::Encoder::null(e)?; });
+                        }
+
+                        encoders.push(quote_spanned! {ty.span()=>
+                            #krate::Encode::encode(#self_ident, e, ctx)?;
+                        });
+                        next = index + 1;
+                    }
+
+                    for _ in next..len {
+                        encoders.push(quote! { #krate::Encoder::null(e)?; });
+                    }
+
+                    let len = Literal::u32_unsuffixed(len);
+
+                    quote! {
+                        #pattern => {
+                            #variant
+                            #krate::Encoder::array(e, #len)?;
+                            #(#encoders)*
+                        }
+                    }
+                });
+                let arms: Vec<_> = arms.collect();
+
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            };
+
+            quote! {
+                impl #impl_generics #krate::Encode<__C> for #self_ty
+                    where #(#predicates,)* #(#bounds),*
+                {
+                    #[allow(unused_variables)]
+                    fn encode<__W: #krate::encode::Write>(
+                        &self,
+                        e: &mut #krate::Encoder<__W>,
+                        ctx: &mut __C,
+                    ) -> ::core::result::Result<(), #krate::encode::Error<__W::Error>> {
+                        #body
+
+                        ::core::result::Result::Ok(())
+                    }
+                }
+            }
+        }
+        _ => unreachable!("unexpected trait"),
+    }
+}
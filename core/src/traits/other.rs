@@ -0,0 +1,47 @@
+//! Other types comparisons can be derived with, as in `PartialEq<Other>`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Error, GenericArgument, PathArguments, Result, Type, TypePath};
+
+/// Right-hand side type of a comparison, which has to be a struct or enum with
+/// the same shape as the item.
+#[derive(Clone)]
+pub struct Other {
+    pub ty: TypePath,
+}
+
+impl Other {
+    /// Parses the single type argument of a trait, e.g. `Other` in
+    /// `PartialEq<Other>`.
+    pub fn from_arguments(arguments: &PathArguments) -> Result<Self> {
+        let error = |span| Error::new(span, "expected a single struct or enum type");
+
+        let arguments = match arguments {
+            PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => arguments,
+            _ => return Err(error(arguments.span())),
+        };
+
+        match &arguments.args[0] {
+            GenericArgument::Type(Type::Path(ty)) if ty.qself.is_none() => {
+                Ok(Self { ty: ty.clone() })
+            }
+            argument => Err(error(argument.span())),
+        }
+    }
+
+    /// Path to match values with, without generic arguments, which are
+    /// inferred instead.
+    pub fn pattern_path(&self) -> TokenStream {
+        let leading_colon = &self.ty.path.leading_colon;
+        let segments = self.ty.path.segments.iter().map(|segment| &segment.ident);
+
+        quote! { #leading_colon #(#segments)::* }
+    }
+}
+
+impl PartialEq for Other {
+    fn eq(&self, other: &Self) -> bool {
+        self.ty.to_token_stream().to_string() == other.ty.to_token_stream().to_string()
+    }
+}
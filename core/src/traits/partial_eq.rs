@@ -0,0 +1,281 @@
+//! [`PartialEq`](core::cmp::PartialEq) implementations, with `Self` or another
+//! type of the same shape. Enums whose variants have no fields compare their
+//! discriminants. Variants marked `incomparable` are never equal to `Self`,
+//! unless `Ord` has a fallback for them given by `incomparable_ord`: they're
+//! then equal to values of the same variant, as `Eq` requires.
+//! Fields with an `epsilon` are equal within that tolerance, and `unordered`
+//! fields are equal if every element occurs as often in both.
+//! `case_insensitive` fields are compared ignoring ASCII case.
+//!
+//! With the `ptr_eq` option, `PartialEq`, `PartialOrd` and `Ord` return early
+//! when comparing a value with itself, without comparing any fields.
+//!
+//! With the `structural_match` option, `StructuralPartialEq` is implemented
+//! too, which only holds if every field is compared by its own `PartialEq`.
+
+#[cfg(feature = "nightly")]
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+#[cfg(feature = "nightly")]
+use syn::{Error, Result};
+
+use crate::{
+    attr::Options,
+    data::{FieldData, Item},
+    traits::{Other, Traits},
+};
+
+/// Returns early if `self` and `other` are the same value, with the `ptr_eq`
+/// option.
+pub fn generate_ptr_eq(trait_: &Traits, options: &Options) -> TokenStream {
+    let equal = match trait_ {
+        _ if !options.ptr_eq => return TokenStream::new(),
+        Traits::PartialEq => quote! { true },
+        Traits::Ord => quote! { ::core::cmp::Ordering::Equal },
+        Traits::PartialOrd => {
+            quote! { ::core::option::Option::Some(::core::cmp::Ordering::Equal) }
+        }
+        _ => return TokenStream::new(),
+    };
+
+    quote! {
+        if ::core::ptr::eq(self, other) {
+            return #equal;
+        }
+    }
+}
+
+/// Checks that no option changes how fields are compared, as constants in
+/// patterns are matched field by field with `structural_match`.
+#[cfg(feature = "nightly")]
+pub fn check_structural(item: &Item, options: &Options) -> Result<()> {
+    let error = |span, option: &str| {
+        Err(Error::new(
+            span,
+            format!("`structural_match` doesn't support `{}`", option),
+        ))
+    };
+
+    if options.key.is_some() {
+        return error(Span::call_site(), "key");
+    }
+
+    for data in item.datas() {
+        if data.options.incomparable {
+            return error(data.name.span(), "incomparable");
+        }
+
+        for field in &data.fields {
+            let span = field.field.ty.span();
+
+            if field.skips(&Traits::PartialEq) {
+                return error(span, "skip");
+            } else if field.options.epsilon.is_some() {
+                return error(span, "epsilon");
+            } else if field.options.with.is_some() {
+                return error(span, "with");
+            } else if field.options.unordered {
+                return error(span, "unordered");
+            } else if field.options.case_insensitive {
+                return error(span, "case_insensitive");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn generate_body(item: &Item, options: &Options, ptr_eq: &TokenStream) -> TokenStream {
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else if is_unit_only(item) {
+        let (value, other) = if item.is_remote() {
+            (quote! { &self.0 }, quote! { &other.0 })
+        } else {
+            (quote! { self }, quote! { other })
+        };
+
+        quote! {
+            ::core::mem::discriminant(#value) == ::core::mem::discriminant(#other)
+        }
+    } else {
+        let arms = item.datas().iter().map(|data| {
+            if let Some(arm) = data.uninhabited_arm(1) {
+                return arm;
+            }
+
+            if data.options.incomparable {
+                let pattern = data.wildcard_pattern();
+                let equal = options.incomparable_ord.is_some();
+
+                return quote! {
+                    (#pattern, #pattern) => #equal
+                };
+            }
+
+            let fields = data.fields_for(&Traits::PartialEq);
+            let self_pattern = data.self_pattern_for(&Traits::PartialEq);
+            let other_pattern = data.other_pattern_for(&Traits::PartialEq);
+
+            let comparisons = fields.iter().copied().map(compare);
+
+            let body = if fields.is_empty() {
+                quote! { true }
+            } else {
+                quote! { #(#comparisons)&&* }
+            };
+
+            quote! {
+                (#self_pattern, #other_pattern) => #body
+            }
+        });
+
+        let rest = if item.is_multi_variant() {
+            quote! { _ => false, }
+        } else {
+            TokenStream::new()
+        };
+
+        quote! {
+            match (self, other) {
+                #(#arms,)*
+                #rest
+            }
+        }
+    };
+
+    quote! {
+        #[inline]
+        fn eq(&self, other: &Self) -> bool {
+            #ptr_eq
+            #body
+        }
+    }
+}
+
+/// Returns `true` for enums with several variants, none of which have fields
+/// or are `incomparable`: values are equal if they're the same variant, which
+/// their discriminants tell without matching.
+fn is_unit_only(item: &Item) -> bool {
+    item.is_multi_variant()
+        && item
+            .datas()
+            .iter()
+            .all(|data| data.fields.is_empty() && !data.options.incomparable)
+}
+
+/// Compares a field with its counterpart, within its `epsilon`, as a multiset
+/// if `unordered`, ignoring ASCII case if `case_insensitive` or through its
+/// `with` module if given.
+///
+/// Exactly equal values are checked first, so infinities are still equal to
+/// themselves. The smaller value is subtracted from the larger one, so unsigned
+/// integers don't overflow.
+fn compare(field: &FieldData) -> TokenStream {
+    let self_ident = &field.self_ident;
+    let other_ident = &field.other_ident;
+
+    if field.options.unordered {
+        // Counting is quadratic, but only needs `PartialEq` of the elements.
+        return quote_spanned! {field.field.ty.span()=>
+            (#self_ident.len() == #other_ident.len()
+                && #self_ident.iter().all(|__element| {
+                    #self_ident
+                        .iter()
+                        .filter(|__self| ::core::cmp::PartialEq::eq(*__self, __element))
+                        .count()
+                        == #other_ident
+                            .iter()
+                            .filter(|__other| ::core::cmp::PartialEq::eq(__element, *__other))
+                            .count()
+                }))
+        };
+    }
+
+    if field.options.case_insensitive {
+        return quote_spanned! {field.field.ty.span()=>
+            <str>::eq_ignore_ascii_case(
+                ::core::convert::AsRef::<str>::as_ref(#self_ident),
+                ::core::convert::AsRef::<str>::as_ref(#other_ident),
+            )
+        };
+    }
+
+    match (&field.options.epsilon, &field.options.with) {
+        (Some(epsilon), _) => {
+            let within = quote_spanned! {field.field.ty.span()=>
+                ::core::cmp::PartialEq::eq(#self_ident, #other_ident)
+                    || *#self_ident > *#other_ident && *#self_ident - *#other_ident <= #epsilon
+                    || *#other_ident > *#self_ident && *#other_ident - *#self_ident <= #epsilon
+            };
+
+            // Only needed next to other fields, but not linted as generated.
+            quote! { (#within) }
+        }
+        (None, Some(with)) => quote_spanned! {field.field.ty.span()=>
+            #with::eq(#self_ident, #other_ident)
+        },
+        (None, None) => quote_spanned! {field.field.ty.span()=>
+            ::core::cmp::PartialEq::eq(#self_ident, #other_ident)
+        },
+    }
+}
+
+/// Compares with the struct or variant of the same name in `other`, field by
+/// field.
+pub fn generate_body_other(item: &Item, other: &Other) -> TokenStream {
+    let ty = &other.ty;
+    let path = other.pattern_path();
+
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let arms = item.datas().iter().map(|data| {
+            if let Some(arm) = data.uninhabited_arm(1) {
+                return arm;
+            }
+
+            let fields = data.fields_for(&Traits::PartialEq);
+            let self_pattern = data.self_pattern_for(&Traits::PartialEq);
+            let other_pattern = data.other_pattern_in(&path, &Traits::PartialEq);
+
+            let comparisons = fields.iter().copied().map(compare);
+
+            let body = if fields.is_empty() {
+                quote! { true }
+            } else {
+                quote! { #(#comparisons)&&* }
+            };
+
+            quote! {
+                (#self_pattern, #other_pattern) => #body
+            }
+        });
+
+        // `other` may have variants `Self` doesn't, so this is needed even for
+        // a single variant.
+        let rest = match item {
+            Item::Enum { .. } => quote! {
+                #[allow(unreachable_patterns)]
+                _ => false,
+            },
+            Item::Struct(_) => TokenStream::new(),
+        };
+
+        quote! {
+            match (self, other) {
+                #(#arms,)*
+                #rest
+            }
+        }
+    };
+
+    quote! {
+        #[inline]
+        fn eq(&self, other: &#ty) -> bool {
+            #body
+        }
+    }
+}
@@ -0,0 +1,446 @@
+//! [`PartialOrd`](core::cmp::PartialOrd) and [`Ord`](core::cmp::Ord)
+//! implementations, which only differ in their return type.
+//! `PartialOrd<Other>` compares with the struct or variant of the same name in
+//! another type instead.
+//!
+//! `PartialOrd` doesn't order variants marked `incomparable`, while `Ord` needs
+//! a fallback for them given by `incomparable_ord = last` or `panic`, which
+//! `PartialOrd` then uses too. Pairs of variants given by
+//! `incomparable_with(...)` aren't ordered with each other by `PartialOrd`,
+//! and can't be derived with `Ord`.
+//!
+//! Fields marked `case_insensitive` are ordered by their ASCII lowercase
+//! bytes.
+//!
+//! Variants of enums with an integer `#[repr(...)]` are compared by their
+//! discriminant first, before matching the fields of equal variants.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Ident, Path, Result,
+};
+
+use super::Traits;
+use crate::{
+    attr::Options,
+    data::{Data, FieldData, Item},
+};
+
+/// How `Ord` orders variants marked `incomparable`.
+#[derive(Clone, Copy)]
+pub enum IncomparableOrd {
+    /// After all other variants, by their discriminant among themselves, and
+    /// equal to values of the same variant, which `PartialEq` agrees with.
+    Last,
+    /// Panic when comparing them.
+    Panic,
+}
+
+impl Parse for IncomparableOrd {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+
+        match ident.to_string().as_str() {
+            "last" => Ok(IncomparableOrd::Last),
+            "panic" => Ok(IncomparableOrd::Panic),
+            _ => Err(Error::new(ident.span(), "expected `last` or `panic`")),
+        }
+    }
+}
+
+/// Checks that `Eq` and `Ord` have a fallback if there are incomparable
+/// variants, and that variants are only incomparable with other variants of the
+/// item.
+pub fn check(
+    item: &Item,
+    trait_: &Traits,
+    incomparable_ord: Option<IncomparableOrd>,
+) -> Result<()> {
+    let datas = item.datas();
+
+    for data in datas {
+        for other in &data.options.incomparable_with {
+            if let Traits::Ord = trait_ {
+                return Err(Error::new(
+                    other.span(),
+                    "`Ord` can't be derived with `incomparable_with`",
+                ));
+            } else if other == data.name {
+                return Err(Error::new(
+                    other.span(),
+                    "a variant can't be incomparable with itself",
+                ));
+            } else if !datas.iter().any(|data| other == data.name) {
+                return Err(Error::new(other.span(), "unknown variant"));
+            }
+        }
+    }
+
+    if let Traits::PartialOrd = trait_ {
+        return Ok(());
+    }
+
+    // Without a fallback, incomparable variants aren't equal to themselves,
+    // which `Eq` rules out too.
+    match datas.iter().find(|data| data.options.incomparable) {
+        Some(data) if incomparable_ord.is_none() => Err(Error::new(
+            data.name.span(),
+            format!(
+                "`{}` with incomparable variants requires `incomparable_ord = last` or `incomparable_ord = panic`",
+                trait_.ident()
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+pub fn generate_body(item: &Item, trait_: &Traits, options: &Options) -> TokenStream {
+    let incomparable_ord = options.incomparable_ord;
+    let ptr_eq = super::partial_eq::generate_ptr_eq(trait_, options);
+
+    type Wrap = fn(TokenStream) -> TokenStream;
+
+    // `wrap` turns an `Ordering` into the output of the method.
+    let (method, output, ordering, wrap): (_, _, Wrap, Wrap) = match trait_ {
+        Traits::Ord => (
+            quote! { cmp },
+            quote! { ::core::cmp::Ordering },
+            |ordering| quote! { ::core::cmp::Ordering::#ordering },
+            |ordering| ordering,
+        ),
+        Traits::PartialOrd | Traits::PartialOrdOther(_) => (
+            quote! { partial_cmp },
+            quote! { ::core::option::Option<::core::cmp::Ordering> },
+            |ordering| quote! { ::core::option::Option::Some(::core::cmp::Ordering::#ordering) },
+            |ordering| quote! { ::core::option::Option::Some(#ordering) },
+        ),
+        _ => unreachable!("unexpected trait"),
+    };
+
+    // Fields of different types are still compared through the plain trait.
+    let (path, other_ty, other_path) = match trait_ {
+        Traits::PartialOrdOther(other) => {
+            let ty = &other.ty;
+            (
+                Traits::PartialOrd.path(options),
+                quote! { #ty },
+                Some(other.pattern_path()),
+            )
+        }
+        _ => (trait_.path(options), quote! { Self }, None),
+    };
+
+    // Comparisons with another type ignore `incomparable`. `PartialOrd` uses
+    // the fallback of `Ord` too if given, as they have to agree.
+    let incomparable = match (&other_path, incomparable_ord) {
+        (Some(_), _) => None,
+        (None, Some(IncomparableOrd::Last)) => Some(ordering(quote! { Equal })),
+        (None, Some(IncomparableOrd::Panic)) => Some(quote! {
+            ::core::panic!("incomparable variants can't be ordered")
+        }),
+        (None, None) if matches!(trait_, Traits::Ord) => None,
+        (None, None) => Some(quote! { ::core::option::Option::None }),
+    };
+
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let arms = item.datas().iter().map(|data| {
+            if let Some(arm) = data.uninhabited_arm(1) {
+                return arm;
+            }
+
+            if let (true, Some(incomparable)) = (data.options.incomparable, &incomparable) {
+                let pattern = data.wildcard_pattern();
+
+                return quote! {
+                    (#pattern, #pattern) => #incomparable
+                };
+            }
+
+            let (self_pattern, other_pattern) = match &other_path {
+                Some(other_path) => (
+                    data.self_pattern_for(trait_),
+                    data.other_pattern_in(other_path, trait_),
+                ),
+                None => (
+                    data.self_pattern_for(trait_),
+                    data.other_pattern_for(trait_),
+                ),
+            };
+            let mut fields = data.fields_for(trait_);
+            // Stable, so fields of the same priority keep declaration order.
+            fields.sort_by_key(|field| (field.options.ord.is_none(), field.options.ord));
+            let body = compare_fields(fields, &path, &method, ordering, wrap);
+
+            quote! {
+                (#self_pattern, #other_pattern) => #body
+            }
+        });
+
+        // With an explicit `#[repr(...)]`, discriminants are compared first,
+        // so only fields of the same variant are matched afterwards.
+        let discriminant_first = matches!(
+            item,
+            Item::Enum {
+                explicit_repr: true,
+                ..
+            }
+        ) && item.is_multi_variant()
+            && other_path.is_none()
+            && item.datas().iter().all(|data| {
+                !data.options.incomparable && data.options.incomparable_with.is_empty()
+            });
+
+        // Different variants are ordered by their discriminant.
+        let rest = match item {
+            Item::Enum { .. } if discriminant_first => quote! {
+                _ => ::core::unreachable!("different variants are ordered by their discriminant"),
+            },
+            Item::Enum { variants, repr, .. } if item.is_multi_variant() => {
+                let patterns = variants.iter().map(Data::wildcard_pattern);
+                let discriminants = variants.iter().map(|variant| &variant.discriminant);
+
+                // Variants of the other type are ordered like those of the
+                // same name.
+                let other_discriminant = match &other_path {
+                    Some(other_path) => {
+                        let patterns = variants
+                            .iter()
+                            .map(|variant| variant.wildcard_pattern_in(other_path));
+                        let discriminants = variants.iter().map(|variant| &variant.discriminant);
+
+                        quote! {
+                            |__this: &#other_ty| -> #repr {
+                                match __this {
+                                    #(#patterns => #discriminants,)*
+                                }
+                            }
+                        }
+                    }
+                    None => quote! { __discriminant },
+                };
+
+                let compare = quote! {
+                    #path::#method(&__discriminant(self), &__other_discriminant(other))
+                };
+                let incomparable_variants: Vec<_> = variants
+                    .iter()
+                    .filter(|variant| variant.options.incomparable)
+                    .map(Data::wildcard_pattern)
+                    .collect();
+
+                let compare = match &incomparable {
+                    Some(_) if !incomparable_variants.is_empty() => {
+                        let (less, greater) =
+                            (ordering(quote! { Less }), ordering(quote! { Greater }));
+                        let fallback = match incomparable_ord {
+                            Some(IncomparableOrd::Last) => quote! {
+                                (false, true) => #less,
+                                (true, false) => #greater,
+                                _ => #compare,
+                            },
+                            Some(IncomparableOrd::Panic) => quote! {
+                                (false, false) => #compare,
+                                _ => ::core::panic!("incomparable variants can't be ordered"),
+                            },
+                            None => quote! {
+                                (false, false) => #compare,
+                                _ => ::core::option::Option::None,
+                            },
+                        };
+
+                        // `matches!` is only available since Rust 1.42.
+                        let is_incomparable = if options.supports(42) {
+                            quote! {
+                                ::core::matches!(__this, #(#incomparable_variants)|*)
+                            }
+                        } else {
+                            quote! {
+                                match __this {
+                                    #(#incomparable_variants)|* => true,
+                                    _ => false,
+                                }
+                            }
+                        };
+
+                        quote! {{
+                            let __incomparable = |__this: &Self| #is_incomparable;
+
+                            match (__incomparable(self), __incomparable(other)) {
+                                #fallback
+                            }
+                        }}
+                    }
+                    _ => compare,
+                };
+
+                quote! {
+                    _ => {
+                        let __discriminant = |__this: &Self| -> #repr {
+                            match __this {
+                                #(#patterns => #discriminants,)*
+                            }
+                        };
+                        let __other_discriminant = #other_discriminant;
+
+                        #compare
+                    }
+                }
+            }
+            _ => TokenStream::new(),
+        };
+
+        // Only differing variants can be incomparable with each other, so these
+        // come right before comparing discriminants.
+        let incomparable_with = match (item, &other_path) {
+            (Item::Enum { variants, .. }, None) => variants
+                .iter()
+                .flat_map(|variant| {
+                    variant.options.incomparable_with.iter().map(move |other| {
+                        let other = variants
+                            .iter()
+                            .find(|data| other == data.name)
+                            .expect("unknown variant");
+                        let (this, other) = (variant.wildcard_pattern(), other.wildcard_pattern());
+
+                        quote! {
+                            (#this, #other) | (#other, #this) => ::core::option::Option::None,
+                        }
+                    })
+                })
+                .collect(),
+            _ => TokenStream::new(),
+        };
+
+        let body = quote! {
+            match (self, other) {
+                #(#arms,)*
+                #incomparable_with
+                #rest
+            }
+        };
+
+        match item {
+            Item::Enum { variants, repr, .. } if discriminant_first => {
+                let patterns = variants.iter().map(Data::wildcard_pattern);
+                let discriminants = variants.iter().map(|variant| &variant.discriminant);
+                let equal = ordering(quote! { Equal });
+
+                quote! {
+                    let __discriminant = |__this: &Self| -> #repr {
+                        match __this {
+                            #(#patterns => #discriminants,)*
+                        }
+                    };
+
+                    match #path::#method(&__discriminant(self), &__discriminant(other)) {
+                        #equal => #body,
+                        __cmp => __cmp,
+                    }
+                }
+            }
+            _ => body,
+        }
+    };
+
+    quote! {
+        #[inline]
+        fn #method(&self, other: &#other_ty) -> #output {
+            #ptr_eq
+            #body
+        }
+    }
+}
+
+/// Returns the result of `Ord`, if it's derived with the same bounds and
+/// fields.
+pub fn generate_body_ord() -> TokenStream {
+    quote! {
+        #[inline]
+        fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+            ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+        }
+    }
+}
+
+/// Compares fields in the given order, returning the first non-equal result.
+/// Fields with a `with` module are compared by its `cmp`, whose result is
+/// passed through `wrap`.
+///
+/// Comparisons are a flat sequence of early returns instead of nested
+/// `match`es, which large structs would hit compiler limits with.
+fn compare_fields(
+    fields: Vec<&FieldData>,
+    path: &Path,
+    method: &TokenStream,
+    ordering: fn(TokenStream) -> TokenStream,
+    wrap: fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let equal = ordering(quote! { Equal });
+
+    let mut comparisons: Vec<_> = fields
+        .into_iter()
+        .map(|field| {
+            let self_ident = &field.self_ident;
+            let other_ident = &field.other_ident;
+
+            if field.options.none_last {
+                let less = ordering(quote! { Less });
+                let greater = ordering(quote! { Greater });
+
+                quote_spanned! {field.field.ty.span()=>
+                    match (#self_ident, #other_ident) {
+                        (::core::option::Option::Some(__this), ::core::option::Option::Some(__other)) => {
+                            #path::#method(__this, __other)
+                        }
+                        (::core::option::Option::None, ::core::option::Option::None) => #equal,
+                        (::core::option::Option::None, ::core::option::Option::Some(_)) => #greater,
+                        (::core::option::Option::Some(_), ::core::option::Option::None) => #less,
+                    }
+                }
+            } else if field.options.case_insensitive {
+                wrap(quote_spanned! {field.field.ty.span()=>
+                    ::core::iter::Iterator::cmp(
+                        ::core::convert::AsRef::<str>::as_ref(#self_ident)
+                            .bytes()
+                            .map(|__byte| __byte.to_ascii_lowercase()),
+                        ::core::convert::AsRef::<str>::as_ref(#other_ident)
+                            .bytes()
+                            .map(|__byte| __byte.to_ascii_lowercase()),
+                    )
+                })
+            } else if let Some(with) = &field.options.with {
+                wrap(quote_spanned! {field.field.ty.span()=>
+                    #with::cmp(#self_ident, #other_ident)
+                })
+            } else {
+                quote_spanned! {field.field.ty.span()=>
+                    #path::#method(#self_ident, #other_ident)
+                }
+            }
+        })
+        .collect();
+
+    // The last comparison is the result if all others are equal.
+    let last = match comparisons.pop() {
+        Some(last) => last,
+        None => return equal,
+    };
+
+    if comparisons.is_empty() {
+        return last;
+    }
+
+    quote! {{
+        #(match #comparisons {
+            #equal => (),
+            __cmp => return __cmp,
+        })*
+
+        #last
+    }}
+}
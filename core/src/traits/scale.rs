@@ -0,0 +1,286 @@
+//! [`Encode`](https://docs.rs/parity-scale-codec), `Decode` and
+//! `MaxEncodedLen` implementations of parity-scale-codec, encoding the fields
+//! in declaration order: enums are preceded by their discriminant as a `u8`,
+//! or the index given by `index(n)` on the variant. Tags are checked at
+//! compile time to fit in a `u8` and to differ between variants.
+//!
+//! Unlike parity-scale-codec's own derive, variants without an explicit
+//! discriminant following one with an explicit discriminant are tagged by their
+//! discriminant, counting up from the explicit one, not by their position.
+//!
+//! Fields skipped with `skip(Encode, Decode)` and `PhantomData` fields aren't
+//! encoded, and are set to their default when decoding. Only the bounds given
+//! to `derive_where` are used, none are added for the types of the fields.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Error, Result};
+
+use super::Traits;
+use crate::{
+    attr::Options,
+    data::{Data, FieldData, Item},
+};
+
+/// Checks that an enum has no more variants than a `u8` can tell apart, and
+/// that indices given by `index(n)` fit in a `u8`.
+pub fn check(item: &Item) -> Result<()> {
+    match item {
+        Item::Enum { variants, .. } if variants.len() > 256 => Err(Error::new(
+            Span::call_site(),
+            "parity-scale-codec encodes the variant in a `u8`, this enum has too many variants",
+        )),
+        Item::Enum { variants, .. } => {
+            for index in variants
+                .iter()
+                .filter_map(|data| data.options.index.as_ref())
+            {
+                if index.base10_parse::<u8>().is_err() {
+                    return Err(Error::new(
+                        index.span(),
+                        "parity-scale-codec encodes the variant in a `u8`, this index doesn't fit",
+                    ));
+                }
+            }
+
+            Ok(())
+        }
+        Item::Struct(_) => Ok(()),
+    }
+}
+
+/// Tag preceding the fields of an enum variant.
+fn tag(data: &Data) -> Option<TokenStream> {
+    match (index(data), &data.discriminant) {
+        (Some(index), _) => Some(quote! { #index }),
+        (None, Some(discriminant)) => Some(quote! { (#discriminant) as u8 }),
+        (None, None) => None,
+    }
+}
+
+/// Index given by `index(n)`, without the suffix it may have been given with.
+fn index(data: &Data) -> Option<Literal> {
+    data.options
+        .index
+        .as_ref()
+        .and_then(|index| index.base10_parse().ok())
+        .map(Literal::u8_unsuffixed)
+}
+
+/// Checks at compile time that the tags of all variants fit in a `u8` and are
+/// distinct, as discriminants given by constants can't be evaluated here.
+/// Panicking in constants requires Rust 1.57.
+pub fn generate_items(item: &Item, options: &Options) -> Option<TokenStream> {
+    let variants = match item {
+        Item::Enum { variants, .. } if !variants.is_empty() && options.supports(57) => variants,
+        _ => return None,
+    };
+
+    let len = variants.len();
+    let tags = variants
+        .iter()
+        .map(|data| match (index(data), &data.discriminant) {
+            (Some(index), _) => quote! { #index },
+            (None, discriminant) => quote! { (#discriminant) as i128 },
+        });
+    let ranges = variants.iter().enumerate().map(|(position, data)| {
+        let message = format!(
+            "the parity-scale-codec tag of `{}` doesn't fit in a `u8`, give it an `index(n)`",
+            data.name
+        );
+
+        quote! {
+            if __tags[#position] < 0 || __tags[#position] > 255 {
+                ::core::panic!(#message);
+            }
+        }
+    });
+
+    Some(quote! {
+        const _: () = {
+            let __tags: [i128; #len] = [#(#tags),*];
+            #(#ranges)*
+
+            let mut __index = 0;
+
+            while __index < #len {
+                let mut __other = 0;
+
+                while __other < __index {
+                    if __tags[__index] == __tags[__other] {
+                        ::core::panic!(
+                            "variants have the same parity-scale-codec tag, give them an `index(n)`"
+                        );
+                    }
+
+                    __other += 1;
+                }
+
+                __index += 1;
+            }
+        };
+    })
+}
+
+/// Fields encoded for `trait_`.
+fn fields<'a, 'b>(data: &'b Data<'a>, trait_: &Traits) -> Vec<&'b FieldData<'a>> {
+    data.fields_for(trait_)
+        .into_iter()
+        .filter(|field| !field.is_phantom_data())
+        .collect()
+}
+
+pub fn generate_body(item: &Item, trait_: &Traits, options: &Options) -> TokenStream {
+    let krate = options.crate_path("parity_scale_codec");
+    match trait_ {
+        Traits::ScaleEncode => {
+            let (size_hint, encode_to) = if item.is_empty() {
+                (quote! { match *self {} }, quote! { match *self {} })
+            } else {
+                let mut hints = Vec::new();
+                let mut encoders = Vec::new();
+
+                for data in item.datas() {
+                    if let Some(arm) = data.uninhabited_arm(0) {
+                        hints.push(arm.clone());
+                        encoders.push(arm);
+                        continue;
+                    }
+
+                    let pattern = data.self_pattern_for(trait_);
+                    let fields = fields(data, trait_);
+                    let self_idents: Vec<_> =
+                        fields.iter().map(|field| &field.self_ident).collect();
+                    let (tag_hint, tag) = match tag(data) {
+                        Some(tag) => (
+                            quote! { 1 },
+                            quote! { #krate::Output::push_byte(__dest, #tag); },
+                        ),
+                        None => (quote! { 0 }, TokenStream::new()),
+                    };
+                    let encoders_ = fields.iter().map(|field| {
+                        let self_ident = &field.self_ident;
+
+                        quote_spanned! {field.field.ty.span()=>
+                            #krate::Encode::encode_to(#self_ident, __dest);
+                        }
+                    });
+
+                    hints.push(quote! {
+                        #pattern => #tag_hint #(+ #krate::Encode::size_hint(#self_idents))*,
+                    });
+                    encoders.push(quote! {
+                        #pattern => {
+                            #tag
+                            #(#encoders_)*
+                        }
+                    });
+                }
+
+                (
+                    quote! { match self { #(#hints)* } },
+                    quote! { match self { #(#encoders)* } },
+                )
+            };
+
+            quote! {
+                fn size_hint(&self) -> usize {
+                    #size_hint
+                }
+
+                fn encode_to<__T: #krate::Output + ?::core::marker::Sized>(
+                    &self,
+                    __dest: &mut __T,
+                ) {
+                    #encode_to
+                }
+            }
+        }
+        Traits::ScaleDecode => {
+            let error = |message: &str| {
+                quote! {
+                    return ::core::result::Result::Err(::core::convert::From::from(#message))
+                }
+            };
+            let decoders: Vec<_> = item
+                .datas()
+                .iter()
+                .map(|data| {
+                    if data.is_uninhabited() {
+                        return error("uninhabited enum variant");
+                    }
+
+                    let fields = data.fields.iter().map(|field| {
+                        let member = &field.member;
+
+                        if field.skips(trait_) || field.is_phantom_data() {
+                            quote! { #member: ::core::default::Default::default() }
+                        } else {
+                            quote_spanned! {field.field.ty.span()=>
+                                #member: #krate::Decode::decode(__input)?
+                            }
+                        }
+                    });
+                    let path = &data.path;
+
+                    data.wrap(quote! { #path { #(#fields,)* } })
+                })
+                .collect();
+
+            let body = match item {
+                Item::Struct(data) if data.is_uninhabited() => quote! { #(#decoders)* },
+                Item::Struct(_) => quote! { ::core::result::Result::Ok(#(#decoders)*) },
+                Item::Enum { variants, .. } => {
+                    let tags = variants.iter().map(tag);
+                    let invalid = error("invalid enum variant");
+
+                    quote! {
+                        ::core::result::Result::Ok(match #krate::Input::read_byte(__input)? {
+                            #(__tag if __tag == #tags => #decoders,)*
+                            _ => #invalid,
+                        })
+                    }
+                }
+            };
+
+            quote! {
+                fn decode<__I: #krate::Input>(
+                    __input: &mut __I,
+                ) -> ::core::result::Result<Self, #krate::Error> {
+                    #body
+                }
+            }
+        }
+        Traits::ScaleMaxEncodedLen => {
+            let lens = item
+                .datas()
+                .iter()
+                .filter(|data| !data.is_uninhabited())
+                .map(|data| {
+                    let tag = match data.discriminant {
+                        Some(_) => quote! { 1_usize },
+                        None => quote! { 0_usize },
+                    };
+                    // Fields that aren't encoded don't count.
+                    let lens = fields(data, &Traits::ScaleEncode).into_iter().map(|field| {
+                        let ty = &field.field.ty;
+
+                        quote_spanned! {ty.span()=>
+                            .saturating_add(<#ty as #krate::MaxEncodedLen>::max_encoded_len())
+                        }
+                    });
+
+                    quote! { #tag #(#lens)* }
+                });
+
+            quote! {
+                fn max_encoded_len() -> usize {
+                    let mut __len = 0;
+                    #(__len = ::core::cmp::max(__len, #lens);)*
+                    __len
+                }
+            }
+        }
+        _ => unreachable!("unexpected trait"),
+    }
+}
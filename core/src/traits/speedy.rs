@@ -0,0 +1,181 @@
+//! [`Readable`](https://docs.rs/speedy) and `Writable` implementations of
+//! speedy, reading and writing the fields in declaration order in the same
+//! format as speedy's own derives: enums are preceded by their discriminant as
+//! a `u32` tag.
+//!
+//! Fields skipped with `skip(Readable, Writable)` and `PhantomData` fields
+//! aren't written, and are set to their default when reading. The impls are
+//! generic over the context `__C`, and `Readable` over its lifetime, so they're
+//! generated whole, with bounds on the types of the fields read or written,
+//! except those mentioning the item itself, which would need the impl itself.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, spanned::Spanned, GenericParam, Generics, Ident, Lifetime, WherePredicate};
+
+use crate::{attr::Options, data::Item, traits::Traits};
+
+pub fn generate_impl(
+    item: &Item,
+    options: &Options,
+    trait_: &Traits,
+    ident: &Ident,
+    self_ty: &TokenStream,
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+) -> TokenStream {
+    let krate = options.crate_path("speedy");
+    let mut generics = generics.clone();
+    let lifetime = Lifetime::new("'__speedy", Span::call_site());
+
+    if let Traits::SpeedyReadable = trait_ {
+        generics
+            .params
+            .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+    }
+
+    generics.params.push(parse_quote!(__C: #krate::Context));
+    let (impl_generics, ..) = generics.split_for_impl();
+
+    let tag = |discriminant: &Option<TokenStream>| {
+        let discriminant = discriminant.as_ref().expect("variants have discriminants");
+        quote! { (#discriminant) as u32 }
+    };
+
+    match trait_ {
+        Traits::SpeedyReadable => {
+            let mut bounds = Vec::new();
+            let mut readers = Vec::new();
+
+            for data in item.datas() {
+                if data.is_uninhabited() {
+                    readers.push(quote! {
+                        return ::core::result::Result::Err(::core::convert::From::from(
+                            #krate::Error::custom("uninhabited enum variant"),
+                        ))
+                    });
+                    continue;
+                }
+
+                let fields = data.fields.iter().map(|field| {
+                    let member = &field.member;
+                    let ty = &field.field.ty;
+
+                    if field.skips(trait_) || field.is_phantom_data() {
+                        bounds.push(quote! { #ty: ::core::default::Default });
+                        quote! { #member: ::core::default::Default::default() }
+                    } else {
+                        if !field.is_recursive(ident) {
+                            bounds.push(quote! { #ty: #krate::Readable<#lifetime, __C> });
+                        }
+
+                        quote_spanned! {ty.span()=>
+                            #member: #krate::Reader::read_value(reader)?
+                        }
+                    }
+                });
+                let path = &data.path;
+
+                readers.push(data.wrap(quote! { #path { #(#fields,)* } }));
+            }
+
+            let body = match item {
+                Item::Struct(_) => quote! { #(#readers)* },
+                Item::Enum { variants, .. } => {
+                    let tags = variants.iter().map(|variant| tag(&variant.discriminant));
+
+                    quote! {
+                        match #krate::Reader::read_u32(reader)? {
+                            #(__tag if __tag == #tags => #readers,)*
+                            _ => {
+                                return ::core::result::Result::Err(::core::convert::From::from(
+                                    #krate::Error::custom("invalid enum variant"),
+                                ))
+                            }
+                        }
+                    }
+                }
+            };
+
+            quote! {
+                impl #impl_generics #krate::Readable<#lifetime, __C> for #self_ty
+                    where #(#predicates,)* #(#bounds),*
+                {
+                    #[inline]
+                    fn read_from<__R: #krate::Reader<#lifetime, __C>>(
+                        reader: &mut __R,
+                    ) -> ::core::result::Result<Self, <__C as #krate::Context>::Error> {
+                        ::core::result::Result::Ok(#body)
+                    }
+                }
+            }
+        }
+        Traits::SpeedyWritable => {
+            let mut bounds = Vec::new();
+
+            let body = if item.is_empty() {
+                quote! { match *self {} }
+            } else {
+                let arms = item.datas().iter().map(|data| {
+                    if let Some(arm) = data.uninhabited_arm(0) {
+                        return arm;
+                    }
+
+                    let pattern = data.self_pattern_for(trait_);
+                    let tag = data.discriminant.as_ref().map(|_| {
+                        let tag = tag(&data.discriminant);
+                        quote! { #krate::Writer::write_u32(writer, #tag)?; }
+                    });
+                    let writers: Vec<_> = data
+                        .fields_for(trait_)
+                        .into_iter()
+                        .filter(|field| !field.is_phantom_data())
+                        .map(|field| {
+                            let ty = &field.field.ty;
+                            let self_ident = &field.self_ident;
+
+                            if !field.is_recursive(ident) {
+                                bounds.push(quote! { #ty: #krate::Writable<__C> });
+                            }
+
+                            quote_spanned! {ty.span()=>
+                                #krate::Writer::write_value(writer, #self_ident)?;
+                            }
+                        })
+                        .collect();
+
+                    quote! {
+                        #pattern => {
+                            #tag
+                            #(#writers)*
+                        }
+                    }
+                });
+                let arms: Vec<_> = arms.collect();
+
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            };
+
+            quote! {
+                impl #impl_generics #krate::Writable<__C> for #self_ty
+                    where #(#predicates,)* #(#bounds),*
+                {
+                    #[inline]
+                    fn write_to<__W: ?::core::marker::Sized + #krate::Writer<__C>>(
+                        &self,
+                        writer: &mut __W,
+                    ) -> ::core::result::Result<(), <__C as #krate::Context>::Error> {
+                        #body
+
+                        ::core::result::Result::Ok(())
+                    }
+                }
+            }
+        }
+        _ => unreachable!("unexpected trait"),
+    }
+}
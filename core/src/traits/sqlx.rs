@@ -0,0 +1,126 @@
+//! [`Type`](https://docs.rs/sqlx), `Encode` and `Decode` implementations of
+//! sqlx for wrappers, forwarding to the wrapped field for any database.
+//!
+//! The impls are generic over the database `__DB`, and `Encode` and `Decode`
+//! over their lifetime, so they're generated whole instead of through
+//! [`Traits::generate_body`]. `Decode` sets all other fields to their default.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, GenericParam, Generics, Lifetime, WherePredicate};
+
+use crate::{attr::Options, data::Item, traits::Traits};
+
+pub fn generate_impl(
+    item: &Item,
+    options: &Options,
+    trait_: &Traits,
+    self_ty: &TokenStream,
+    generics: &Generics,
+    predicates: &[&WherePredicate],
+) -> TokenStream {
+    let krate = options.crate_path("sqlx");
+    let field = item.wrapped_field(trait_).expect("checked before");
+    let ty = &field.field.ty;
+    let member = &field.member;
+
+    let mut generics = generics.clone();
+    let lifetime = Lifetime::new("'__sqlx", Span::call_site());
+
+    if !matches!(trait_, Traits::SqlxType) {
+        generics
+            .params
+            .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+    }
+
+    generics.params.push(parse_quote!(__DB: #krate::Database));
+
+    let (impl_generics, ..) = generics.split_for_impl();
+
+    let (path, body, bounds) = match trait_ {
+        Traits::SqlxType => (
+            quote! { #krate::Type<__DB> },
+            quote! {
+                #[inline]
+                fn type_info() -> <__DB as #krate::Database>::TypeInfo {
+                    <#ty as #krate::Type<__DB>>::type_info()
+                }
+
+                #[inline]
+                fn compatible(ty: &<__DB as #krate::Database>::TypeInfo) -> bool {
+                    <#ty as #krate::Type<__DB>>::compatible(ty)
+                }
+            },
+            vec![quote! { #ty: #krate::Type<__DB> }],
+        ),
+        Traits::SqlxEncode => (
+            quote! { #krate::Encode<#lifetime, __DB> },
+            quote! {
+                #[inline]
+                fn encode_by_ref(
+                    &self,
+                    buf: &mut <__DB as #krate::Database>::ArgumentBuffer<#lifetime>,
+                ) -> ::core::result::Result<#krate::encode::IsNull, #krate::error::BoxDynError> {
+                    <#ty as #krate::Encode<#lifetime, __DB>>::encode_by_ref(&self.#member, buf)
+                }
+
+                #[inline]
+                fn produces(&self) -> ::core::option::Option<<__DB as #krate::Database>::TypeInfo> {
+                    <#ty as #krate::Encode<#lifetime, __DB>>::produces(&self.#member)
+                }
+
+                #[inline]
+                fn size_hint(&self) -> usize {
+                    <#ty as #krate::Encode<#lifetime, __DB>>::size_hint(&self.#member)
+                }
+            },
+            vec![quote! { #ty: #krate::Encode<#lifetime, __DB> }],
+        ),
+        Traits::SqlxDecode => {
+            let data = match item {
+                Item::Struct(data) => data,
+                Item::Enum { .. } => unreachable!("checked before"),
+            };
+            let mut bounds = vec![quote! { #ty: #krate::Decode<#lifetime, __DB> }];
+            let mut fields = Vec::new();
+
+            for other in &data.fields {
+                let other_member = &other.member;
+
+                if other_member == member {
+                    fields.push(quote! {
+                        #member: <#ty as #krate::Decode<#lifetime, __DB>>::decode(value)?
+                    });
+                } else {
+                    let ty = &other.field.ty;
+                    bounds.push(quote! { #ty: ::core::default::Default });
+                    fields.push(quote! { #other_member: ::core::default::Default::default() });
+                }
+            }
+
+            let path = &data.path;
+
+            (
+                quote! { #krate::Decode<#lifetime, __DB> },
+                quote! {
+                    #[inline]
+                    fn decode(
+                        value: <__DB as #krate::Database>::ValueRef<#lifetime>,
+                    ) -> ::core::result::Result<Self, #krate::error::BoxDynError> {
+                        ::core::result::Result::Ok(#path { #(#fields,)* })
+                    }
+                },
+                bounds,
+            )
+        }
+        _ => unreachable!("unexpected trait"),
+    };
+
+    quote! {
+        impl #impl_generics #path for #self_ty
+            where #(#predicates,)* #(#bounds),*
+        {
+            #body
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! [`Tabled`](https://docs.rs/tabled) implementation for structs, with a
+//! column for each field: headed by its name, or the one given by its `rename`
+//! option, and rendered with `Display`, or `Debug` if given `tabled(debug)`.
+//! `PhantomData` fields and fields skipped with `skip(Tabled)` have no column.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Ident, Result, Token,
+};
+
+use super::Traits;
+use crate::data::{FieldData, Item};
+
+/// Rendering of a single field in tables: `tabled(debug)`.
+#[derive(Default)]
+pub struct FieldTabled {
+    /// Render the field with `Debug` instead of `Display`.
+    pub debug: bool,
+}
+
+impl Parse for FieldTabled {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut tabled = Self::default();
+
+        if input.is_empty() {
+            return Err(input.error("expected at least one option"));
+        }
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            match ident.to_string().as_str() {
+                "debug" => {
+                    if tabled.debug {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    tabled.debug = true;
+                }
+                _ => return Err(Error::new(ident.span(), "unsupported option")),
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(tabled)
+    }
+}
+
+/// Checks that the item is a struct.
+pub fn check(item: &Item) -> Result<()> {
+    match item {
+        Item::Struct(_) => Ok(()),
+        Item::Enum { .. } => Err(Error::new(
+            Span::call_site(),
+            "`Tabled` is only supported for structs",
+        )),
+    }
+}
+
+pub fn generate_body(item: &Item) -> TokenStream {
+    let data = match item {
+        Item::Struct(data) => data,
+        Item::Enum { .. } => unreachable!("checked by `check`"),
+    };
+    let fields: Vec<_> = data
+        .fields_for(&Traits::Tabled)
+        .into_iter()
+        .filter(|field| !field.is_phantom_data())
+        .collect();
+
+    let length = fields.len();
+    let headers = fields.iter().map(|field| header(field));
+    let body = match data.uninhabited_arm(0) {
+        Some(arm) => quote! { match self { #arm } },
+        None => {
+            let pattern = data.self_pattern_for(&Traits::Tabled);
+            let values = fields.iter().map(|field| value(field));
+
+            quote! {
+                match self {
+                    #pattern => ::std::vec![#(#values),*],
+                }
+            }
+        }
+    };
+
+    quote! {
+        const LENGTH: usize = #length;
+
+        fn fields(&self) -> ::std::vec::Vec<::std::borrow::Cow<'_, str>> {
+            #body
+        }
+
+        fn headers() -> ::std::vec::Vec<::std::borrow::Cow<'static, str>> {
+            ::std::vec![#(::std::borrow::Cow::Borrowed(#headers)),*]
+        }
+    }
+}
+
+/// Header of the column of the field.
+fn header(field: &FieldData) -> String {
+    match &field.options.rename {
+        Some(rename) => rename.value(),
+        None => field.member_name(),
+    }
+}
+
+/// Text of the field in its column.
+fn value(field: &FieldData) -> TokenStream {
+    let self_ident = &field.self_ident;
+
+    match &field.options.tabled {
+        Some(FieldTabled { debug: true }) => quote_spanned! {field.field.ty.span()=>
+            ::std::borrow::Cow::Owned(::std::format!("{:?}", #self_ident))
+        },
+        _ => quote_spanned! {field.field.ty.span()=>
+            ::std::borrow::Cow::Owned(::std::string::ToString::to_string(#self_ident))
+        },
+    }
+}
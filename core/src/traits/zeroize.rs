@@ -0,0 +1,153 @@
+//! [`Zeroize`](https://docs.rs/zeroize) implementation, zeroizing the fields of
+//! the struct or of whichever variant is active. Fields can be skipped with
+//! `skip(Zeroize)`, zeroized by a function with `zeroize(with = path)`, or have
+//! their allocation released afterwards with `zeroize(shrink)` or
+//! `zeroize(replace)`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Ident, Path, Result, Token,
+};
+
+use super::Traits;
+use crate::{
+    attr::Options,
+    data::{FieldData, Item},
+};
+
+/// Zeroization of a single field: `zeroize(with = path::to::function)`,
+/// `zeroize(shrink)` or `zeroize(replace)`.
+#[derive(Default)]
+pub struct FieldZeroize {
+    /// Function called with `&mut` the field instead of `Zeroize::zeroize`.
+    pub with: Option<Path>,
+    /// Shrink the capacity of a `Vec` or `String` after zeroizing, releasing
+    /// the zeroized allocation.
+    pub shrink: bool,
+    /// Replace the field with its default after zeroizing, releasing the
+    /// allocation of a `Box`, `Vec` or `String`.
+    pub replace: bool,
+}
+
+impl Parse for FieldZeroize {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut zeroize = Self::default();
+
+        if input.is_empty() {
+            return Err(input.error("expected at least one option"));
+        }
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            match ident.to_string().as_str() {
+                "with" => {
+                    if zeroize.with.is_some() {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    input.parse::<Token![=]>()?;
+                    zeroize.with = Some(input.parse()?);
+                }
+                "shrink" | "replace" => {
+                    let option = if ident == "shrink" {
+                        &mut zeroize.shrink
+                    } else {
+                        &mut zeroize.replace
+                    };
+
+                    if *option {
+                        return Err(Error::new(ident.span(), "duplicate option"));
+                    }
+
+                    *option = true;
+                }
+                _ => return Err(Error::new(ident.span(), "unsupported option")),
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        if [zeroize.with.is_some(), zeroize.shrink, zeroize.replace]
+            .iter()
+            .filter(|given| **given)
+            .count()
+            > 1
+        {
+            return Err(Error::new(
+                input.span(),
+                "only one of `with`, `shrink` and `replace` can be given",
+            ));
+        }
+
+        Ok(zeroize)
+    }
+}
+
+pub fn generate_body(item: &Item, options: &Options) -> TokenStream {
+    let krate = options.crate_path("zeroize");
+    let body = if item.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let arms = item.datas().iter().map(|data| {
+            if let Some(arm) = data.uninhabited_arm(0) {
+                return arm;
+            }
+
+            let pattern = data.self_pattern_for(&Traits::Zeroize);
+            let fields = data
+                .fields_for(&Traits::Zeroize)
+                .into_iter()
+                .map(|field| zeroize_field(field, &krate));
+
+            quote! {
+                #pattern => { #(#fields)* }
+            }
+        });
+
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        }
+    };
+
+    quote! {
+        fn zeroize(&mut self) {
+            #body
+        }
+    }
+}
+
+fn zeroize_field(field: &FieldData, krate: &Path) -> TokenStream {
+    let self_ident = &field.self_ident;
+
+    let span = field.field.ty.span();
+    let zeroize = quote_spanned! {span=>
+        #krate::Zeroize::zeroize(#self_ident);
+    };
+
+    match &field.options.zeroize {
+        Some(FieldZeroize {
+            with: Some(with), ..
+        }) => quote_spanned! {with.span()=>
+            #with(#self_ident);
+        },
+        Some(FieldZeroize { shrink: true, .. }) => quote_spanned! {span=>
+            #zeroize
+            #self_ident.shrink_to_fit();
+        },
+        Some(FieldZeroize { replace: true, .. }) => quote_spanned! {span=>
+            #zeroize
+            *#self_ident = ::core::default::Default::default();
+        },
+        _ => zeroize,
+    }
+}
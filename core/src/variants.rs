@@ -0,0 +1,172 @@
+//! Inherent items describing the variants of an enum, requested by the
+//! `variant_names`, `variant_iter` and `accessors` options.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, Error, Result, Visibility};
+
+use crate::data::{Data, Item};
+
+/// `VARIANTS`, the names of all variants, and `variant_name()`, the name of
+/// the variant of a value.
+pub fn generate_names(item: &Item, vis: &Visibility) -> Result<TokenStream> {
+    let variants = variants(item, "variant_names")?;
+    let names: Vec<_> = variants
+        .iter()
+        .map(|variant| {
+            variant
+                .ident
+                .expect("variants have names")
+                .unraw()
+                .to_string()
+        })
+        .collect();
+
+    let body = if variants.is_empty() {
+        quote! { match *self {} }
+    } else {
+        let patterns = variants.iter().map(|variant| variant.wildcard_pattern());
+
+        quote! {
+            match self {
+                #(#patterns => #names,)*
+            }
+        }
+    };
+
+    Ok(quote! {
+        /// Names of all variants, in declaration order.
+        #vis const VARIANTS: &'static [&'static str] = &[#(#names),*];
+
+        /// Name of the variant of this value.
+        #vis fn variant_name(&self) -> &'static str {
+            #body
+        }
+    })
+}
+
+/// `variants()`, iterating over all variants of an enum without fields.
+pub fn generate_iter(item: &Item, vis: &Visibility) -> Result<TokenStream> {
+    let variants = variants(item, "variant_iter")?;
+
+    if let Some(variant) = variants.iter().find(|variant| !variant.fields.is_empty()) {
+        return Err(Error::new(
+            variant.ident.expect("variants have names").span(),
+            "`variant_iter` requires variants without fields",
+        ));
+    }
+
+    let paths = variants.iter().map(|variant| &variant.path);
+
+    Ok(quote! {
+        /// Iterates over all variants, in declaration order.
+        #vis fn variants() -> impl ::core::iter::Iterator<Item = Self> {
+            ::core::iter::IntoIterator::into_iter([#(#paths),*])
+        }
+    })
+}
+
+/// `is_variant()`, `as_variant()` and `into_variant()` for each variant, named
+/// after it in snake case. Variants without fields only get `is_variant()`.
+pub fn generate_accessors(item: &Item, vis: &Visibility) -> Result<TokenStream> {
+    let variants = variants(item, "accessors")?;
+
+    Ok(variants
+        .iter()
+        .map(|variant| {
+            let ident = variant.ident.expect("variants have names");
+            let name = snake_case(&ident.unraw().to_string());
+            let is = format_ident!("is_{}", name);
+            let wildcard = variant.wildcard_pattern();
+
+            let is = quote! {
+                #[doc = concat!("Returns `true` if this is a [`Self::", stringify!(#ident), "`].")]
+                #vis fn #is(&self) -> bool {
+                    ::core::matches!(self, #wildcard)
+                }
+            };
+
+            if variant.fields.is_empty() {
+                return is;
+            }
+
+            let as_ = format_ident!("as_{}", name);
+            let into = format_ident!("into_{}", name);
+            let pattern = variant.self_pattern();
+            let types: Vec<_> = variant.fields.iter().map(|field| &field.field.ty).collect();
+            let bindings = variant.fields.iter().map(|field| &field.self_ident);
+
+            // A single field is returned as is, several as a tuple.
+            let (ref_ty, ty, value) = if variant.fields.len() == 1 {
+                (
+                    quote! { &#(#types)* },
+                    quote! { #(#types)* },
+                    quote! { #(#bindings)* },
+                )
+            } else {
+                (
+                    quote! { (#(&#types,)*) },
+                    quote! { (#(#types,)*) },
+                    quote! { (#(#bindings,)*) },
+                )
+            };
+
+            quote! {
+                #is
+
+                #[doc = concat!("Returns the fields if this is a [`Self::", stringify!(#ident), "`].")]
+                #vis fn #as_(&self) -> ::core::option::Option<#ref_ty> {
+                    match self {
+                        #pattern => ::core::option::Option::Some(#value),
+                        #[allow(unreachable_patterns)]
+                        _ => ::core::option::Option::None,
+                    }
+                }
+
+                #[doc = concat!("Returns the fields if this is a [`Self::", stringify!(#ident), "`], otherwise `self`.")]
+                #vis fn #into(self) -> ::core::result::Result<#ty, Self> {
+                    match self {
+                        #pattern => ::core::result::Result::Ok(#value),
+                        #[allow(unreachable_patterns)]
+                        __other => ::core::result::Result::Err(__other),
+                    }
+                }
+            }
+        })
+        .collect())
+}
+
+/// Converts a variant name to snake case, keeping acronyms together:
+/// `HttpServer` and `HTTPServer` both become `http_server`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+
+    for (index, &char) in chars.iter().enumerate() {
+        if char.is_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_lowercase = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+
+            if previous.is_lowercase()
+                || previous.is_numeric()
+                || (previous.is_uppercase() && next_lowercase)
+            {
+                snake.push('_');
+            }
+        }
+
+        snake.extend(char.to_lowercase());
+    }
+
+    snake
+}
+
+fn variants<'a, 'b>(item: &'b Item<'a>, option: &str) -> Result<&'b [Data<'a>]> {
+    match item {
+        Item::Enum { variants, .. } => Ok(variants),
+        Item::Struct(_) => Err(Error::new(
+            Span::call_site(),
+            format!("`{}` is only supported for enums", option),
+        )),
+    }
+}
@@ -0,0 +1,23 @@
+//! Warnings, which aren't supported by stable proc-macro APIs.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote_spanned};
+
+/// Names of the warnings that can be allowed by `allow(...)`.
+pub const WARNINGS: &[&str] = &["inconsistent_skip", "unused_bound"];
+
+/// Emits a warning at `span` by using a deprecated item, reported by the
+/// compiler as "use of deprecated unit struct `_::derive_where_<name>`: <note>".
+pub fn warning(span: Span, name: &str, note: &str) -> TokenStream {
+    let ident = format_ident!("derive_where_{}", name, span = span);
+
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_camel_case_types)]
+            struct #ident;
+
+            let _ = #ident;
+        };
+    }
+}
@@ -0,0 +1,40 @@
+//! Derives of zerocopy: `FromBytes` and `IntoBytes` are unsafe to implement,
+//! only zerocopy's own derives can check the layout they require, so they
+//! aren't implemented but added to the item as `#[derive(::zerocopy::...)]`,
+//! under the `cfg` of their `derive_where` if any.
+//!
+//! zerocopy bounds its impls by the types of the fields and takes no other
+//! bounds. The bounds of `derive_where` are asserted to suffice instead: the
+//! types of the fields have to implement the trait under them.
+
+use quote::quote;
+use syn::{parse_quote, Attribute};
+
+use crate::attr::{DeriveWhere, Options};
+
+/// `#[derive(...)]` of the zerocopy traits of each of `derive_wheres`, if
+/// any.
+pub fn generate_derives(derive_wheres: &[DeriveWhere], options: &Options) -> Vec<Attribute> {
+    derive_wheres
+        .iter()
+        .filter_map(|derive_where| {
+            let paths: Vec<_> = derive_where
+                .traits
+                .iter()
+                .filter(|(trait_, _)| trait_.is_zerocopy())
+                .map(|(trait_, _)| trait_.path(options))
+                .collect();
+
+            if paths.is_empty() {
+                return None;
+            }
+
+            let derive = quote! { derive(#(#paths),*) };
+
+            Some(match &derive_where.cfg {
+                Some(cfg) => parse_quote!(#[cfg_attr(#cfg, #derive)]),
+                None => parse_quote!(#[#derive]),
+            })
+        })
+        .collect()
+}
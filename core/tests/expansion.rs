@@ -0,0 +1,381 @@
+use derive_restricted_core::assert_expansion;
+
+#[test]
+fn test_clone() {
+    assert_expansion! {
+        {
+            #[derive_where(T: Clone; Clone)]
+            struct Test<T>(T);
+        }
+        {
+            struct Test<T>(T);
+
+            impl<T> ::core::clone::Clone for Test<T>
+            where
+                T: Clone
+            {
+                #[inline]
+                fn clone(&self) -> Self {
+                    match self {
+                        Self { 0: __field_0, } => Self { 0: ::core::clone::Clone::clone(__field_0) },
+                    }
+                }
+
+                #[inline]
+                fn clone_from(&mut self, source: &Self) {
+                    match (self, source) {
+                        (Self { 0: __field_0, }, Self { 0: __other_field_0, }) => {
+                            ::core::clone::Clone::clone_from(__field_0, __other_field_0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_forwarding() {
+    assert_expansion! {
+        {
+            #[derive_where(T: Copy + Ord; Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            struct Test<T>(T);
+        }
+        {
+            struct Test<T>(T);
+
+            impl<T> ::core::clone::Clone for Test<T>
+            where
+                T: Copy + Ord
+            {
+                #[inline]
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl<T> ::core::marker::Copy for Test<T>
+            where
+                T: Copy + Ord
+            {}
+
+            impl<T> ::core::cmp::Eq for Test<T>
+            where
+                T: Copy + Ord
+            {}
+
+            const _: () = {
+                impl<T> Test<T>
+                where
+                    T: Copy + Ord
+                {
+                    #[allow(dead_code)]
+                    #[doc(hidden)]
+                    fn __derive_where_assert_eq(&self) {
+                        struct __AssertEq<__T: ::core::cmp::Eq + ?::core::marker::Sized>(
+                            ::core::marker::PhantomData<__T>,
+                        );
+
+                        let _: __AssertEq<T>;
+                    }
+                }
+            };
+
+            impl<T> ::core::cmp::Ord for Test<T>
+            where
+                T: Copy + Ord
+            {
+                #[inline]
+                fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                    match (self, other) {
+                        (Self { 0: __field_0, }, Self { 0: __other_field_0, }) =>
+                            ::core::cmp::Ord::cmp(__field_0, __other_field_0),
+                    }
+                }
+            }
+
+            impl<T> ::core::cmp::PartialEq for Test<T>
+            where
+                T: Copy + Ord
+            {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    match (self, other) {
+                        (Self { 0: __field_0, }, Self { 0: __other_field_0, }) =>
+                            ::core::cmp::PartialEq::eq(__field_0, __other_field_0),
+                    }
+                }
+            }
+
+            impl<T> ::core::cmp::PartialOrd for Test<T>
+            where
+                T: Copy + Ord
+            {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                    ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_doc_bounds() {
+    assert_expansion! {
+        {
+            #[derive_where(doc_bounds)]
+            #[derive_where(T: Copy; Copy)]
+            struct Test<T>(T);
+        }
+        {
+            struct Test<T>(T);
+
+            #[doc = "Derived by `derive_where` with bounds: `T : Copy`."]
+            impl<T> ::core::marker::Copy for Test<T>
+            where
+                T: Copy
+            {}
+        }
+    }
+}
+
+#[test]
+fn test_debug_expansion() {
+    assert_expansion! {
+        {
+            #[derive_where(debug_expansion)]
+            #[derive_where(; Copy)]
+            struct Test;
+        }
+        {
+            struct Test;
+
+            impl ::core::marker::Copy for Test
+            where
+            {}
+
+            const _: () = {
+                #[deprecated(note = "impl :: core :: marker :: Copy for Test where { }")]
+                #[allow(non_camel_case_types)]
+                struct derive_where_expansion;
+
+                let _ = derive_where_expansion;
+            };
+        }
+    }
+}
+
+#[test]
+fn test_unused_bound_skipped() {
+    assert_expansion! {
+        {
+            #[derive_where(T: Hash; Hash)]
+            struct Test<T>(#[derive_where(skip(Hash))] T);
+        }
+        {
+            struct Test<T>(T);
+
+            const _: () = {
+                #[deprecated(note = "`T` isn't used by any field, its bounds can be removed")]
+                #[allow(non_camel_case_types)]
+                struct derive_where_unused_bound;
+
+                let _ = derive_where_unused_bound;
+            };
+
+            impl<T> ::core::hash::Hash for Test<T>
+            where
+                T: Hash
+            {
+                fn hash<__H: ::core::hash::Hasher>(&self, __state: &mut __H) {
+                    match self {
+                        Self { .. } => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_unit_only_partial_eq() {
+    assert_expansion! {
+        {
+            #[derive_where(; PartialEq)]
+            enum Test {
+                A,
+                B,
+            }
+        }
+        {
+            enum Test {
+                A,
+                B,
+            }
+
+            impl ::core::cmp::PartialEq for Test
+            where
+            {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    ::core::mem::discriminant(self) == ::core::mem::discriminant(other)
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn test_structural_match() {
+    assert_expansion! {
+        {
+            #[derive_where(structural_match)]
+            #[derive_where(T: PartialEq; PartialEq)]
+            struct Test<T>(T);
+        }
+        {
+            struct Test<T>(T);
+
+            impl<T> ::core::cmp::PartialEq for Test<T>
+            where
+                T: PartialEq
+            {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    match (self, other) {
+                        (Self { 0: __field_0, }, Self { 0: __other_field_0, }) =>
+                            ::core::cmp::PartialEq::eq(__field_0, __other_field_0),
+                    }
+                }
+            }
+
+            impl<T> ::core::marker::StructuralPartialEq for Test<T> {}
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn test_crate_path() {
+    assert_expansion! {
+        {
+            #[derive_where(zeroize_crate = framework::zeroize)]
+            #[derive_where(; Zeroize)]
+            struct Test(u8);
+        }
+        {
+            struct Test(u8);
+
+            impl framework::zeroize::Zeroize for Test
+            where
+            {
+                fn zeroize(&mut self) {
+                    match self {
+                        Self { 0: __field_0, } => {
+                            framework::zeroize::Zeroize::zeroize(__field_0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "unexpected expansion")]
+fn test_mismatch() {
+    assert_expansion! {
+        {
+            #[derive_where(; Copy)]
+            struct Test;
+        }
+        {
+            struct Test;
+        }
+    }
+}
+
+#[test]
+fn test_trait_order() {
+    use derive_restricted_core::{__quote::quote, derive_where_items};
+
+    let ordered = derive_where_items(quote! {
+        #[derive_where(T: Clone + std::fmt::Debug; Clone, Debug, PartialEq)]
+        enum Test<T> {
+            A(T),
+            B,
+        }
+    });
+    let reversed = derive_where_items(quote! {
+        #[derive_where(T: Clone + std::fmt::Debug; PartialEq, Debug, Clone)]
+        enum Test<T> {
+            A(T),
+            B,
+        }
+    });
+
+    assert_eq!(ordered.to_string(), reversed.to_string());
+}
+
+#[test]
+fn test_item_tokens() {
+    use derive_restricted_core::{__quote::quote, derive_where};
+
+    let expanded = derive_where(
+        quote! { ; Debug },
+        quote! {
+            #[derive_where(debug_sorted)]
+            #[allow(dead_code)]
+            struct Test {
+                #[derive_where(skip(Debug))]
+                #[doc = "Hidden."]
+                a: u8,
+                b: [u8; { #[allow(unused)] 1 }],
+            }
+        },
+    );
+    let item = quote! {
+        #[allow(dead_code)]
+        struct Test {
+            #[doc = "Hidden."]
+            a: u8,
+            b: [u8; { #[allow(unused)] 1 }],
+        }
+    };
+
+    assert!(expanded.to_string().starts_with(&item.to_string()));
+}
+
+#[test]
+fn test_compat_strip() {
+    use derive_restricted_core::{__quote::quote, derive_where, derive_where_items};
+
+    let item = quote! {
+        #[derivative(Debug)]
+        enum Test {
+            #[educe(Debug(named_field = false))]
+            A(#[derivative(Debug = "ignore")] u8),
+        }
+    };
+    let stripped = quote! {
+        #[derivative(Debug)]
+        enum Test {
+            #[educe(Debug(named_field = false))]
+            A(u8),
+        }
+    };
+
+    // Re-emitting the original tokens.
+    let expanded = derive_where(quote! { ; Debug }, quote! { #[derive_where(compat)] #item });
+    assert!(expanded.to_string().starts_with(&stripped.to_string()));
+
+    // Re-emitting the parsed item.
+    let expanded = derive_where_items(quote! {
+        #[derive_where(; Debug)]
+        #[derive_where(compat)]
+        #item
+    });
+    assert!(expanded.to_string().starts_with(&stripped.to_string()));
+}
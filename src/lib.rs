@@ -1,156 +1,42 @@
-use proc_macro::{self, TokenStream, TokenTree};
-use quote::__private::TokenStream as TS;
-use quote::{format_ident, quote};
-use syn::{parse_macro_input, DataEnum, DataStruct, DeriveInput};
-
-enum Traits {
-    Clone,
+#[proc_macro_attribute]
+pub fn derive_where(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    derive_restricted_core::derive_where(attr.into(), item.into()).into()
 }
 
-impl TryFrom<TokenStream> for Traits {
-    type Error = String;
-
-    fn try_from(value: TokenStream) -> Result<Self, Self::Error> {
-        use Traits::*;
-        if let Some(TokenTree::Ident(i)) = value.into_iter().next() {
-            Ok(match i.to_string().as_str() {
-                "Clone" => Clone,
-                _ => return Err(format!("")),
-            })
-        } else {
-            Err(format!(""))
-        }
-    }
+/// Function-like alternative to the attribute, for contexts where attributes
+/// are awkward: takes any number of items with `#[derive_where(...)]`
+/// attributes and generates the same output as the attribute would.
+#[proc_macro]
+pub fn derive_where_items(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_restricted_core::derive_where_items(input.into()).into()
 }
 
-impl Traits {
-    fn ident(&self) -> syn::Ident {
-        use Traits::*;
-
-        format_ident!(
-            "{}",
-            match self {
-                Clone => "Clone",
-            }
-        )
-    }
-
-    fn body_struct(&self, data: DataStruct) -> TS {
-        use Traits::*;
-        match self {
-            Clone => {
-                let body = match data.fields {
-                    syn::Fields::Named(f) => {
-                        let fields: Vec<_> = f
-                            .named
-                            .into_iter()
-                            .map(|f| f.ident.expect("Every field should have a name"))
-                            .collect();
-                        quote! {
-                            match self {
-                                Self{#(#fields),*} => Self{#(#fields: #fields.clone()),*}
-                            }
-                        }
-                    }
-                    syn::Fields::Unnamed(f) => {
-                        let fields: Vec<_> = (0..f.unnamed.len())
-                            .into_iter()
-                            .map(|n| format_ident!("field{}", n))
-                            .collect();
-                        quote! {
-                            match self {
-                                Self(#(#fields),*) => Self(#(#fields.clone()),*)
-                            }
-                        }
-                    }
-                    syn::Fields::Unit => quote! {Self},
-                };
-                quote! {
-                    fn clone(&self) -> Self {
-                        #body
-                    }
-                }
-            }
-        }
-    }
-    fn body_enum(&self, data: DataEnum) -> TS {
-        use Traits::*;
-        match self {
-            Clone => {
-                let body: Vec<_> = data
-                    .variants
-                    .iter()
-                    .map(|v| {
-                        let ident = &v.ident;
-                        match &v.fields {
-                            syn::Fields::Named(f) => {
-                                let fields: Vec<_> = f
-                                    .named.clone()
-                                    .into_iter()
-                                    .map(|f| f.ident.expect("Every field should have a name"))
-                                    .collect();
-                                quote! {
-                                    Self::#ident{#(#fields),*} => Self::#ident{#(#fields: #fields.clone()),*}
-                                }
-                            }
-                            syn::Fields::Unnamed(f) => {
-                                let fields: Vec<_> = (0..f.unnamed.len())
-                                    .into_iter()
-                                    .map(|n| format_ident!("field{}", n))
-                                    .collect();
-                                quote! {
-                                    Self::#ident(#(#fields),*) => Self::#ident(#(#fields.clone()),*)
-                                }
-                            }
-                            syn::Fields::Unit => quote! {Self::#ident => Self::#ident},
-                        }
-                    })
-                    .collect();
-                quote! {
-                    fn clone(&self) -> Self {
-                        match self {
-                            #(#body),*
-                        }
-                    }
-                }
-            }
-        }
-    }
+/// Defines a named list of traits, e.g.
+/// `derive_where_alias!(Standard = Clone, Debug, Eq, PartialEq, Hash)`, which
+/// `derive_where` attributes can list as `Standard!` among their traits.
+#[proc_macro]
+pub fn derive_where_alias(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_restricted_core::derive_where_alias(input.into()).into()
 }
 
+/// Applies the same configuration to every struct and enum of an inline
+/// module, as if each had `#[derive_where(...)]` with the given arguments, e.g.
+/// `#[derive_where_all(T: Clone; Clone, Debug)] mod messages { ... }`.
 #[proc_macro_attribute]
-pub fn derive_where(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let item2: TS = item.clone().into();
-    let mut iter = attr.into_iter();
-    let w: TokenStream = iter
-        .by_ref()
-        .take_while(|a| !matches!(a, proc_macro::TokenTree::Punct(p) if p.as_char() == ';'))
-        .collect();
-    let w: TS = w.into();
-    let t: TokenStream = iter.collect();
-    let t: Traits = t.try_into().unwrap();
-
-    let DeriveInput {
-        ident,
-        generics,
-        data,
-        ..
-    } = parse_macro_input!(item);
-
-    let body = match data {
-        syn::Data::Struct(s) => t.body_struct(s),
-        syn::Data::Enum(e) => t.body_enum(e),
-        syn::Data::Union(_) => todo!("Unions are not supported"),
-    };
+pub fn derive_where_all(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    derive_restricted_core::derive_where_all(attr.into(), item.into()).into()
+}
 
-    let t = t.ident();
-    let output = quote! {
-        #item2
-        impl #generics #t for #ident #generics
-            where #w
-        {
-            #body
-        }
-    };
-    output.into()
+/// Checks that the listed traits agree with `PartialEq` and each other for all
+/// pairs of the given values, e.g. `assert_consistent!(Ord, Hash; a, b, c)`.
+#[cfg(feature = "test-helpers")]
+#[proc_macro]
+pub fn assert_consistent(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_restricted_core::assert_consistent(input.into()).into()
 }
@@ -0,0 +1,51 @@
+use std::{collections::HashSet, fmt::Debug, hash::Hash, marker::PhantomData};
+
+use derive_restricted::{derive_where, derive_where_alias};
+
+derive_where_alias!(Standard = Clone, Debug, Eq, PartialEq, Hash);
+
+mod aliases {
+    use derive_restricted::derive_where_alias;
+
+    derive_where_alias!(pub(crate) Ordered = PartialOrd, Ord);
+}
+
+use aliases::Ordered;
+
+struct NotStandard;
+
+#[test]
+fn test_alias() {
+    #[derive_where(T: Clone + Debug + Eq + Hash; Standard!)]
+    struct Test<T>(T, PhantomData<NotStandard>);
+
+    let test = Test(1, PhantomData);
+    let mut set = HashSet::new();
+    set.insert(test.clone());
+
+    assert!(set.contains(&test));
+    assert_eq!(
+        format!("{:?}", test),
+        "Test(1, PhantomData<alias::NotStandard>)"
+    );
+}
+
+#[test]
+fn test_combined() {
+    derive_where_alias!(Printable = Debug);
+
+    #[derive_where(; Copy, Clone, Ordered!, PartialEq, Eq)]
+    #[derive_where(variant_names)]
+    #[derive_where(; Printable!)]
+    enum Test<T> {
+        A(PhantomData<T>),
+        B,
+    }
+
+    let a = Test::<NotStandard>::A(PhantomData);
+    let b = Test::B;
+
+    assert!(a < b);
+    assert_eq!(a.variant_name(), "A");
+    assert_eq!(format!("{:?}", b.clone()), "B");
+}
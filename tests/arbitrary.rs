@@ -0,0 +1,80 @@
+use std::marker::PhantomData;
+
+use arbitrary::{Arbitrary, Unstructured};
+use derive_restricted::derive_where;
+
+struct NotArbitrary;
+
+#[test]
+fn test_struct() {
+    #[derive_where(; Arbitrary, Debug, PartialEq)]
+    struct Test<T> {
+        a: u8,
+        b: bool,
+        #[derive_where(skip(Arbitrary))]
+        cache: Option<u32>,
+        marker: PhantomData<T>,
+    }
+
+    let mut u = Unstructured::new(&[1, 1, 0xff]);
+
+    assert_eq!(
+        Test::<NotArbitrary>::arbitrary(&mut u).unwrap(),
+        Test {
+            a: 1,
+            b: true,
+            cache: None,
+            marker: PhantomData,
+        }
+    );
+    assert_eq!(
+        <Test<NotArbitrary> as Arbitrary>::size_hint(0),
+        (2, Some(2))
+    );
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(; Arbitrary, Debug, PartialEq)]
+    enum Test {
+        A,
+        B(u8),
+    }
+
+    assert_eq!(
+        Test::arbitrary(&mut Unstructured::new(&[0, 0, 0, 0])).unwrap(),
+        Test::A
+    );
+    assert_eq!(
+        Test::arbitrary(&mut Unstructured::new(&[0xff, 0xff, 0xff, 0xff, 7])).unwrap(),
+        Test::B(7)
+    );
+    assert_eq!(Test::size_hint(0), (4, Some(5)));
+}
+
+#[test]
+fn test_recursion() {
+    #[derive_where(; Arbitrary)]
+    enum Tree<T> {
+        Node(Box<Tree<T>>, Box<Self>),
+        Leaf(T),
+    }
+
+    fn depth<T>(tree: &Tree<T>) -> usize {
+        match tree {
+            Tree::Node(left, right) => 1 + depth(left).max(depth(right)),
+            Tree::Leaf(..) => 0,
+        }
+    }
+
+    // Always choosing `Node` is limited in depth.
+    let data = [0; 4096];
+    let tree = Tree::<u8>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+    assert!(depth(&tree) <= 20);
+
+    // Running out of data ends recursion.
+    let tree = Tree::<u8>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+    assert_eq!(depth(&tree), 0);
+
+    assert_eq!(Tree::<u8>::size_hint(0), (0, None));
+}
@@ -0,0 +1,60 @@
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
+
+use derive_restricted::derive_where;
+
+#[test]
+fn test_option() {
+    #[derive_where(assert(Send, Sync))]
+    struct Test<T: Send + Sync>(Arc<T>);
+
+    let test = Test(Arc::new(1));
+    assert_eq!(*test.0, 1);
+}
+
+#[test]
+fn test_bounds() {
+    #[derive_where(T: Clone; Clone)]
+    #[derive_where(T: Send + Sync + Debug; assert(Send, Sync), Debug)]
+    struct Test<T>(Arc<T>, PhantomData<fn() -> T>);
+
+    let test = Test(Arc::new(1), PhantomData);
+    assert_eq!(
+        format!("{:?}", test.clone()),
+        "Test(1, PhantomData<fn() -> i32>)"
+    );
+}
+
+#[test]
+fn test_assert_only() {
+    #[derive_where(T: Clone; Clone)]
+    #[derive_where(T: Debug; Debug)]
+    #[derive_where(assert_only)]
+    struct Test<T> {
+        a: Arc<T>,
+        b: PhantomData<T>,
+    }
+
+    impl<T: Clone> Clone for Test<T> {
+        fn clone(&self) -> Self {
+            Test {
+                a: Arc::new(T::clone(&self.a)),
+                b: PhantomData,
+            }
+        }
+    }
+
+    impl<T: Debug> Debug for Test<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Test({:?})", self.a)
+        }
+    }
+
+    let test = Test {
+        a: Arc::new(1),
+        b: PhantomData,
+    };
+    let clone = test.clone();
+
+    assert!(!Arc::ptr_eq(&test.a, &clone.a));
+    assert_eq!(format!("{:?}", clone), "Test(1)");
+}
@@ -0,0 +1,57 @@
+use std::marker::PhantomData;
+
+use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect, Struct, TypePath, Typed};
+use derive_restricted::derive_where;
+
+#[derive(Default)]
+struct NotReflect;
+
+#[derive(TypePath)]
+struct OnlyTypePath;
+
+#[test]
+fn test_bounds() {
+    #[derive_where(T: FromReflect + TypePath + Typed + GetTypeRegistration; Reflect, FromReflect)]
+    #[derive(Reflect)]
+    #[allow(dead_code)]
+    struct Test<T, U> {
+        a: Vec<T>,
+        #[derive_where(skip(Reflect))]
+        cache: NotReflect,
+        marker: PhantomData<U>,
+    }
+
+    let test = Test::<u8, OnlyTypePath> {
+        a: vec![1],
+        cache: NotReflect,
+        marker: PhantomData,
+    };
+
+    assert_eq!(test.field_len(), 1);
+    assert_eq!(
+        test.field("a").unwrap().try_downcast_ref(),
+        Some(&vec![1_u8])
+    );
+    assert!(test.field("cache").is_none());
+    assert!(test.field("marker").is_none());
+
+    let test = Test::<u8, OnlyTypePath>::from_reflect(&test).unwrap();
+    assert_eq!(test.a, [1]);
+}
+
+#[test]
+fn test_no_from_reflect() {
+    #[derive_where(; Reflect)]
+    #[derive(Reflect)]
+    struct Test<T> {
+        a: u8,
+        marker: PhantomData<T>,
+    }
+
+    let test = Test::<OnlyTypePath> {
+        a: 1,
+        marker: PhantomData,
+    };
+
+    assert_eq!(test.field("a").unwrap().try_downcast_ref(), Some(&1_u8));
+}
@@ -0,0 +1,29 @@
+use std::fmt;
+
+use derive_restricted::derive_where;
+
+#[test]
+fn test_enabled() {
+    #[derive_where(cfg(test), T: Clone + fmt::Debug; Clone, Debug)]
+    struct Test<T>(T);
+
+    let test = Test(1);
+
+    assert_eq!(format!("{:?}", test.clone()), "Test(1)");
+}
+
+#[test]
+fn test_disabled() {
+    #[derive_where(cfg(any()); Debug, PartialEq)]
+    #[derive_where(; Clone)]
+    struct Test(u8);
+
+    // Would conflict with the derived impl if it wasn't disabled.
+    impl fmt::Debug for Test {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("manual")
+        }
+    }
+
+    assert_eq!(format!("{:?}", Test(1).clone()), "manual");
+}
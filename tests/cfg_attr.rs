@@ -0,0 +1,32 @@
+use derive_restricted::derive_where;
+
+#[test]
+fn test_after() {
+    #[derive_where(T: Clone; Clone)]
+    #[cfg_attr(all(), derive_where(T: PartialEq; PartialEq))]
+    #[cfg_attr(any(), derive_where(T: Clone; Clone))]
+    struct TestAfter<T>(T);
+
+    let test = TestAfter(1);
+    assert!(test.clone() == test);
+}
+
+#[test]
+fn test_before() {
+    #[cfg_attr(all(), derive_where(T: PartialEq; PartialEq))]
+    #[derive_where(T: Clone; Clone)]
+    struct TestBefore<T>(T);
+
+    let test = TestBefore(1);
+    assert!(test.clone() == test);
+}
+
+#[test]
+fn test_only() {
+    #[cfg_attr(all(), derive_where(T: PartialEq; PartialEq), derive_where(T: Clone; Clone))]
+    #[cfg_attr(any(), derive_where(T: Clone; Clone))]
+    struct TestOnly<T>(T);
+
+    let test = TestOnly(1);
+    assert!(test.clone() == test);
+}
@@ -0,0 +1,21 @@
+use clap::ValueEnum;
+use derive_restricted::derive_where;
+
+#[test]
+fn test_value_enum() {
+    #[derive_where(; Clone, Debug, Display, FromStr, PartialEq, ValueEnum)]
+    enum Test {
+        A,
+        #[derive_where(display = "bee")]
+        B,
+    }
+
+    assert_eq!(Test::value_variants(), [Test::A, Test::B]);
+    assert_eq!(
+        Test::B.to_possible_value().unwrap().get_name(),
+        Test::B.to_string()
+    );
+    assert_eq!(Test::from_str("bee", false).unwrap(), Test::B);
+    assert_eq!(Test::from_str("a", true).unwrap(), Test::A);
+    assert!(Test::from_str("a", false).is_err());
+}
@@ -67,3 +67,70 @@ fn test_enum() {
     dbg!(test);
     dbg!(cloned);
 }
+
+#[test]
+fn test_path() {
+    #[allow(unused)]
+    trait Clone {}
+
+    #[derive_where(T: ::core::clone::Clone; core::clone::Clone)]
+    #[derive(Debug)]
+    struct TestCore<T>(T);
+
+    #[derive_where(T: ::std::clone::Clone; ::std::clone::Clone)]
+    #[derive(Debug)]
+    struct TestStd<T>(T);
+
+    let test = TestCore(1);
+    let cloned = ::core::clone::Clone::clone(&test);
+    dbg!(test);
+    dbg!(cloned);
+
+    let test = TestStd(1);
+    let cloned = ::core::clone::Clone::clone(&test);
+    dbg!(test);
+    dbg!(cloned);
+}
+
+#[test]
+fn test_trailing_separators() {
+    #[derive_where(T: Clone, S: Clone,; Clone,;)]
+    #[derive(Debug)]
+    struct TestTrailing<T, S>(T, S);
+
+    let test = TestTrailing(1, String::from("Test"));
+    let cloned = test.clone();
+
+    dbg!(test);
+    dbg!(cloned);
+}
+
+#[test]
+fn test_clone_from() {
+    #[derive_where(T: Clone; Clone)]
+    enum Test<T> {
+        A(Vec<T>),
+        B { b: String },
+    }
+
+    let mut test = Test::A(Vec::with_capacity(16));
+    test.clone_from(&Test::A(vec![1, 2]));
+
+    match &test {
+        Test::A(a) => {
+            assert_eq!(a, &[1, 2]);
+            // The existing allocation is reused.
+            assert!(a.capacity() >= 16);
+        }
+        Test::B { .. } => unreachable!(),
+    }
+
+    test.clone_from(&Test::B {
+        b: String::from("b"),
+    });
+
+    match test {
+        Test::B { b } => assert_eq!(b, "b"),
+        Test::A(_) => unreachable!(),
+    }
+}
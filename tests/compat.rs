@@ -0,0 +1,71 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+
+#[test]
+fn test_derivative() {
+    #[derive_where(T: std::fmt::Debug + PartialEq; Debug, PartialEq)]
+    #[derive_where(compat)]
+    struct Test<T> {
+        a: T,
+        #[derivative(Debug = "ignore", PartialEq = "ignore")]
+        cache: usize,
+        #[derivative(Debug(bound = ""))]
+        marker: PhantomData<T>,
+    }
+
+    let test = Test {
+        a: 1,
+        cache: 2,
+        marker: PhantomData,
+    };
+
+    assert_eq!(
+        format!("{:?}", test),
+        "Test { a: 1, marker: PhantomData<i32> }"
+    );
+    assert_eq!(test.cache, 2);
+    assert!(
+        test == Test {
+            a: 1,
+            cache: 3,
+            marker: PhantomData,
+        }
+    );
+}
+
+#[test]
+fn test_educe() {
+    #[derive_where(; Debug)]
+    #[derive_where(compat)]
+    enum Test {
+        A(u8, #[educe(Debug(ignore))] &'static str),
+    }
+
+    let test = Test::A(1, "secret");
+
+    assert_eq!(format!("{:?}", test), "A(1)");
+
+    let Test::A(_, secret) = test;
+    assert_eq!(secret, "secret");
+}
+
+#[test]
+fn test_other_options() {
+    use derivative::Derivative;
+
+    #[derive_where(; Debug)]
+    #[derive_where(compat)]
+    #[derive(Derivative)]
+    #[derivative(Default)]
+    struct Test {
+        #[derivative(Debug = "ignore", Default(value = "1"))]
+        a: u8,
+        b: u8,
+    }
+
+    let test = Test::default();
+
+    assert_eq!(format!("{:?}", test), "Test { b: 0 }");
+    assert_eq!(test.a, 1);
+}
@@ -0,0 +1,31 @@
+use derive_restricted::{assert_consistent, derive_where};
+
+#[test]
+fn test_consistent() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Test {
+        a: u8,
+        b: &'static str,
+    }
+
+    assert_consistent!(
+        PartialEq, Eq, PartialOrd, Ord, Hash;
+        Test { a: 1, b: "a" },
+        Test { a: 1, b: "b" },
+        Test { a: 2, b: "a" },
+        Test { a: 1, b: "a" },
+    );
+}
+
+#[test]
+#[should_panic(expected = "`Hash` disagrees with `PartialEq`")]
+fn test_inconsistent() {
+    #[derive_where(; PartialEq, Hash)]
+    struct Test {
+        #[derive_where(delegate(PartialEq))]
+        a: u8,
+        b: u8,
+    }
+
+    assert_consistent!(PartialEq, Hash; Test { a: 1, b: 1 }, Test { a: 1, b: 2 });
+}
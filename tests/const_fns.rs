@@ -0,0 +1,58 @@
+use std::cmp::Ordering;
+
+use derive_restricted::derive_where;
+
+#[test]
+fn test_struct() {
+    #[derive_where(; Debug, PartialEq, Eq, PartialOrd, Ord)]
+    #[derive_where(const_fns)]
+    struct Inner(u8, #[derive_where(skip(Ord, PartialEq, PartialOrd))] bool);
+
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord)]
+    #[derive_where(const_fns)]
+    struct Test {
+        a: Inner,
+        #[derive_where(ord(0))]
+        b: char,
+    }
+
+    const A: Test = Test {
+        a: Inner(2, true),
+        b: 'a',
+    };
+    const B: Test = Test {
+        a: Inner(2, false),
+        b: 'a',
+    };
+    const C: Test = Test {
+        a: Inner(1, true),
+        b: 'b',
+    };
+    const RESULTS: (bool, Ordering) = (A.const_eq(&B), A.const_cmp(&C));
+
+    assert_eq!(RESULTS, (true, Ordering::Less));
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord)]
+    #[derive_where(const_fns)]
+    #[repr(u8)]
+    enum Test {
+        A(i32),
+        B = 5,
+    }
+
+    const EQ: [bool; 2] = [
+        Test::A(1).const_eq(&Test::A(1)),
+        Test::A(1).const_eq(&Test::B),
+    ];
+    const CMP: Ordering = Test::B.const_cmp(&Test::A(-1));
+
+    assert_eq!(EQ, [true, false]);
+    assert_eq!(CMP, Ordering::Greater);
+    assert_eq!(
+        Test::A(1).const_cmp(&Test::A(2)),
+        Test::A(1).cmp(&Test::A(2))
+    );
+}
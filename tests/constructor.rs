@@ -0,0 +1,33 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+
+#[test]
+fn test_named() {
+    #[derive_where(T: Clone; Clone)]
+    #[derive_where(constructor)]
+    struct Test<T, U> {
+        a: T,
+        #[derive_where(default)]
+        b: Vec<T>,
+        r#type: PhantomData<U>,
+    }
+
+    let test = Test::<u8, ()>::new(1, PhantomData);
+
+    assert_eq!(test.a, 1);
+    assert!(test.b.is_empty());
+}
+
+#[test]
+fn test_tuple() {
+    struct NotDefault;
+
+    #[derive_where(T: Clone; Clone)]
+    #[derive_where(constructor)]
+    struct Test<T>(T, #[derive_where(default)] Option<T>);
+
+    let test = Test::new(NotDefault);
+
+    assert!(matches!(test, Test(NotDefault, None)));
+}
@@ -0,0 +1,58 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+
+trait Weight {
+    fn weight(&self) -> usize;
+}
+
+impl Weight for u8 {
+    fn weight(&self) -> usize {
+        usize::from(*self)
+    }
+}
+
+impl<T> Weight for PhantomData<T> {
+    fn weight(&self) -> usize {
+        0
+    }
+}
+
+macro_rules! weight {
+    ($($path:path { $(($member:tt, $binding:ident, $other:ident, $ty:ty))* })*) => {
+        fn weight(&self) -> usize {
+            match self {
+                $($path { $($member: $binding),* } => 0 $(+ Weight::weight($binding))*,)*
+            }
+        }
+    };
+}
+
+struct NotWeight;
+
+#[test]
+fn test_struct() {
+    #[derive_where(T: Weight; custom(Weight, weight))]
+    struct TestStruct<T> {
+        a: T,
+        b: T,
+    }
+
+    assert_eq!(TestStruct { a: 1_u8, b: 2 }.weight(), 3);
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(T: Weight; custom(Weight, weight))]
+    #[derive_where(T: Clone; Clone)]
+    enum TestEnum<T, U> {
+        Tuple(T, T),
+        Struct { field: T },
+        Unit,
+        Phantom(PhantomData<U>),
+    }
+
+    assert_eq!(TestEnum::<u8, NotWeight>::Tuple(1, 2).weight(), 3);
+    assert_eq!(TestEnum::<u8, NotWeight>::Struct { field: 4 }.weight(), 4);
+    assert_eq!(TestEnum::<u8, NotWeight>::Unit.weight(), 0);
+}
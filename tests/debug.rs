@@ -0,0 +1,221 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+
+struct NotDebug;
+
+#[test]
+fn test_struct() {
+    #[derive_where(T: std::fmt::Debug; Debug)]
+    struct Test<T, U> {
+        a: T,
+        r#type: PhantomData<U>,
+    }
+
+    let test = Test::<u8, NotDebug> {
+        a: 1,
+        r#type: PhantomData,
+    };
+
+    assert_eq!(
+        format!("{:?}", test),
+        "Test { a: 1, type: PhantomData<debug::NotDebug> }"
+    );
+    assert_eq!(
+        format!("{:#?}", test),
+        "Test {\n    a: 1,\n    type: PhantomData<debug::NotDebug>,\n}"
+    );
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(T: std::fmt::Debug; Debug)]
+    enum Test<T> {
+        A,
+        B(T, u8),
+        C { c: T },
+    }
+
+    assert_eq!(format!("{:?}", Test::<u8>::A), "A");
+    assert_eq!(format!("{:?}", Test::B("b", 1)), "B(\"b\", 1)");
+    assert_eq!(format!("{:?}", Test::C { c: 1 }), "C { c: 1 }");
+}
+
+#[test]
+fn test_template() {
+    #[derive_where(; Debug)]
+    #[derive_where(debug_template(
+        no_name,
+        open = "[",
+        close = "]",
+        separator = "=",
+        delimiter = " "
+    ))]
+    struct Named {
+        a: u8,
+        b: &'static str,
+    }
+
+    #[derive_where(; Debug)]
+    #[derive_where(debug_template(delimiter = "; "))]
+    enum Test {
+        A,
+        B(u8, u8),
+    }
+
+    assert_eq!(format!("{:?}", Named { a: 1, b: "b" }), "[a=1 b=\"b\"]");
+    assert_eq!(format!("{:?}", Test::A), "A");
+    assert_eq!(format!("{:?}", Test::B(1, 2)), "B(1; 2)");
+}
+
+#[test]
+fn test_truncate() {
+    #[derive_where(; Debug)]
+    struct Test<'a> {
+        #[derive_where(debug(truncate = 2))]
+        array: [u8; 3],
+        #[derive_where(debug(truncate = 2))]
+        vec: Vec<&'a str>,
+        #[derive_where(debug(truncate = 2))]
+        slice: &'a [u8],
+    }
+
+    let test = Test {
+        array: [1, 2, 3],
+        vec: vec!["a"],
+        slice: &[0; 1024],
+    };
+
+    assert_eq!(
+        format!("{:?}", test),
+        "Test { array: [1, 2, \u{2026} (3 total)], vec: [\"a\"], slice: [0, 0, \u{2026} (1024 total)] }"
+    );
+}
+
+#[test]
+fn test_str() {
+    #[derive_where(; Debug)]
+    struct Test<'a> {
+        #[derive_where(debug(max_chars = 4))]
+        name: String,
+        #[derive_where(debug(max_chars = 8, escape))]
+        input: &'a str,
+        #[derive_where(debug(escape))]
+        raw: &'a str,
+    }
+
+    let test = Test {
+        name: String::from("r\u{e9}sum\u{e9}s"),
+        input: "ok",
+        raw: "\u{1b}[2J\u{202e}",
+    };
+
+    assert_eq!(
+        format!("{:?}", test),
+        "Test { name: \"r\u{e9}su\"\u{2026} (7 total), input: \"ok\", raw: \"\\u{1b}[2J\\u{202e}\" }"
+    );
+}
+
+#[test]
+fn test_hex() {
+    #[derive_where(; Debug)]
+    struct Test<'a>(
+        #[derive_where(debug(hex))] [u8; 3],
+        #[derive_where(debug(hex))] Vec<u8>,
+        #[derive_where(debug(hex))] &'a [u8],
+    );
+
+    let test = Test([0, 0x1b, 0xff], Vec::new(), &[0xab; 17]);
+
+    assert_eq!(
+        format!("{:?}", test),
+        format!("Test(001bff, , {})", "ab".repeat(17))
+    );
+    assert_eq!(
+        format!("{:#?}", test),
+        "Test(\n    00000000: 00 1b ff,\n    ,\n    00000000: ab ab ab ab ab ab ab ab ab ab ab ab ab ab ab ab\n    00000010: ab,\n)"
+    );
+}
+
+#[test]
+fn test_discriminant() {
+    #[derive_where(; Debug)]
+    #[derive_where(debug_discriminant)]
+    #[repr(u8)]
+    enum Test {
+        A,
+        B(u8) = 3,
+        C(u8, u8),
+        D { d: u8 } = 255,
+    }
+
+    assert_eq!(format!("{:?}", Test::A), "A (= 0)");
+    assert_eq!(format!("{:?}", Test::B(1)), "B (= 3)(1)");
+    assert_eq!(format!("{:#?}", Test::B(1)), "B (= 3)(\n    1,\n)");
+    assert_eq!(format!("{:?}", Test::C(1, 2)), "C (= 4)(1, 2)");
+    assert_eq!(format!("{:?}", Test::D { d: 1 }), "D (= 255) { d: 1 }");
+}
+
+#[test]
+fn test_discriminant_template() {
+    #[derive_where(; Debug)]
+    #[derive_where(debug_discriminant, debug_template(no_name))]
+    #[repr(i8)]
+    enum Test {
+        A = -1,
+        B(u8),
+    }
+
+    assert_eq!(format!("{:?}", Test::A), "(= -1)");
+    assert_eq!(format!("{:?}", Test::B(1)), "(= 0)(1)");
+}
+
+#[test]
+fn test_sorted() {
+    #[derive_where(; Debug)]
+    #[derive_where(debug_sorted)]
+    enum Test {
+        A { zeta: u8, alpha: u8, r#mid: u8 },
+        B(u8, u8),
+    }
+
+    assert_eq!(
+        format!(
+            "{:?}",
+            Test::A {
+                zeta: 1,
+                alpha: 2,
+                mid: 3
+            }
+        ),
+        "A { alpha: 2, mid: 3, zeta: 1 }"
+    );
+    assert_eq!(format!("{:?}", Test::B(1, 2)), "B(1, 2)");
+}
+
+#[test]
+fn test_type_names() {
+    #[derive_where(T: std::fmt::Debug; Debug)]
+    #[derive_where(debug_type_names)]
+    enum Test<T, U> {
+        A { a: T },
+        B(T, PhantomData<U>),
+        C(T),
+        D,
+    }
+
+    assert_eq!(
+        format!("{:?}", Test::<u8, bool>::A { a: 1 }),
+        "A<u8, bool> { a: 1 }"
+    );
+    assert_eq!(
+        format!("{:?}", Test::<u8, bool>::B(1, PhantomData)),
+        "B<u8, bool>(1, PhantomData<bool>)"
+    );
+    assert_eq!(format!("{:?}", Test::<u8, bool>::C(1)), "C<u8, bool>(1)");
+    assert_eq!(
+        format!("{:#?}", Test::<u8, bool>::C(1)),
+        "C<u8, bool>(\n    1,\n)"
+    );
+    assert_eq!(format!("{:?}", Test::<u8, bool>::D), "D<u8, bool>");
+}
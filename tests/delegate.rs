@@ -0,0 +1,101 @@
+mod util;
+
+use std::{cmp::Ordering, marker::PhantomData};
+
+use derive_restricted::derive_where;
+use util::hash;
+
+#[test]
+fn test_struct() {
+    #[derive_where(; Hash, PartialEq, Eq, PartialOrd, Ord)]
+    struct Id<T> {
+        #[derive_where(delegate(Hash, PartialEq, Eq, PartialOrd, Ord))]
+        id: u64,
+        name: &'static str,
+        marker: PhantomData<T>,
+    }
+
+    let a = Id::<f32> {
+        id: 1,
+        name: "a",
+        marker: PhantomData,
+    };
+    let b = Id::<f32> {
+        id: 1,
+        name: "b",
+        marker: PhantomData,
+    };
+    let c = Id::<f32> {
+        id: 2,
+        name: "a",
+        marker: PhantomData,
+    };
+
+    assert!(a == b);
+    assert!(a != c);
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert_eq!(a.cmp(&c), Ordering::Less);
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!((b.name, c.name), ("b", "a"));
+}
+
+#[test]
+fn test_partial() {
+    #[derive_where(; PartialEq, PartialOrd)]
+    struct Test(#[derive_where(delegate(PartialEq))] u8, u8);
+
+    assert!(Test(1, 1) == Test(1, 2));
+    assert_eq!(Test(1, 1).partial_cmp(&Test(1, 2)), Some(Ordering::Less));
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(; PartialEq, Hash)]
+    enum Test {
+        A {
+            #[derive_where(delegate(PartialEq, Hash))]
+            id: u8,
+            name: &'static str,
+        },
+        B(u8),
+    }
+
+    let a = Test::A { id: 1, name: "a" };
+    let b = Test::A { id: 1, name: "b" };
+
+    assert!(a == b);
+    assert!(a != Test::B(1));
+    assert_eq!(hash(&a), hash(&b));
+
+    if let Test::A { name, .. } = b {
+        assert_eq!(name, "b");
+    }
+}
+
+#[test]
+fn test_transparent() {
+    #[derive_where(; Hash, PartialEq, Eq, PartialOrd, Ord)]
+    #[repr(transparent)]
+    struct Id<T>(u64, PhantomData<T>);
+
+    #[derive_where(; Hash, PartialEq, Eq, PartialOrd, Ord)]
+    struct Name {
+        #[derive_where(transparent)]
+        name: &'static str,
+        cache: usize,
+    }
+
+    assert!(Id::<f32>(1, PhantomData) == Id(1, PhantomData));
+    assert_eq!(
+        Id::<f32>(1, PhantomData).cmp(&Id(2, PhantomData)),
+        Ordering::Less
+    );
+    assert_eq!(hash(&Id::<f32>(1, PhantomData)), hash(&1_u64));
+
+    let name = |name, cache| Name { name, cache };
+
+    assert!(name("a", 1) == name("a", 2));
+    assert_eq!(name("a", 2).cmp(&name("b", 1)), Ordering::Less);
+    assert_eq!(hash(&name("a", 1)), hash(&"a"));
+    assert_eq!(name("a", 1).cache, 1);
+}
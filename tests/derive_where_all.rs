@@ -0,0 +1,38 @@
+use derive_restricted::derive_where_all;
+
+#[derive_where_all(T: Clone; Clone)]
+mod messages {
+    use std::marker::PhantomData;
+
+    pub struct NotClone;
+
+    pub struct Request<T, U> {
+        pub id: T,
+        pub marker: PhantomData<U>,
+    }
+
+    #[derive_where(T: PartialEq; PartialEq)]
+    pub enum Response<T> {
+        Ok(T),
+        Err,
+    }
+
+    pub fn unchanged() -> u8 {
+        1
+    }
+}
+
+use messages::{NotClone, Request, Response};
+
+#[test]
+fn test_all() {
+    let request = Request::<u8, NotClone> {
+        id: 1,
+        marker: std::marker::PhantomData,
+    };
+
+    assert_eq!(request.clone().id, 1);
+    assert!(Response::Ok(1).clone() == Response::Ok(1));
+    assert!(Response::<u8>::Err.clone() == Response::Err);
+    assert_eq!(messages::unchanged(), 1);
+}
@@ -0,0 +1,55 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+use diesel::{
+    deserialize::FromSqlRow, sql_types::BigInt, Connection, IntoSql, QueryableByName, RunQueryDsl,
+    SqliteConnection,
+};
+
+#[derive_where(; Debug, AsExpression, FromSql, ToSql)]
+#[derive(FromSqlRow)]
+struct Id<T>(#[derive_where(transparent)] i64, PhantomData<T>);
+
+fn connection() -> SqliteConnection {
+    SqliteConnection::establish(":memory:").unwrap()
+}
+
+#[test]
+fn test_from_sql() {
+    struct NotDebug;
+
+    let id: Id<NotDebug> = diesel::select(42_i64.into_sql::<BigInt>())
+        .get_result(&mut connection())
+        .unwrap();
+
+    assert_eq!(id.0, 42);
+}
+
+#[test]
+fn test_to_sql() {
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = BigInt)]
+        value: i64,
+    }
+
+    let row: Row = diesel::sql_query("SELECT ? AS value")
+        .bind::<BigInt, _>(Id::<()>(42, PhantomData))
+        .get_result(&mut connection())
+        .unwrap();
+
+    assert_eq!(row.value, 42);
+}
+
+#[test]
+fn test_as_expression() {
+    let id = Id::<()>(42, PhantomData);
+    let by_ref: i64 = diesel::select((&id).into_sql::<BigInt>())
+        .get_result(&mut connection())
+        .unwrap();
+    let by_value: i64 = diesel::select(id.into_sql::<BigInt>())
+        .get_result(&mut connection())
+        .unwrap();
+
+    assert_eq!((by_ref, by_value), (42, 42));
+}
@@ -0,0 +1,26 @@
+use derive_restricted::derive_where;
+
+#[test]
+fn test_enum() {
+    #[derive_where(; Display)]
+    enum Test {
+        A,
+        #[derive_where(display = "bee")]
+        B,
+        r#C,
+    }
+
+    assert_eq!(Test::A.to_string(), "A");
+    assert_eq!(Test::B.to_string(), "bee");
+    assert_eq!(Test::C.to_string(), "C");
+    assert_eq!(format!("{:>4}", Test::A), "   A");
+}
+
+#[test]
+fn test_struct() {
+    #[derive_where(; Debug, Display)]
+    struct Test;
+
+    assert_eq!(Test.to_string(), "Test");
+    assert_eq!(format!("{:?}", Test), "Test");
+}
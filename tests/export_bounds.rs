@@ -0,0 +1,24 @@
+use std::{fmt, marker::PhantomData};
+
+use derive_restricted::derive_where;
+
+struct NotClone;
+
+#[derive_where(T: Clone; Clone)]
+#[derive_where(T: fmt::Display; export_bounds(with_bounds))]
+struct Test<T, U>(T, PhantomData<U>);
+
+with_bounds! {
+    impl fmt::Display {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "<{}>", self.0)
+        }
+    }
+}
+
+#[test]
+fn test_export_bounds() {
+    let test = Test::<u8, NotClone>(1, PhantomData);
+
+    assert_eq!(test.clone().to_string(), "<1>");
+}
@@ -0,0 +1,57 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+use fake::{rand::RngCore, Dummy, Fake, Faker};
+
+struct NotDummy;
+
+/// Generates the same bits over and over.
+struct Constant(u64);
+
+impl RngCore for Constant {
+    fn next_u32(&mut self) -> u32 {
+        self.0 as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        dst.fill(self.0 as u8)
+    }
+}
+
+#[test]
+fn test_struct() {
+    #[derive_where(; Dummy)]
+    #[allow(dead_code)]
+    struct Test<T> {
+        a: u8,
+        name: String,
+        #[derive_where(skip(Dummy))]
+        cache: Option<u32>,
+        marker: PhantomData<T>,
+    }
+
+    let test: Test<NotDummy> = Faker.fake();
+    assert!(test.name.chars().count() <= 20);
+    assert_eq!(test.cache, None);
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(; Dummy)]
+    #[allow(dead_code)]
+    enum Test {
+        A,
+        B(u8),
+        C(#[derive_where(uninhabited)] std::convert::Infallible),
+    }
+
+    let mut rng = Constant(0);
+    assert!(matches!(Test::dummy_with_rng(&Faker, &mut rng), Test::A));
+
+    let mut rng = Constant(u64::MAX);
+    assert!(matches!(Test::dummy_with_rng(&Faker, &mut rng), Test::B(_)));
+}
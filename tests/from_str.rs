@@ -0,0 +1,58 @@
+use std::str::FromStr;
+
+use derive_restricted::derive_where;
+
+#[test]
+fn test_round_trip() {
+    #[derive_where(; Debug, Display, FromStr, PartialEq)]
+    enum Test {
+        A,
+        #[derive_where(display = "bee")]
+        B,
+        r#C,
+    }
+
+    for test in [Test::A, Test::B, Test::C] {
+        assert_eq!(test.to_string().parse::<Test>(), Ok(test));
+    }
+
+    assert_eq!(Test::from_str("B").unwrap_err().input(), "B");
+    assert_eq!(Test::from_str("a").unwrap_err().input(), "a");
+}
+
+#[test]
+fn test_case_insensitive() {
+    #[derive_where(; Debug, FromStr(ascii_case_insensitive), PartialEq)]
+    enum Test {
+        Alpha,
+        #[derive_where(display = "beta-2")]
+        Beta,
+    }
+
+    assert_eq!("alpha".parse(), Ok(Test::Alpha));
+    assert_eq!("ALPHA".parse(), Ok(Test::Alpha));
+    assert_eq!("Beta-2".parse(), Ok(Test::Beta));
+    assert_eq!("Beta".parse::<Test>().unwrap_err().input(), "Beta");
+}
+
+#[test]
+fn test_struct() {
+    #[derive_where(; Debug, FromStr, PartialEq)]
+    struct Test;
+
+    assert_eq!("Test".parse(), Ok(Test));
+    assert_eq!("test".parse::<Test>().unwrap_err().input(), "test");
+}
+
+#[test]
+fn test_error() {
+    #[derive_where(; Debug, FromStr, PartialEq)]
+    enum Test {
+        A,
+    }
+
+    let error: ParseTestError = "B".parse::<Test>().unwrap_err();
+
+    assert_eq!(error.to_string(), r#"invalid `Test`: "B""#);
+    assert!(std::error::Error::source(&error).is_none());
+}
@@ -0,0 +1,36 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where_items;
+
+struct NotClone;
+
+derive_where_items! {
+    #[derive_where(T: Clone; Clone)]
+    #[derive_where(T: PartialEq; PartialEq)]
+    struct TestStruct<T, U> {
+        a: T,
+        b: PhantomData<U>,
+    }
+
+    #[derive_where(implied_traits)]
+    #[derive_where(T: Ord; Ord)]
+    enum TestEnum<T> {
+        A(T),
+        B,
+    }
+}
+
+#[test]
+fn test_struct() {
+    let test = TestStruct::<u8, NotClone> {
+        a: 1,
+        b: PhantomData,
+    };
+
+    assert!(test.clone() == test);
+}
+
+#[test]
+fn test_enum() {
+    assert!(TestEnum::A(1) < TestEnum::B);
+}
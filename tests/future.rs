@@ -0,0 +1,88 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+};
+
+use derive_restricted::derive_where;
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Ready after being polled once, and not `Unpin`.
+struct YieldOnce(bool, std::marker::PhantomPinned);
+
+impl Future for YieldOnce {
+    type Output = u8;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u8> {
+        // SAFETY: only the `bool` is changed, which isn't pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if this.0 {
+            Poll::Ready(1)
+        } else {
+            this.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn test_single_field() {
+    #[derive_where(F: Future; Future)]
+    struct Named<F>(F);
+
+    assert_eq!(block_on(Named(async { 1 })), 1);
+    assert_eq!(
+        block_on(Named(YieldOnce(false, std::marker::PhantomPinned))),
+        1
+    );
+}
+
+#[test]
+fn test_delegate() {
+    #[derive_where(F: Future; Future)]
+    struct Named<F> {
+        name: &'static str,
+        #[derive_where(delegate(Future))]
+        future: F,
+    }
+
+    let named = Named {
+        name: "yield",
+        future: YieldOnce(false, std::marker::PhantomPinned),
+    };
+
+    assert_eq!(named.name, "yield");
+    assert_eq!(block_on(named), 1);
+}
+
+#[test]
+fn test_unpin() {
+    fn assert_unpin<T: Unpin>(_: &T) {}
+
+    #[derive_where(F: Future; Future)]
+    struct Named<F>(F);
+
+    let named = Named(std::future::ready(1));
+    assert_unpin(&named);
+    assert_eq!(block_on(named), 1);
+}
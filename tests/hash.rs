@@ -0,0 +1,221 @@
+mod util;
+
+use std::{
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use derive_restricted::derive_where;
+use util::hash;
+
+struct NotHash;
+
+#[test]
+fn test_struct() {
+    #[derive_where(T: Hash; Hash)]
+    struct TestStruct<T, U> {
+        a: T,
+        b: PhantomData<U>,
+    }
+
+    let test = TestStruct::<u8, NotHash> {
+        a: 1,
+        b: PhantomData,
+    };
+
+    assert_eq!(hash(&test), hash(&(1_u8, PhantomData::<NotHash>)));
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(T: Hash; Hash)]
+    enum TestEnum<T> {
+        A(T),
+        B(T),
+    }
+
+    assert_eq!(hash(&TestEnum::A(1)), hash(&TestEnum::A(1)));
+    assert_ne!(hash(&TestEnum::A(1)), hash(&TestEnum::B(1)));
+}
+
+/// Records the bytes written to it.
+#[derive(Default)]
+struct Recorder(Vec<u8>);
+
+impl Hasher for Recorder {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+#[test]
+fn test_portable() {
+    #[derive_where(; Hash(portable))]
+    enum Test {
+        A(u8),
+        B(u8, u8),
+    }
+
+    let record = |test: Test| {
+        let mut recorder = Recorder::default();
+        test.hash(&mut recorder);
+        recorder.0
+    };
+
+    assert_eq!(record(Test::A(5)), [0, 0, 0, 0, 5]);
+    assert_eq!(record(Test::B(6, 7)), [1, 0, 0, 0, 6, 7]);
+
+    #[derive_where(; Hash(portable))]
+    struct Fields {
+        a: u32,
+        b: String,
+        c: Vec<u16>,
+        d: usize,
+    }
+
+    let fields = Fields {
+        a: 0x0102_0304,
+        b: String::from("ab"),
+        c: vec![0x0506, 0x0708],
+        d: 9,
+    };
+    let mut recorder = Recorder::default();
+    fields.hash(&mut recorder);
+
+    assert_eq!(
+        recorder.0,
+        [
+            4, 3, 2, 1, // a
+            2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b', // b
+            2, 0, 0, 0, 0, 0, 0, 0, 6, 5, 8, 7, // c
+            9, 0, 0, 0, 0, 0, 0, 0, // d
+        ]
+    );
+}
+
+#[test]
+fn test_repr() {
+    #[derive_where(; Hash)]
+    #[repr(u16)]
+    enum Test {
+        A(u8) = 3,
+        B,
+    }
+
+    let record = |value: &dyn Fn(&mut Recorder)| {
+        let mut recorder = Recorder::default();
+        value(&mut recorder);
+        recorder.0
+    };
+
+    assert_eq!(
+        record(&|recorder| Test::A(5).hash(recorder)),
+        record(&|recorder| (3_u16, 5_u8).hash(recorder))
+    );
+    assert_eq!(
+        record(&|recorder| Test::B.hash(recorder)),
+        record(&|recorder| 4_u16.hash(recorder))
+    );
+}
+
+#[test]
+fn test_portable_repr() {
+    #[derive_where(; Hash(portable))]
+    #[repr(u16)]
+    enum Test {
+        A(u8) = 0x0102,
+        B,
+    }
+
+    let record = |test: Test| {
+        let mut recorder = Recorder::default();
+        test.hash(&mut recorder);
+        recorder.0
+    };
+
+    assert_eq!(record(Test::A(5)), [2, 1, 5]);
+    assert_eq!(record(Test::B), [3, 1]);
+}
+
+#[test]
+fn test_tag() {
+    #[derive_where(; Hash)]
+    #[derive_where(hash_tag)]
+    struct Meters(u32);
+
+    #[derive_where(; Hash)]
+    #[derive_where(hash_tag = "feet")]
+    struct Feet(u32);
+
+    let record = |value: &dyn Fn(&mut Recorder)| {
+        let mut recorder = Recorder::default();
+        value(&mut recorder);
+        recorder.0
+    };
+
+    assert_eq!(
+        record(&|recorder| Meters(1).hash(recorder)),
+        record(&|recorder| ("Meters", 1_u32).hash(recorder))
+    );
+    assert_eq!(
+        record(&|recorder| Feet(1).hash(recorder)),
+        record(&|recorder| ("feet", 1_u32).hash(recorder))
+    );
+}
+
+#[test]
+fn test_to_bits() {
+    #[derive_where(; Hash)]
+    struct Test(
+        #[derive_where(to_bits)] f64,
+        #[derive_where(to_bits(canonical_nan))] f32,
+    );
+
+    let nan = f32::from_bits(f32::NAN.to_bits() ^ 1);
+
+    assert_eq!(
+        hash(&Test(1.5, 2.)),
+        hash(&(1.5_f64.to_bits(), 2_f32.to_bits()))
+    );
+    assert_eq!(hash(&Test(-0., -0.)), hash(&Test(0., 0.)));
+    assert_eq!(hash(&Test(0., nan)), hash(&Test(0., f32::NAN)));
+    assert_ne!(hash(&Test(1.5, 0.)), hash(&Test(2.5, 0.)));
+}
+
+#[test]
+fn test_unordered() {
+    #[derive_where(; Hash)]
+    struct Test {
+        #[derive_where(unordered)]
+        tags: Vec<&'static str>,
+    }
+
+    let test = |tags| hash(&Test { tags });
+
+    assert_eq!(test(vec!["a", "b", "b"]), test(vec!["b", "a", "b"]));
+    assert_ne!(test(vec!["a", "b"]), test(vec!["a", "b", "b"]));
+}
+
+#[test]
+fn test_integers() {
+    #[derive_where(; Hash)]
+    struct Test {
+        a: u8,
+        b: i64,
+        c: usize,
+        d: u16,
+    }
+
+    let test = Test {
+        a: 1,
+        b: -2,
+        c: 3,
+        d: 4,
+    };
+
+    assert_eq!(hash(&test), hash(&(1_u8, -2_i64, 3_usize, 4_u16)));
+}
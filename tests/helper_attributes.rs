@@ -0,0 +1,19 @@
+use derive_restricted::derive_where;
+
+#[test]
+fn test_default() {
+    #[derive_where(T: Clone; Clone)]
+    #[derive(Debug, Default)]
+    enum TestDefault<T> {
+        #[default]
+        A,
+        #[allow(dead_code)]
+        B(T),
+    }
+
+    let test = TestDefault::<u8>::default();
+    let cloned = test.clone();
+
+    dbg!(test);
+    dbg!(cloned);
+}
@@ -0,0 +1,45 @@
+use std::{cmp::Ordering, marker::PhantomData};
+
+use derive_restricted::derive_where;
+
+struct NotOrd;
+
+#[test]
+fn test_copy() {
+    #[derive_where(implied_traits)]
+    #[derive_where(T: Copy; Copy)]
+    struct TestCopy<T>(T);
+
+    let test = TestCopy(1);
+    let copied = test;
+    let cloned = Clone::clone(&test);
+
+    assert_eq!(copied.0, cloned.0);
+}
+
+#[test]
+fn test_ord() {
+    #[derive_where(implied_traits)]
+    #[derive_where(T: Ord; Ord)]
+    struct TestOrd<T, U>(T, PhantomData<U>);
+
+    let test = TestOrd::<u8, NotOrd>(1, PhantomData);
+
+    assert!(test == TestOrd(1, PhantomData));
+    assert_eq!(
+        test.partial_cmp(&TestOrd(2, PhantomData)),
+        Some(Ordering::Less)
+    );
+    assert_eq!(test.cmp(&TestOrd(0, PhantomData)), Ordering::Greater);
+}
+
+#[test]
+fn test_explicit() {
+    // Explicitly requested traits keep their own bounds.
+    #[derive_where(implied_traits, allow(unused_bound))]
+    #[derive_where(T: Ord; Ord)]
+    #[derive_where(; PartialEq)]
+    struct TestExplicit<T>(PhantomData<T>);
+
+    assert!(TestExplicit::<NotOrd>(PhantomData) == TestExplicit(PhantomData));
+}
@@ -0,0 +1,40 @@
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+use derive_restricted::derive_where;
+
+#[test]
+fn test_single_field() {
+    #[derive_where(T: Read; Read)]
+    #[derive_where(T: Seek; Seek)]
+    #[derive_where(T: Write; Write)]
+    struct Wrapper<T>(T);
+
+    let mut wrapper = Wrapper(Cursor::new(Vec::new()));
+
+    wrapper.write_all(b"abc").unwrap();
+    wrapper.flush().unwrap();
+    assert_eq!(wrapper.seek(SeekFrom::Start(1)).unwrap(), 1);
+
+    let mut string = String::new();
+    wrapper.read_to_string(&mut string).unwrap();
+    assert_eq!(string, "bc");
+}
+
+#[test]
+fn test_delegate() {
+    #[derive_where(W: Write; Write)]
+    struct Logging<W> {
+        prefix: &'static str,
+        #[derive_where(delegate(Write))]
+        writer: W,
+    }
+
+    let mut logging = Logging {
+        prefix: "log",
+        writer: Vec::new(),
+    };
+
+    let prefix = logging.prefix;
+    write!(logging, "{}: {}", prefix, 1).unwrap();
+    assert_eq!(logging.writer, b"log: 1");
+}
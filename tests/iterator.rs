@@ -0,0 +1,35 @@
+use derive_restricted::derive_where;
+
+#[test]
+fn test_single_field() {
+    #[derive_where(I: Iterator; Iterator)]
+    #[derive_where(I: DoubleEndedIterator; DoubleEndedIterator)]
+    #[derive_where(I: ExactSizeIterator; ExactSizeIterator)]
+    struct Wrapper<I>(I);
+
+    let mut wrapper = Wrapper(1..4);
+
+    assert_eq!(wrapper.len(), 3);
+    assert_eq!(wrapper.next_back(), Some(3));
+    assert_eq!(wrapper.size_hint(), (2, Some(2)));
+    assert_eq!(wrapper.collect::<Vec<_>>(), [1, 2]);
+}
+
+#[test]
+fn test_delegate() {
+    #[derive_where(I: Iterator; Iterator)]
+    #[derive_where(I: DoubleEndedIterator; DoubleEndedIterator)]
+    struct Counting<I> {
+        #[derive_where(delegate(Iterator))]
+        iter: I,
+        label: &'static str,
+    }
+
+    let counting = Counting {
+        iter: "abc".chars(),
+        label: "chars",
+    };
+
+    assert_eq!(counting.label, "chars");
+    assert_eq!(counting.rev().collect::<String>(), "cba");
+}
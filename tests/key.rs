@@ -0,0 +1,122 @@
+mod util;
+
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use derive_restricted::derive_where;
+use util::hash;
+
+#[test]
+fn test_method() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[derive_where(key = self.key())]
+    struct Test {
+        name: &'static str,
+        cache: f32,
+    }
+
+    impl Test {
+        fn key(&self) -> String {
+            self.name.to_lowercase()
+        }
+    }
+
+    let a = Test {
+        name: "A",
+        cache: 1.,
+    };
+    let b = Test {
+        name: "a",
+        cache: 2.,
+    };
+    let c = Test {
+        name: "b",
+        cache: 1.,
+    };
+
+    assert!(a == b);
+    assert!(a != c);
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert_eq!(a.cmp(&c), Ordering::Less);
+    assert_eq!(a.partial_cmp(&c), Some(Ordering::Less));
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(a.cache + b.cache + c.cache, 4.);
+}
+
+#[test]
+fn test_borrowed() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord)]
+    #[derive_where(key = (&self.0, self.1.len()))]
+    struct Test(String, Vec<f32>);
+
+    let a = Test(String::from("a"), vec![1.]);
+
+    assert!(a == Test(String::from("a"), vec![2.]));
+    assert_eq!(
+        a.cmp(&Test(String::from("a"), vec![1., 2.])),
+        Ordering::Less
+    );
+}
+
+mod keys {
+    pub fn lowercase(value: &str) -> String {
+        value.to_lowercase()
+    }
+}
+
+#[test]
+fn test_self_path() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord)]
+    #[derive_where(key = self::keys::lowercase(&self.0))]
+    struct Test(String);
+
+    assert!(Test(String::from("A")) == Test(String::from("a")));
+    assert_eq!(
+        Test(String::from("a")).cmp(&Test(String::from("B"))),
+        Ordering::Less
+    );
+}
+
+#[test]
+fn test_via() {
+    struct CaseInsensitive(String);
+
+    impl From<&Test> for CaseInsensitive {
+        fn from(test: &Test) -> Self {
+            Self(test.0.to_lowercase())
+        }
+    }
+
+    impl PartialEq for CaseInsensitive {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Hash for CaseInsensitive {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state);
+        }
+    }
+
+    #[derive_where(; PartialEq, Hash)]
+    #[derive_where(via = CaseInsensitive::from)]
+    struct Test(&'static str);
+
+    #[derive_where(; PartialEq)]
+    #[derive_where(via = Self::len)]
+    struct Length(&'static str);
+
+    impl Length {
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    assert!(Test("A") == Test("a"));
+    assert_eq!(hash(&Test("A")), hash(&Test("a")));
+    assert!(Length("a") == Length("b"));
+    assert!(Length("a") != Length("ab"));
+}
@@ -0,0 +1,62 @@
+use std::{cmp::Ordering, marker::PhantomData};
+
+use derive_restricted::derive_where;
+
+pub struct NotClone;
+
+pub mod types {
+    pub type Value<T> = Option<T>;
+}
+
+macro_rules! tuple {
+    ($name:ident, $ty:ty, $($trait:path),+) => {
+        #[derive_where(T: Clone + Ord; $($trait),+)]
+        struct $name<T, U>($ty, ::core::marker::PhantomData<U>);
+    };
+}
+
+macro_rules! named {
+    ($name:ident, $field:ident, $($bound:tt)*) => {
+        #[derive_where($($bound)*; Clone, PartialEq)]
+        #[::derive_restricted::derive_where($($bound)*; Eq, PartialOrd, Ord)]
+        enum $name<T, U> {
+            Struct {
+                $field: $crate::types::Value<T>,
+                other: PhantomData<U>,
+            },
+            Unit,
+        }
+    };
+}
+
+tuple!(
+    TestTuple,
+    T,
+    Clone,
+    core::cmp::PartialEq,
+    Eq,
+    ::core::cmp::PartialOrd,
+    Ord
+);
+named!(TestNamed, field, T: Clone + Ord);
+
+#[test]
+fn test_tuple() {
+    let test = TestTuple::<u8, NotClone>(1, PhantomData);
+    let cloned = test.clone();
+
+    assert!(test == cloned);
+    assert_eq!(test.cmp(&TestTuple(2, PhantomData)), Ordering::Less);
+}
+
+#[test]
+fn test_named() {
+    let test = TestNamed::<u8, NotClone>::Struct {
+        field: Some(1),
+        other: PhantomData,
+    };
+    let cloned = test.clone();
+
+    assert!(test == cloned);
+    assert_eq!(test.cmp(&TestNamed::Unit), Ordering::Less);
+}
@@ -0,0 +1,74 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+
+struct NotCbor;
+
+#[test]
+fn test_struct() {
+    #[derive_where(; minicbor::Encode, minicbor::Decode, Debug, PartialEq)]
+    struct Test<T> {
+        a: u8,
+        #[derive_where(index(4))]
+        b: Option<bool>,
+        #[derive_where(skip(minicbor::Encode, minicbor::Decode, PartialEq))]
+        cache: Option<u32>,
+        marker: PhantomData<T>,
+    }
+
+    let test = Test::<NotCbor> {
+        a: 1,
+        b: Some(true),
+        cache: Some(2),
+        marker: PhantomData,
+    };
+    let encoded = minicbor::to_vec(&test).unwrap();
+
+    // `a`, then `null` at the unused index 1, for `cache` and `marker`, then `b`.
+    assert_eq!(encoded, [0x85, 0x01, 0xf6, 0xf6, 0xf6, 0xf5]);
+    assert_eq!(minicbor::decode::<Test<NotCbor>>(&encoded).unwrap(), test);
+
+    // Missing trailing fields are `nil`, additional ones are skipped.
+    assert_eq!(
+        minicbor::decode::<Test<NotCbor>>(&[0x81, 0x01]).unwrap().b,
+        None
+    );
+    assert_eq!(
+        minicbor::decode::<Test<NotCbor>>(&[0x86, 0x01, 0xf6, 0xf6, 0xf6, 0xf4, 0x02])
+            .unwrap()
+            .b,
+        Some(false)
+    );
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(; minicbor::Encode, minicbor::Decode, Debug, PartialEq)]
+    enum Test<'a> {
+        A,
+        #[derive_where(index(5))]
+        B(&'a str),
+    }
+
+    let encoded = minicbor::to_vec(Test::B("b")).unwrap();
+
+    assert_eq!(minicbor::to_vec(Test::A).unwrap(), [0x82, 0x00, 0x80]);
+    assert_eq!(encoded, [0x82, 0x05, 0x81, 0x61, b'b']);
+    assert_eq!(minicbor::decode::<Test>(&encoded).unwrap(), Test::B("b"));
+    assert!(minicbor::decode::<Test>(&[0x82, 0x01, 0x80]).is_err());
+}
+
+#[test]
+fn test_recursive() {
+    #[derive_where(; minicbor::Encode, minicbor::Decode)]
+    #[derive_where(T: std::fmt::Debug + PartialEq; Debug, PartialEq)]
+    enum List<T> {
+        Nil,
+        Cons(T, Box<List<T>>),
+    }
+
+    let list = List::Cons(1_u8, Box::new(List::Nil));
+    let encoded = minicbor::to_vec(&list).unwrap();
+
+    assert_eq!(minicbor::decode::<List<u8>>(&encoded).unwrap(), list);
+}
@@ -0,0 +1,281 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+
+struct NotPartialEq;
+
+#[test]
+fn test_struct() {
+    #[derive_where(T: PartialEq; PartialEq)]
+    #[derive_where(T: Eq; Eq)]
+    struct TestStruct<T, U> {
+        a: T,
+        b: PhantomData<U>,
+    }
+
+    let test = TestStruct::<u8, NotPartialEq> {
+        a: 1,
+        b: PhantomData,
+    };
+
+    assert!(
+        test == TestStruct {
+            a: 1,
+            b: PhantomData
+        }
+    );
+    assert!(
+        test != TestStruct {
+            a: 2,
+            b: PhantomData
+        }
+    );
+}
+
+#[test]
+fn test_tuple() {
+    #[derive_where(T: PartialEq; PartialEq)]
+    #[derive_where(T: Eq; Eq)]
+    struct TestTuple<T, U>(T, PhantomData<U>);
+
+    let test = TestTuple::<u8, NotPartialEq>(1, PhantomData);
+
+    assert!(test == TestTuple(1, PhantomData));
+    assert!(test != TestTuple(2, PhantomData));
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(T: PartialEq; PartialEq)]
+    #[derive_where(T: Eq; Eq)]
+    enum TestEnum<T, U> {
+        VariantStruct { field: T },
+        VariantTuple(PhantomData<U>),
+        Variant,
+    }
+
+    let test = TestEnum::<u8, NotPartialEq>::VariantStruct { field: 1 };
+
+    assert!(test == TestEnum::VariantStruct { field: 1 });
+    assert!(test != TestEnum::VariantStruct { field: 2 });
+    assert!(test != TestEnum::VariantTuple(PhantomData));
+    assert!(test != TestEnum::Variant);
+    assert!(TestEnum::<u8, NotPartialEq>::Variant == TestEnum::Variant);
+}
+
+#[test]
+fn test_unit_only_enum() {
+    #[derive_where(; PartialEq)]
+    enum TestEnum {
+        A,
+        B {},
+        C(),
+    }
+
+    let test = TestEnum::B {};
+
+    assert!(test == TestEnum::B {});
+    assert!(test != TestEnum::A);
+    assert!(test != TestEnum::C());
+}
+
+#[test]
+fn test_unit() {
+    #[derive_where(; PartialEq, Eq)]
+    struct TestUnit;
+
+    assert!(TestUnit == TestUnit);
+}
+
+#[test]
+fn test_other() {
+    struct Borrowed<'a> {
+        name: &'a str,
+        id: u32,
+    }
+
+    #[derive_where(; PartialEq<Borrowed<'_>>)]
+    struct Owned {
+        name: String,
+        id: u32,
+    }
+
+    let owned = Owned {
+        name: String::from("a"),
+        id: 1,
+    };
+
+    assert!(owned == Borrowed { name: "a", id: 1 });
+    assert!(owned != Borrowed { name: "b", id: 1 });
+    assert!(owned != Borrowed { name: "a", id: 2 });
+}
+
+#[test]
+fn test_other_enum() {
+    enum Mirror<T> {
+        A(T),
+        B { b: u8 },
+        C(PhantomData<T>),
+    }
+
+    #[derive_where(T: PartialEq<U>; PartialEq<Mirror<U>>)]
+    enum Test<T, U> {
+        A(T),
+        B { b: u8 },
+        C(PhantomData<U>),
+    }
+
+    let test = Test::<String, &str>::A(String::from("a"));
+
+    assert!(test == Mirror::A("a"));
+    assert!(test != Mirror::A("b"));
+    assert!(test != Mirror::<&str>::B { b: 1 });
+    assert!(Test::<String, &str>::B { b: 1 } == Mirror::<&str>::B { b: 1 });
+    assert!(Test::<String, &str>::C(PhantomData) == Mirror::<&str>::C(PhantomData));
+}
+
+#[test]
+fn test_other_skip() {
+    #[allow(dead_code)]
+    struct Other {
+        a: u8,
+        b: u8,
+    }
+
+    #[derive_where(; PartialEq<Other>)]
+    #[allow(dead_code)]
+    struct Mine {
+        a: u8,
+        #[derive_where(skip(PartialEq))]
+        b: u8,
+    }
+
+    assert!(Mine { a: 1, b: 1 } == Other { a: 1, b: 2 });
+    assert!(Mine { a: 1, b: 1 } != Other { a: 2, b: 1 });
+}
+
+#[test]
+fn test_other_instantiation() {
+    #[derive_where(<U> T: PartialEq<U>; PartialEq<Test<U>>)]
+    struct Test<T>(T);
+
+    assert!(Test(String::from("a")) == Test("a"));
+    assert!(Test("a") == Test(String::from("a")));
+    assert!(Test(String::from("a")) != Test("b"));
+}
+
+#[test]
+fn test_epsilon() {
+    #[derive_where(; PartialEq)]
+    struct Test {
+        #[derive_where(epsilon = 1e-9)]
+        a: f64,
+        #[derive_where(epsilon = f32::EPSILON)]
+        b: f32,
+        c: f64,
+    }
+
+    let test = |a, b, c| Test { a, b, c };
+
+    assert!(test(0.1 + 0.2, 1., 1.) == test(0.3, 1. + f32::EPSILON, 1.));
+    assert!(test(0.3, 1., 1.) != test(0.4, 1., 1.));
+    assert!(test(0.3, 1., 1.) != test(0.3, 1.5, 1.));
+    assert!(test(0.3, 1., 0.1 + 0.2) != test(0.3, 1., 0.3));
+    assert!(test(f64::INFINITY, 1., 1.) == test(f64::INFINITY, 1., 1.));
+    assert!(test(f64::NAN, 1., 1.) != test(f64::NAN, 1., 1.));
+}
+
+#[test]
+fn test_epsilon_unsigned() {
+    #[derive_where(; PartialEq)]
+    struct Test(#[derive_where(epsilon = 1)] u32);
+
+    assert!(Test(1) == Test(2));
+    assert!(Test(2) == Test(1));
+    assert!(Test(1) != Test(5));
+    assert!(Test(5) != Test(1));
+}
+
+#[test]
+fn test_epsilon_skip() {
+    #[derive_where(; PartialEq, PartialOrd)]
+    struct Test {
+        #[derive_where(epsilon = 1e-9, skip(PartialOrd))]
+        a: f64,
+        b: f64,
+    }
+
+    let test = |a, b| Test { a, b };
+
+    assert!(test(0.1 + 0.2, 1.) == test(0.3, 1.));
+    assert!(test(0.3, 1.) < test(0.1, 2.));
+}
+
+#[test]
+#[allow(clippy::eq_op)]
+fn test_ptr_eq() {
+    #[derive_where(; PartialEq, PartialOrd)]
+    #[derive_where(ptr_eq)]
+    struct Test(f64);
+
+    let test = Test(f64::NAN);
+
+    assert!(test == test);
+    assert_eq!(test.partial_cmp(&test), Some(std::cmp::Ordering::Equal));
+    assert!(test != Test(f64::NAN));
+    assert_eq!(test.partial_cmp(&Test(f64::NAN)), None);
+}
+
+#[test]
+fn test_unordered() {
+    #[derive_where(T: PartialEq; PartialEq)]
+    struct Test<'a, T> {
+        #[derive_where(unordered)]
+        tags: Vec<T>,
+        #[derive_where(unordered)]
+        slice: &'a [u8],
+    }
+
+    let test = |tags, slice| Test { tags, slice };
+
+    assert!(test(vec![1, 2, 2, 3], &[1, 2]) == test(vec![2, 3, 1, 2], &[2, 1]));
+    assert!(test(vec![1, 2, 2], &[]) != test(vec![1, 1, 2], &[]));
+    assert!(test(vec![1, 2], &[]) != test(vec![1, 2, 2], &[]));
+}
+
+#[test]
+fn test_case_insensitive() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Identifier<'a> {
+        #[derive_where(case_insensitive)]
+        name: String,
+        #[derive_where(case_insensitive)]
+        namespace: &'a str,
+    }
+
+    let identifier = |name: &str, namespace| Identifier {
+        name: name.to_owned(),
+        namespace,
+    };
+
+    assert!(identifier("Name", "Std") == identifier("nAME", "std"));
+    assert!(identifier("Name", "Std") != identifier("Names", "Std"));
+    assert!(identifier("apple", "") < identifier("Banana", ""));
+    assert_eq!(
+        identifier("a", "B").cmp(&identifier("A", "b")),
+        std::cmp::Ordering::Equal
+    );
+
+    let hash = |identifier: &Identifier| {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        identifier.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    assert_eq!(
+        hash(&identifier("Name", "Std")),
+        hash(&identifier("NAME", "std"))
+    );
+}
@@ -0,0 +1,343 @@
+mod util;
+
+use std::{cmp::Ordering, marker::PhantomData};
+
+use derive_restricted::derive_where;
+use util::hash;
+
+struct NotOrd;
+
+#[test]
+fn test_struct() {
+    #[derive_where(T: PartialEq; PartialEq)]
+    #[derive_where(T: Ord; Eq, PartialOrd, Ord)]
+    struct TestStruct<T, U> {
+        a: T,
+        b: T,
+        c: PhantomData<U>,
+    }
+
+    let test = TestStruct::<u8, NotOrd> {
+        a: 1,
+        b: 2,
+        c: PhantomData,
+    };
+
+    assert_eq!(
+        test.cmp(&TestStruct {
+            a: 1,
+            b: 2,
+            c: PhantomData
+        }),
+        Ordering::Equal
+    );
+    assert_eq!(
+        test.cmp(&TestStruct {
+            a: 1,
+            b: 3,
+            c: PhantomData
+        }),
+        Ordering::Less
+    );
+    assert_eq!(
+        test.partial_cmp(&TestStruct {
+            a: 0,
+            b: 3,
+            c: PhantomData
+        }),
+        Some(Ordering::Greater)
+    );
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(T: PartialEq; PartialEq)]
+    #[derive_where(T: Ord; Eq, PartialOrd, Ord)]
+    enum TestEnum<T, U> {
+        VariantStruct { field: T },
+        VariantTuple(PhantomData<U>),
+        Variant,
+    }
+
+    let test = TestEnum::<u8, NotOrd>::VariantStruct { field: 1 };
+
+    assert_eq!(
+        test.cmp(&TestEnum::VariantStruct { field: 1 }),
+        Ordering::Equal
+    );
+    assert_eq!(
+        test.cmp(&TestEnum::VariantStruct { field: 0 }),
+        Ordering::Greater
+    );
+    assert_eq!(
+        test.cmp(&TestEnum::VariantTuple(PhantomData)),
+        Ordering::Less
+    );
+    assert_eq!(
+        TestEnum::<u8, NotOrd>::Variant.partial_cmp(&TestEnum::VariantTuple(PhantomData)),
+        Some(Ordering::Greater)
+    );
+}
+
+#[test]
+fn test_discriminant() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord)]
+    #[repr(u8)]
+    enum TestDiscriminant<T> {
+        A(PhantomData<T>) = 2,
+        B(PhantomData<T>),
+        C(PhantomData<T>) = 1,
+    }
+
+    let a = TestDiscriminant::<NotOrd>::A(PhantomData);
+    let b = TestDiscriminant::B(PhantomData);
+    let c = TestDiscriminant::C(PhantomData);
+
+    assert_eq!(a.cmp(&b), Ordering::Less);
+    assert_eq!(a.cmp(&c), Ordering::Greater);
+    assert_eq!(b.cmp(&c), Ordering::Greater);
+}
+
+#[test]
+fn test_discriminant_fields() {
+    #[derive_where(; PartialEq, PartialOrd)]
+    #[repr(i16)]
+    enum Test {
+        A(f32) = -1,
+        B { a: u8, b: u8 } = -2,
+    }
+
+    assert_eq!(Test::A(1.).partial_cmp(&Test::A(2.)), Some(Ordering::Less));
+    assert_eq!(Test::A(f32::NAN).partial_cmp(&Test::A(2.)), None);
+    assert_eq!(
+        Test::B { a: 1, b: 2 }.partial_cmp(&Test::B { a: 1, b: 1 }),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(
+        Test::A(f32::NAN).partial_cmp(&Test::B { a: 1, b: 1 }),
+        Some(Ordering::Greater)
+    );
+}
+
+#[test]
+fn test_other() {
+    struct Key {
+        name: &'static str,
+        version: u32,
+    }
+
+    #[derive_where(; PartialEq<Key>, PartialOrd<Key>)]
+    struct Entry {
+        name: &'static str,
+        version: u32,
+    }
+
+    let entries = [("a", 1), ("a", 2), ("b", 1)].map(|(name, version)| Entry { name, version });
+
+    let find =
+        |key: Key| entries.binary_search_by(|entry| entry.partial_cmp(&key).expect("comparable"));
+
+    assert_eq!(
+        find(Key {
+            name: "a",
+            version: 2
+        }),
+        Ok(1)
+    );
+    assert_eq!(
+        find(Key {
+            name: "b",
+            version: 1
+        }),
+        Ok(2)
+    );
+    assert_eq!(
+        find(Key {
+            name: "a",
+            version: 3
+        }),
+        Err(2)
+    );
+}
+
+#[test]
+fn test_other_enum() {
+    enum Mirror {
+        A(u16),
+        B,
+    }
+
+    #[derive_where(; PartialEq<Mirror>, PartialOrd<Mirror>)]
+    enum Test {
+        A(u16),
+        B,
+    }
+
+    assert_eq!(Test::A(1).partial_cmp(&Mirror::A(2)), Some(Ordering::Less));
+    assert_eq!(Test::A(1).partial_cmp(&Mirror::B), Some(Ordering::Less));
+    assert_eq!(Test::B.partial_cmp(&Mirror::A(1)), Some(Ordering::Greater));
+    assert!(Test::B == Mirror::B);
+}
+
+#[test]
+fn test_priority() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord)]
+    struct Test {
+        name: &'static str,
+        #[derive_where(ord(1))]
+        minor: u8,
+        #[derive_where(ord(0))]
+        major: u8,
+    }
+
+    let test = |name, major, minor| Test { name, major, minor };
+
+    assert_eq!(test("b", 1, 0).cmp(&test("a", 0, 1)), Ordering::Greater);
+    assert_eq!(test("a", 1, 0).cmp(&test("a", 1, 1)), Ordering::Less);
+    assert_eq!(test("b", 1, 1).cmp(&test("a", 1, 1)), Ordering::Greater);
+}
+
+#[test]
+fn test_none_last() {
+    #[derive_where(; PartialEq, Eq, PartialOrd, Ord)]
+    struct Task {
+        #[derive_where(none_last)]
+        deadline: Option<u32>,
+        name: &'static str,
+    }
+
+    let task = |deadline, name| Task { deadline, name };
+
+    let mut tasks = [task(None, "a"), task(Some(2), "b"), task(Some(1), "c")];
+    tasks.sort();
+
+    assert_eq!(tasks.map(|task| task.name), ["c", "b", "a"]);
+    assert_eq!(
+        task(None, "a").partial_cmp(&task(None, "b")),
+        Some(Ordering::Less)
+    );
+}
+
+#[test]
+fn test_incomparable() {
+    use std::cmp::Ordering;
+
+    #[derive_where(; Debug, PartialEq, PartialOrd)]
+    enum Test {
+        A(u8),
+        #[derive_where(incomparable)]
+        B(f32),
+        C,
+    }
+
+    assert!(Test::A(1) == Test::A(1));
+    assert!(Test::B(1.) != Test::B(1.));
+    assert_eq!(Test::A(1).partial_cmp(&Test::C), Some(Ordering::Less));
+    assert_eq!(Test::B(1.).partial_cmp(&Test::B(1.)), None);
+    assert_eq!(Test::A(1).partial_cmp(&Test::B(1.)), None);
+    assert_eq!(Test::B(1.).partial_cmp(&Test::C), None);
+}
+
+#[test]
+fn test_incomparable_with() {
+    use std::cmp::Ordering;
+
+    #[derive_where(; Debug, PartialEq, PartialOrd)]
+    enum Test {
+        #[derive_where(incomparable_with(B, C))]
+        A(u8),
+        B,
+        C,
+        D,
+    }
+
+    assert_eq!(Test::A(1).partial_cmp(&Test::A(2)), Some(Ordering::Less));
+    assert_eq!(Test::A(1).partial_cmp(&Test::B), None);
+    assert_eq!(Test::C.partial_cmp(&Test::A(1)), None);
+    assert_eq!(Test::A(1).partial_cmp(&Test::D), Some(Ordering::Less));
+    assert_eq!(Test::B.partial_cmp(&Test::C), Some(Ordering::Less));
+}
+
+#[test]
+fn test_incomparable_ord() {
+    use std::cmp::Ordering;
+
+    #[derive_where(; Debug, Eq, Ord, PartialEq, PartialOrd)]
+    #[derive_where(incomparable_ord = last)]
+    enum Test {
+        #[derive_where(incomparable)]
+        A,
+        B(u8),
+        #[derive_where(incomparable)]
+        C,
+        D,
+    }
+
+    let mut values = [Test::C, Test::D, Test::A, Test::B(2), Test::B(1)];
+    values.sort();
+
+    assert!(matches!(
+        values[..],
+        [Test::B(1), Test::B(2), Test::D, Test::A, Test::C]
+    ));
+    assert_eq!(Test::A.cmp(&Test::A), Ordering::Equal);
+    assert_eq!(Test::A.partial_cmp(&Test::A), Some(Ordering::Equal));
+    assert!(Test::A == Test::A);
+}
+
+#[test]
+fn test_incomparable_ord_eq() {
+    use std::cmp::Ordering;
+
+    #[derive_where(; Eq, Hash, Ord, PartialEq, PartialOrd)]
+    #[derive_where(incomparable_ord = last)]
+    #[allow(dead_code)]
+    enum Test {
+        #[derive_where(incomparable)]
+        A(u8),
+        B(u8),
+        #[derive_where(incomparable)]
+        C,
+    }
+
+    let values = [Test::A(1), Test::A(2), Test::B(1), Test::B(2), Test::C];
+
+    for a in &values {
+        for b in &values {
+            assert_eq!(a == b, a.cmp(b) == Ordering::Equal);
+
+            if a == b {
+                assert_eq!(hash(a), hash(b));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_incomparable_msrv() {
+    #[derive_where(; Eq, Ord, PartialEq, PartialOrd)]
+    #[derive_where(incomparable_ord = last, msrv = "1.41.1")]
+    enum Test {
+        #[derive_where(incomparable)]
+        A,
+        B(u8),
+    }
+
+    assert!(Test::B(2) > Test::B(1));
+    assert!(Test::A > Test::B(1));
+    assert!(Test::B(1) < Test::A);
+}
+
+#[test]
+#[should_panic = "incomparable variants can't be ordered"]
+fn test_incomparable_ord_panic() {
+    #[derive_where(; Eq, Ord, PartialEq, PartialOrd)]
+    #[derive_where(incomparable_ord = panic)]
+    enum Test {
+        A,
+        #[derive_where(incomparable)]
+        B,
+    }
+
+    let _ = Test::A.cmp(&Test::B);
+}
@@ -0,0 +1,35 @@
+use std::collections::hash_map::DefaultHasher;
+
+use derive_restricted::derive_where;
+
+#[test]
+fn test_full_paths() {
+    #[derive_where(
+        T: ::core::marker::Copy
+            + ::core::fmt::Debug
+            + ::core::hash::Hash
+            + ::core::cmp::Ord;
+        ::core::clone::Clone,
+        ::core::marker::Copy,
+        ::core::fmt::Debug,
+        ::core::cmp::Eq,
+        ::core::hash::Hash,
+        ::core::cmp::Ord,
+        ::core::cmp::PartialEq,
+        ::core::cmp::PartialOrd,
+    )]
+    struct Test<T>(T);
+
+    #[derive_where(; ::std::fmt::Display, ::std::str::FromStr)]
+    enum Unit {
+        A,
+    }
+
+    let test = Test(1);
+    let copied = test;
+
+    assert_eq!(format!("{:?}", copied.clone()), "Test(1)");
+    assert!(test == copied && test <= copied);
+    std::hash::Hash::hash(&test, &mut DefaultHasher::new());
+    assert_eq!(Unit::A.to_string().parse::<Unit>().map(|_| ()), Ok(()));
+}
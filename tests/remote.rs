@@ -0,0 +1,63 @@
+mod util;
+
+use std::{cmp::Ordering, hash::Hash, marker::PhantomData};
+
+use derive_restricted::derive_where;
+use util::hash;
+
+/// Stands in for a crate that can't be annotated.
+mod foreign {
+    use std::marker::PhantomData;
+
+    pub struct Pair<T, U> {
+        pub a: T,
+        pub b: PhantomData<U>,
+    }
+
+    pub enum Either<T> {
+        Left(T),
+        Right { value: T },
+    }
+}
+
+struct NotClone;
+
+#[test]
+fn test_struct() {
+    #[derive_where(T: Clone; Clone)]
+    #[derive_where(T: PartialEq; PartialEq)]
+    #[derive_where(remote = foreign::Pair)]
+    struct Pair<T, U> {
+        a: T,
+        b: PhantomData<U>,
+    }
+
+    let pair = Pair(foreign::Pair::<u8, NotClone> {
+        a: 1,
+        b: PhantomData,
+    });
+    let clone = pair.clone();
+
+    assert_eq!(clone.0.a, 1);
+    assert!(pair == clone);
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(T: PartialEq; PartialEq)]
+    #[derive_where(T: PartialOrd; PartialOrd)]
+    #[derive_where(T: Hash; Hash)]
+    #[derive_where(remote = foreign::Either)]
+    enum Either<T> {
+        Left(T),
+        Right { value: T },
+    }
+
+    let left = Either(foreign::Either::Left(1));
+    let right = Either(foreign::Either::Right { value: 0 });
+
+    assert!(left != right);
+    assert_eq!(left.partial_cmp(&right), Some(Ordering::Less));
+    assert_ne!(hash(&left), hash(&right));
+    assert_eq!(hash(&left), hash(&Either(foreign::Either::Left(1))));
+}
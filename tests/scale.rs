@@ -0,0 +1,83 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+
+struct NotCodec;
+
+#[test]
+fn test_struct() {
+    #[derive_where(T: Encode; parity_scale_codec::Encode)]
+    #[derive_where(T: Decode; parity_scale_codec::Decode)]
+    #[derive_where(T: MaxEncodedLen; MaxEncodedLen)]
+    #[derive_where(T: std::fmt::Debug + PartialEq; Debug, PartialEq)]
+    struct Test<T, U> {
+        a: T,
+        b: u16,
+        #[derive_where(skip(parity_scale_codec::Encode, parity_scale_codec::Decode, PartialEq))]
+        cache: Option<u32>,
+        marker: PhantomData<U>,
+    }
+
+    let test = Test::<u8, NotCodec> {
+        a: 1,
+        b: 2,
+        cache: Some(3),
+        marker: PhantomData,
+    };
+    let encoded = test.encode();
+
+    assert_eq!(encoded, [1, 2, 0]);
+    assert_eq!(test.size_hint(), 3);
+    assert_eq!(Test::<u8, NotCodec>::max_encoded_len(), 3);
+
+    let decoded = Test::<u8, NotCodec>::decode(&mut &encoded[..]).unwrap();
+    assert_eq!(decoded, test);
+    assert_eq!(decoded.cache, None);
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(; parity_scale_codec::Encode, parity_scale_codec::Decode, MaxEncodedLen)]
+    #[derive_where(; Debug, PartialEq)]
+    #[repr(u8)]
+    enum Test {
+        A,
+        B(u32) = 5,
+        C { a: bool },
+    }
+
+    assert_eq!(Test::A.encode(), [0]);
+    assert_eq!(Test::B(1).encode(), [5, 1, 0, 0, 0]);
+    assert_eq!(Test::C { a: true }.encode(), [6, 1]);
+    assert_eq!(Test::max_encoded_len(), 5);
+
+    assert_eq!(
+        Test::decode(&mut &[6, 0][..]).unwrap(),
+        Test::C { a: false }
+    );
+    assert!(Test::decode(&mut &[1][..]).is_err());
+}
+
+#[test]
+fn test_index() {
+    const BIG: u16 = 257;
+
+    #[derive_where(; parity_scale_codec::Encode, parity_scale_codec::Decode, MaxEncodedLen)]
+    #[derive_where(; Debug, PartialEq)]
+    #[repr(u16)]
+    enum Test {
+        A = 1,
+        #[derive_where(index(2))]
+        B = BIG,
+        #[derive_where(index(3u8))]
+        C,
+    }
+
+    assert_eq!(Test::A.encode(), [1]);
+    assert_eq!(Test::B.encode(), [2]);
+    assert_eq!(Test::C.encode(), [3]);
+
+    assert_eq!(Test::decode(&mut &[2][..]).unwrap(), Test::B);
+    assert_eq!(Test::decode(&mut &[3][..]).unwrap(), Test::C);
+}
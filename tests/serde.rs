@@ -0,0 +1,157 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+use serde::{Deserialize, Serialize};
+
+struct NotSerde;
+
+#[test]
+fn test_bounds() {
+    #[derive_where(T: Serialize; Serialize)]
+    #[derive_where(T: Deserialize<'de>; Deserialize)]
+    #[derive(Serialize, Deserialize)]
+    struct Test<T, U> {
+        a: T,
+        #[serde(skip)]
+        b: PhantomData<U>,
+    }
+
+    let test = Test::<u8, NotSerde> {
+        a: 1,
+        b: PhantomData,
+    };
+    let json = serde_json::to_string(&test).unwrap();
+
+    assert_eq!(json, r#"{"a":1}"#);
+    assert_eq!(
+        serde_json::from_str::<Test<u8, NotSerde>>(&json).unwrap().a,
+        1
+    );
+}
+
+#[test]
+fn test_no_bounds() {
+    #[derive_where(; Clone, Serialize)]
+    #[derive(Serialize)]
+    struct Test<T>(PhantomData<T>);
+
+    let test = Test::<NotSerde>(PhantomData);
+
+    assert_eq!(serde_json::to_string(&test.clone()).unwrap(), "null");
+}
+
+#[test]
+fn test_skip() {
+    #[derive_where(; Debug, PartialEq, Serialize, Deserialize)]
+    #[derive_where(serde_skip)]
+    #[derive(Serialize, Deserialize)]
+    enum Test {
+        A {
+            a: u8,
+            #[derive_where(skip(PartialEq))]
+            cache: Option<u8>,
+        },
+        B(#[derive_where(skip(PartialEq))] u8, u8),
+    }
+
+    let a = Test::A {
+        a: 1,
+        cache: Some(2),
+    };
+    let json = serde_json::to_string(&a).unwrap();
+
+    assert_eq!(json, r#"{"A":{"a":1}}"#);
+    assert_eq!(serde_json::from_str::<Test>(&json).unwrap(), a);
+    assert_eq!(
+        serde_json::to_string(&Test::B(1, 2)).unwrap(),
+        r#"{"B":[2]}"#
+    );
+}
+
+#[test]
+fn test_rename() {
+    #[derive_where(T: Serialize; Serialize)]
+    #[derive_where(T: Deserialize<'de>; Deserialize)]
+    #[derive_where(rename_all = "camelCase")]
+    #[derive(Serialize, Deserialize)]
+    enum Test<T> {
+        #[derive_where(rename_all = "SCREAMING_SNAKE_CASE")]
+        FirstVariant {
+            first_field: T,
+            #[derive_where(rename = "other")]
+            second_field: u8,
+        },
+        #[derive_where(rename = "second")]
+        SecondVariant(T),
+    }
+
+    let test = Test::FirstVariant {
+        first_field: 1_u8,
+        second_field: 2,
+    };
+    let json = serde_json::to_string(&test).unwrap();
+
+    assert_eq!(json, r#"{"firstVariant":{"FIRST_FIELD":1,"other":2}}"#);
+    assert!(matches!(
+        serde_json::from_str::<Test<u8>>(&json).unwrap(),
+        Test::FirstVariant {
+            first_field: 1,
+            second_field: 2,
+        }
+    ));
+    assert_eq!(
+        serde_json::to_string(&Test::SecondVariant(3_u8)).unwrap(),
+        r#"{"second":3}"#
+    );
+}
+
+#[test]
+fn test_tagging() {
+    #[derive_where(; Serialize, Deserialize)]
+    #[derive_where(tag = "type")]
+    #[derive(Serialize, Deserialize)]
+    enum Internal {
+        A { a: u8 },
+        B,
+    }
+
+    #[derive_where(; Serialize, Deserialize)]
+    #[derive_where(tag = "t", content = "c")]
+    #[derive(Serialize, Deserialize)]
+    enum Adjacent {
+        A(u8),
+        B,
+    }
+
+    #[derive_where(; Serialize, Deserialize)]
+    #[derive_where(untagged)]
+    #[derive(Serialize, Deserialize)]
+    enum Untagged {
+        A(u8),
+        B(String),
+    }
+
+    let json = serde_json::to_string(&Internal::A { a: 1 }).unwrap();
+    assert_eq!(json, r#"{"type":"A","a":1}"#);
+    assert!(matches!(
+        serde_json::from_str(&json).unwrap(),
+        Internal::A { a: 1 }
+    ));
+    assert_eq!(
+        serde_json::to_string(&Internal::B).unwrap(),
+        r#"{"type":"B"}"#
+    );
+
+    let json = serde_json::to_string(&Adjacent::A(1)).unwrap();
+    assert_eq!(json, r#"{"t":"A","c":1}"#);
+    assert!(matches!(
+        serde_json::from_str(&json).unwrap(),
+        Adjacent::A(1)
+    ));
+
+    assert_eq!(serde_json::to_string(&Untagged::A(1)).unwrap(), "1");
+    assert!(matches!(
+        serde_json::from_str(r#""b""#).unwrap(),
+        Untagged::B(b) if b == "b"
+    ));
+}
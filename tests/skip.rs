@@ -0,0 +1,63 @@
+mod util;
+
+use derive_restricted::derive_where;
+use util::hash;
+
+#[test]
+fn test_debug() {
+    #[derive_where(; Debug)]
+    struct Test {
+        a: u8,
+        #[derive_where(skip(Debug))]
+        secret: &'static str,
+        c: u8,
+    }
+
+    let test = Test {
+        a: 1,
+        secret: "hunter2",
+        c: 2,
+    };
+
+    assert_eq!(format!("{:?}", test), "Test { a: 1, c: 2 }");
+    assert_eq!(test.secret, "hunter2");
+}
+
+#[test]
+fn test_comparisons() {
+    #[derive_where(; Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    #[derive_where(strict_skip)]
+    enum Test {
+        A {
+            a: u8,
+            #[derive_where(skip(Hash, Ord, PartialEq, PartialOrd))]
+            cache: usize,
+        },
+        B(#[derive_where(skip(Hash))] u8, u8),
+    }
+
+    let a = Test::A { a: 1, cache: 1 };
+    let b = Test::A { a: 1, cache: 2 };
+
+    assert!(a == b);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+    assert_eq!(hash(&a), hash(&b));
+
+    assert!(Test::B(1, 2) != Test::B(2, 2));
+    assert!(Test::B(1, 2) < Test::B(2, 2));
+    assert_eq!(hash(&Test::B(1, 2)), hash(&Test::B(2, 2)));
+}
+
+#[test]
+fn test_allow_inconsistent() {
+    #[derive_where(; Hash, PartialEq)]
+    #[derive_where(allow(inconsistent_skip))]
+    struct Test {
+        a: u8,
+        #[derive_where(skip(PartialEq))]
+        b: u8,
+    }
+
+    assert!(Test { a: 1, b: 1 } == Test { a: 1, b: 2 });
+    assert_ne!(hash(&Test { a: 1, b: 1 }), hash(&Test { a: 1, b: 2 }));
+}
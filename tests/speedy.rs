@@ -0,0 +1,85 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+use speedy::{Readable, Writable};
+
+#[test]
+fn test_struct() {
+    struct NotSpeedy;
+
+    #[derive_where(; Readable, Writable)]
+    struct Test<T> {
+        a: u32,
+        #[derive_where(skip(Readable, Writable))]
+        b: Vec<u8>,
+        c: String,
+        d: PhantomData<T>,
+    }
+
+    let test = Test::<NotSpeedy> {
+        a: 1,
+        b: vec![2],
+        c: String::from("c"),
+        d: PhantomData,
+    };
+    let bytes = test.write_to_vec().unwrap();
+    let read = Test::<NotSpeedy>::read_from_buffer(&bytes).unwrap();
+
+    assert_eq!(read.a, 1);
+    assert!(read.b.is_empty());
+    assert_eq!(read.c, "c");
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(; Debug, PartialEq, Readable, Writable)]
+    #[repr(u8)]
+    enum Test {
+        A,
+        B(u16, bool),
+        C { c: u8 } = 5,
+    }
+
+    #[derive(Writable)]
+    #[repr(u8)]
+    enum Speedy {
+        A,
+        B(u16, bool),
+        C { c: u8 } = 5,
+    }
+
+    for (test, speedy) in [
+        (Test::A, Speedy::A),
+        (Test::B(1, true), Speedy::B(1, true)),
+        (Test::C { c: 2 }, Speedy::C { c: 2 }),
+    ] {
+        let bytes = test.write_to_vec().unwrap();
+
+        assert_eq!(bytes, speedy.write_to_vec().unwrap());
+        assert_eq!(Test::read_from_buffer(&bytes).unwrap(), test);
+    }
+
+    assert!(Test::read_from_buffer(&4_u32.to_le_bytes()).is_err());
+}
+
+#[test]
+fn test_recursive() {
+    #[derive_where(; Readable, Writable)]
+    struct Node<T> {
+        value: T,
+        next: Option<Box<Self>>,
+    }
+
+    let node = Node {
+        value: 1_u8,
+        next: Some(Box::new(Node {
+            value: 2,
+            next: None,
+        })),
+    };
+    let bytes = node.write_to_vec().unwrap();
+    let read = Node::<u8>::read_from_buffer(&bytes).unwrap();
+
+    assert_eq!(read.value, 1);
+    assert_eq!(read.next.unwrap().value, 2);
+}
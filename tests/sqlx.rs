@@ -0,0 +1,62 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+use sqlx::{encode::IsNull, sqlite::SqliteArgumentValue, Database, Decode, Encode, Sqlite, Type};
+
+#[derive_where(; sqlx::Type, sqlx::Encode, sqlx::Decode)]
+struct Id<T>(#[derive_where(transparent)] i64, PhantomData<T>);
+
+/// Compiles only if `T` implements all traits for every database `i64` does.
+fn assert_traits<'a, DB: Database, T: Type<DB> + Encode<'a, DB> + Decode<'a, DB>>() {}
+
+#[allow(dead_code)]
+fn assert_id<'a, DB: Database, T>()
+where
+    i64: Type<DB> + Encode<'a, DB> + Decode<'a, DB>,
+{
+    assert_traits::<DB, Id<T>>();
+}
+
+#[test]
+fn test_type() {
+    struct NotType;
+
+    assert_eq!(
+        <Id<NotType> as Type<Sqlite>>::type_info(),
+        <i64 as Type<Sqlite>>::type_info()
+    );
+    assert!(<Id<NotType> as Type<Sqlite>>::compatible(&<i64 as Type<
+        Sqlite,
+    >>::type_info(
+    )));
+}
+
+#[test]
+fn test_encode() {
+    let mut buf = Vec::new();
+    let id = Id::<()>(42, PhantomData);
+
+    let null = Encode::<Sqlite>::encode_by_ref(&id, &mut buf).unwrap();
+
+    assert!(matches!(null, IsNull::No));
+    assert!(matches!(buf[..], [SqliteArgumentValue::Int64(42)]));
+}
+
+#[test]
+fn test_named() {
+    #[derive_where(; sqlx::Type, sqlx::Encode, sqlx::Decode)]
+    struct Name {
+        value: String,
+    }
+
+    let mut buf = Vec::new();
+    let name = Name {
+        value: String::from("name"),
+    };
+
+    let null = Encode::<Sqlite>::encode_by_ref(&name, &mut buf).unwrap();
+
+    assert!(matches!(null, IsNull::No));
+    assert!(matches!(&buf[..], [SqliteArgumentValue::Text(text)] if text == "name"));
+    assert_traits::<Sqlite, Name>();
+}
@@ -0,0 +1,40 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+use tabled::Tabled;
+
+struct NotTabled;
+
+#[test]
+fn test_struct() {
+    #[derive_where(T: std::fmt::Display; Tabled)]
+    struct Test<T, U> {
+        name: T,
+        #[derive_where(rename = "Tags", tabled(debug))]
+        tags: Vec<&'static str>,
+        #[derive_where(skip(Tabled))]
+        cache: Option<U>,
+        marker: PhantomData<U>,
+    }
+
+    let test = Test::<_, NotTabled> {
+        name: "test",
+        tags: vec!["a"],
+        cache: None,
+        marker: PhantomData,
+    };
+
+    assert_eq!(Test::<&str, NotTabled>::LENGTH, 2);
+    assert_eq!(Test::<&str, NotTabled>::headers(), ["name", "Tags"]);
+    assert_eq!(test.fields(), ["test", "[\"a\"]"]);
+    assert!(test.cache.is_none());
+}
+
+#[test]
+fn test_tuple() {
+    #[derive_where(; Tabled)]
+    struct Test(u8, #[derive_where(rename = "b")] bool);
+
+    assert_eq!(Test::headers(), ["0", "b"]);
+    assert_eq!(Test(1, true).fields(), ["1", "true"]);
+}
@@ -0,0 +1,7 @@
+#[test]
+fn test_ui() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "parity-scale-codec")]
+    tests.compile_fail("tests/ui/scale/*.rs");
+}
@@ -0,0 +1,8 @@
+use std::rc::Rc;
+
+use derive_restricted::derive_where;
+
+#[derive_where(assert(Send))]
+struct Test(Rc<u8>);
+
+fn main() {}
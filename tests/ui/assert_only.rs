@@ -0,0 +1,13 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; Clone)]
+#[derive_where(assert_only)]
+struct Test<T>(T);
+
+impl<T> Clone for Test<T> {
+    fn clone(&self) -> Self {
+        unimplemented!()
+    }
+}
+
+fn main() {}
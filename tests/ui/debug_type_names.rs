@@ -0,0 +1,9 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; Debug)]
+#[derive_where(debug_discriminant, debug_type_names, msrv = "1.37")]
+enum Test {
+    A,
+}
+
+fn main() {}
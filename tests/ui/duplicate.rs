@@ -0,0 +1,6 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; Clone, Clone)]
+struct Test;
+
+fn main() {}
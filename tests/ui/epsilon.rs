@@ -0,0 +1,9 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; PartialEq, PartialOrd)]
+struct Test {
+    #[derive_where(epsilon = 1e-9)]
+    a: f64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; Eq, Ord, PartialEq, PartialOrd)]
+enum Test {
+    A,
+    #[derive_where(incomparable)]
+    B,
+}
+
+fn main() {}
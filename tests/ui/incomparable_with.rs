@@ -0,0 +1,10 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; PartialEq, PartialOrd)]
+enum Test {
+    #[derive_where(incomparable_with(C))]
+    A,
+    B,
+}
+
+fn main() {}
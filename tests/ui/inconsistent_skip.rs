@@ -0,0 +1,11 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; Hash, PartialEq)]
+#[derive_where(strict_skip)]
+struct Test {
+    a: u8,
+    #[derive_where(skip(PartialEq))]
+    b: u8,
+}
+
+fn main() {}
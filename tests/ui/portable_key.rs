@@ -0,0 +1,7 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; PartialEq, Eq, Hash(portable))]
+#[derive_where(key = self.0)]
+struct Test(u8, u8);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; parity_scale_codec::Encode)]
+enum Test {
+    A,
+    #[derive_where(index(0))]
+    B,
+}
+
+fn main() {}
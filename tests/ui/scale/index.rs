@@ -0,0 +1,9 @@
+use derive_restricted::derive_where;
+
+#[derive_where(; parity_scale_codec::Encode)]
+enum Test {
+    #[derive_where(index(256))]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use derive_restricted::derive_where;
+
+const BIG: u16 = 257;
+
+#[derive_where(; parity_scale_codec::Encode)]
+#[repr(u16)]
+enum Test {
+    A = 1,
+    B = BIG,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use derive_restricted::derive_where;
+
+#[derive_where(T: Clone; Clone)]
+#[derive(Clone)]
+struct Test<T>(T);
+
+fn main() {}
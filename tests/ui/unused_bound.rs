@@ -0,0 +1,10 @@
+#![deny(deprecated)]
+
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+
+#[derive_where(T: Clone; Clone)]
+struct Test<T>(PhantomData<T>);
+
+fn main() {}
@@ -0,0 +1,40 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use derive_restricted::derive_where;
+
+enum Void {}
+
+#[test]
+fn test_uninhabited() {
+    #[derive_where(; Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[allow(dead_code)]
+    enum Message<T> {
+        Data(u8, PhantomData<T>),
+        Unsupported(#[derive_where(uninhabited)] Void, T),
+        Never {
+            #[derive_where(uninhabited)]
+            void: Void,
+        },
+    }
+
+    let message = Message::<Void>::Data(1, PhantomData);
+
+    assert_eq!(
+        format!("{:?}", message.clone()),
+        "Data(1, PhantomData<uninhabited::Void>)"
+    );
+    assert!(message == Message::Data(1, PhantomData));
+    assert!(message < Message::Data(2, PhantomData));
+
+    let hash = |message: &Message<Void>| {
+        let mut hasher = DefaultHasher::new();
+        message.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    assert_eq!(hash(&message), hash(&Message::Data(1, PhantomData)));
+}
@@ -0,0 +1,60 @@
+use derive_restricted::derive_where;
+
+#[test]
+fn test_names() {
+    #[derive_where(T: Clone; Clone)]
+    #[derive_where(variant_names)]
+    enum Test<T> {
+        A,
+        B(T),
+        r#C { c: T },
+    }
+
+    assert_eq!(Test::<u8>::VARIANTS, ["A", "B", "C"]);
+    assert_eq!(Test::<u8>::A.variant_name(), "A");
+    assert_eq!(Test::B(1).variant_name(), "B");
+    assert_eq!(Test::C { c: 1 }.clone().variant_name(), "C");
+}
+
+#[test]
+fn test_iter() {
+    #[derive_where(; PartialEq)]
+    #[derive_where(variant_names, variant_iter)]
+    enum Test {
+        A,
+        B = 5,
+    }
+
+    assert!(Test::variants().eq([Test::A, Test::B]));
+    assert_eq!(
+        Test::variants()
+            .map(|variant| variant.variant_name())
+            .collect::<Vec<_>>(),
+        Test::VARIANTS
+    );
+}
+
+#[test]
+fn test_accessors() {
+    #[derive_where(accessors)]
+    enum Test<T> {
+        Unit,
+        Single(T),
+        HTTPPair { a: T, b: u8 },
+    }
+
+    let single = Test::Single(String::from("a"));
+    let pair = Test::<String>::HTTPPair {
+        a: String::from("b"),
+        b: 1,
+    };
+
+    assert!(Test::<u8>::Unit.is_unit());
+    assert!(!single.is_unit());
+    assert!(single.is_single());
+    assert_eq!(single.as_single(), Some(&String::from("a")));
+    assert_eq!(single.as_http_pair(), None);
+    assert_eq!(pair.as_http_pair(), Some((&String::from("b"), &1)));
+    assert_eq!(single.into_single().ok(), Some(String::from("a")));
+    assert!(pair.into_single().is_err());
+}
@@ -0,0 +1,88 @@
+mod util;
+
+use std::cmp::Ordering;
+
+use derive_restricted::derive_where;
+use util::hash;
+
+mod case_insensitive {
+    use std::{
+        cmp::Ordering,
+        fmt,
+        hash::{Hash, Hasher},
+    };
+
+    pub fn clone(value: &str) -> String {
+        value.to_ascii_lowercase()
+    }
+
+    pub fn eq(a: &str, b: &str) -> bool {
+        a.eq_ignore_ascii_case(b)
+    }
+
+    pub fn hash<H: Hasher>(value: &str, state: &mut H) {
+        value.to_ascii_lowercase().hash(state);
+    }
+
+    pub fn fmt(value: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}i", value.to_ascii_lowercase())
+    }
+
+    pub fn cmp(a: &str, b: &str) -> Ordering {
+        a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase())
+    }
+}
+
+mod saturating {
+    pub fn clone(value: &u8) -> u8 {
+        value.saturating_add(1)
+    }
+}
+
+#[test]
+fn test_with() {
+    #[derive_where(; Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    struct Test {
+        #[derive_where(with = case_insensitive)]
+        name: String,
+        id: u8,
+    }
+
+    let test = |name: &str, id| Test {
+        name: name.to_owned(),
+        id,
+    };
+
+    assert_eq!(test("Abc", 1).clone().name, "abc");
+    assert_eq!(
+        format!("{:?}", test("Abc", 1)),
+        r#"Test { name: "abc"i, id: 1 }"#
+    );
+    assert!(test("Abc", 1) == test("aBC", 1));
+    assert!(test("Abc", 1) != test("aBC", 2));
+    assert_eq!(hash(&test("Abc", 1)), hash(&test("aBC", 1)));
+    assert_eq!(test("B", 1).cmp(&test("a", 2)), Ordering::Greater);
+    assert_eq!(
+        test("A", 2).partial_cmp(&test("a", 1)),
+        Some(Ordering::Greater)
+    );
+
+    let mut target = test("x", 0);
+    target.clone_from(&test("Abc", 1));
+    assert_eq!(target.name, "abc");
+}
+
+#[test]
+fn test_copy() {
+    #[derive_where(; Clone, Copy)]
+    struct Test {
+        #[derive_where(with = saturating)]
+        count: u8,
+    }
+
+    let test = Test { count: 1 };
+    let copied = test;
+
+    assert_eq!(copied.count, 1);
+    assert_eq!(Clone::clone(&test).count, 2);
+}
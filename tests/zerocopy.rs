@@ -0,0 +1,58 @@
+use std::marker::PhantomData;
+
+use derive_restricted::derive_where;
+use zerocopy::{FromBytes, FromZeros, Immutable, IntoBytes, KnownLayout};
+
+#[test]
+fn test_wire() {
+    #[derive_where(; FromBytes, IntoBytes)]
+    #[derive(Immutable, KnownLayout)]
+    #[repr(C, packed)]
+    struct Header {
+        kind: u8,
+        len: u16,
+        checksum: u32,
+    }
+
+    let header = Header {
+        kind: 1,
+        len: 0x0203,
+        checksum: 0x0405_0607,
+    };
+
+    assert_eq!(header.as_bytes(), [1, 3, 2, 7, 6, 5, 4]);
+
+    let header = Header::read_from_bytes(header.as_bytes()).unwrap();
+    assert_eq!({ header.len }, 0x0203);
+    assert_eq!({ header.checksum }, 0x0405_0607);
+
+    assert_eq!(Header::new_zeroed().as_bytes(), [0; 7]);
+}
+
+#[test]
+fn test_bounds() {
+    #[derive_where(T: FromBytes + IntoBytes + Immutable; FromBytes, IntoBytes)]
+    #[derive(Immutable)]
+    #[repr(transparent)]
+    struct Test<T, U> {
+        a: T,
+        marker: PhantomData<U>,
+    }
+
+    struct NotBytes;
+
+    let test = Test::<u32, NotBytes>::read_from_bytes(&[1, 0, 0, 0]).unwrap();
+    assert_eq!(test.a, u32::from_ne_bytes([1, 0, 0, 0]));
+    assert_eq!(test.as_bytes(), [1, 0, 0, 0]);
+}
+
+#[test]
+fn test_cfg() {
+    #[derive_where(cfg(all()); FromBytes)]
+    #[repr(C)]
+    struct Test {
+        a: [u8; 2],
+    }
+
+    assert_eq!(Test::read_from_bytes(&[1, 2]).unwrap().a, [1, 2]);
+}
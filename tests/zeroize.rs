@@ -0,0 +1,91 @@
+use derive_restricted::derive_where;
+use zeroize::Zeroize;
+
+mod framework {
+    pub use ::zeroize as secrets;
+}
+
+fn zero_len(value: &mut usize) {
+    *value = 0;
+}
+
+#[test]
+fn test_struct() {
+    #[derive_where(; Zeroize)]
+    struct Test {
+        a: [u8; 2],
+        #[derive_where(skip(Zeroize))]
+        b: u8,
+    }
+
+    let mut test = Test { a: [1, 2], b: 3 };
+    test.zeroize();
+
+    assert_eq!(test.a, [0, 0]);
+    assert_eq!(test.b, 3);
+}
+
+#[test]
+fn test_enum() {
+    #[derive_where(T: Zeroize; zeroize::Zeroize)]
+    enum Test<T> {
+        A(T, #[derive_where(skip(Zeroize))] &'static str),
+        B {
+            #[derive_where(zeroize(with = zero_len))]
+            len: usize,
+            data: T,
+        },
+        C,
+    }
+
+    let mut test = Test::A(1_u8, "a");
+    test.zeroize();
+    assert!(matches!(test, Test::A(0, "a")));
+
+    let mut test = Test::B { len: 2, data: 3_u8 };
+    test.zeroize();
+    assert!(matches!(test, Test::B { len: 0, data: 0 }));
+
+    let mut test = Test::<u8>::C;
+    test.zeroize();
+    assert!(matches!(test, Test::C));
+}
+
+#[test]
+fn test_heap() {
+    #[derive_where(; Zeroize)]
+    struct Test {
+        in_place: Vec<u8>,
+        #[derive_where(zeroize(shrink))]
+        shrink: String,
+        #[derive_where(zeroize(replace))]
+        replace: Box<[u8]>,
+    }
+
+    let mut test = Test {
+        in_place: Vec::with_capacity(8),
+        shrink: String::with_capacity(8),
+        replace: Box::new([1, 2]),
+    };
+    test.in_place.push(1);
+    test.shrink.push('a');
+    test.zeroize();
+
+    assert!(test.in_place.is_empty());
+    assert!(test.in_place.capacity() >= 8);
+    assert!(test.shrink.is_empty());
+    assert_eq!(test.shrink.capacity(), 0);
+    assert!(test.replace.is_empty());
+}
+
+#[test]
+fn test_crate_path() {
+    #[derive_where(; Zeroize)]
+    #[derive_where(zeroize_crate = framework::secrets)]
+    struct Test([u8; 2]);
+
+    let mut test = Test([1, 2]);
+    framework::secrets::Zeroize::zeroize(&mut test);
+
+    assert_eq!(test.0, [0, 0]);
+}